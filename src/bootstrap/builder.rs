@@ -1044,6 +1044,13 @@ impl<'a> Builder<'a> {
             rustflags.arg("-Wrust_2018_idioms");
             rustflags.arg("-Wunused_lifetimes");
 
+            // Helpers nested in the compiler and the standard library redeclare the generic
+            // parameters of the items around them, `'tcx` most of all. The stage0 compiler
+            // doesn't know the lint yet.
+            if stage != 0 {
+                rustflags.arg("-Ashadowed_generic_params");
+            }
+
             if self.config.deny_warnings {
                 rustflags.arg("-Dwarnings");
             }
@@ -170,6 +170,7 @@ impl<T> Box<T> {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     #[inline(always)]
+    #[cfg_attr(not(test), rustc_diagnostic_item = "box_new")]
     pub fn new(x: T) -> Box<T> {
         box x
     }
@@ -366,6 +366,7 @@ fn panic_safe() {
         }
     }
 
+    #[allow(inconsistent_trait_impls)]
     impl<T: PartialOrd> PartialOrd for PanicOrd<T> {
         fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
             if self.1 || other.1 {
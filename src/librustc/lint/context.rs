@@ -21,6 +21,7 @@ use crate::lint::levels::{LintLevelSets, LintLevelsBuilder};
 use crate::lint::{EarlyLintPassObject, LateLintPassObject};
 use crate::middle::privacy::AccessLevels;
 use crate::middle::stability;
+use crate::session::config::CrateType;
 use crate::session::Session;
 use crate::ty::layout::{LayoutError, LayoutOf, TyLayout};
 use crate::ty::{self, print::Printer, subst::GenericArg, Ty, TyCtxt};
@@ -32,10 +33,12 @@ use rustc_hir as hir;
 use rustc_hir::def_id::{CrateNum, DefId};
 use rustc_session::lint::BuiltinLintDiagnostics;
 use rustc_session::lint::{FutureIncompatibleInfo, Level, Lint, LintBuffer, LintId};
+use rustc_span::edition::Edition;
 use rustc_span::{symbol::Symbol, MultiSpan, Span, DUMMY_SP};
 use syntax::ast;
 use syntax::util::lev_distance::find_best_match_for_name;
 
+use std::fmt;
 use std::slice;
 
 /// Information about the registered lints.
@@ -63,6 +66,36 @@ pub struct LintStore {
 
     /// Map of registered lint groups to what lints they expand to.
     lint_groups: FxHashMap<&'static str, LintGroup>,
+
+    /// The path of the plugin that registered each plugin lint and lint group, by name.
+    plugin_paths: FxHashMap<String, String>,
+
+    /// The path of the plugin registering lints at the moment, see `set_registering_plugin`.
+    registering_plugin: Option<String>,
+}
+
+/// Where a lint or lint group comes from, as listed by `-W help` and `--print lints-json`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintProvenance {
+    /// Built into the compiler.
+    Rustc,
+    /// Registered by the plugin at this path.
+    Plugin(String),
+    /// A `tool::name` lint, registered for the tool of that name.
+    Tool(String),
+    /// Registered by the driver running the compiler.
+    Driver,
+}
+
+impl fmt::Display for LintProvenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LintProvenance::Rustc => write!(f, "rustc"),
+            LintProvenance::Plugin(path) => write!(f, "the plugin `{}`", path),
+            LintProvenance::Tool(tool) => write!(f, "the tool `{}`", tool),
+            LintProvenance::Driver => write!(f, "the driver"),
+        }
+    }
 }
 
 /// The target of the `by_name` map, which accounts for renaming/deprecation.
@@ -120,6 +153,8 @@ impl LintStore {
             late_module_passes: vec![],
             by_name: Default::default(),
             lint_groups: Default::default(),
+            plugin_paths: Default::default(),
+            registering_plugin: None,
         }
     }
 
@@ -140,6 +175,61 @@ impl LintStore {
             .collect()
     }
 
+    /// The old names of renamed lints, along with their new names. This includes the deprecated
+    /// names of lint groups.
+    pub fn get_renamed_lints(&self) -> Vec<(&str, &str)> {
+        let renamed_lints = self.by_name.iter().filter_map(|(old_name, target)| match target {
+            Renamed(new_name, _) => Some((&old_name[..], &new_name[..])),
+            _ => None,
+        });
+        let renamed_groups =
+            self.lint_groups.iter().filter_map(|(&old_name, group)| match group.depr {
+                Some(LintAlias { name, silent: false }) => Some((old_name, name)),
+                _ => None,
+            });
+        renamed_lints.chain(renamed_groups).collect()
+    }
+
+    /// The names of removed lints, along with the reason for their removal.
+    pub fn get_removed_lints(&self) -> Vec<(&str, &str)> {
+        self.by_name
+            .iter()
+            .filter_map(|(name, target)| match target {
+                Removed(reason) => Some((&name[..], &reason[..])),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Records the lints and lint groups registered from now on as coming from the plugin at
+    /// `path`, or from no plugin if `None`.
+    pub fn set_registering_plugin(&mut self, path: Option<String>) {
+        self.registering_plugin = path;
+    }
+
+    /// Where the lint comes from.
+    pub fn lint_provenance(&self, lint: &'static Lint) -> LintProvenance {
+        self.provenance(&lint.name_lower(), lint.is_plugin)
+    }
+
+    /// Where the lint group `name` comes from.
+    pub fn group_provenance(&self, name: &str) -> LintProvenance {
+        let from_plugin = self.lint_groups.get(name).map_or(false, |group| group.from_plugin);
+        self.provenance(name, from_plugin)
+    }
+
+    fn provenance(&self, name: &str, from_plugin: bool) -> LintProvenance {
+        if let Some(path) = self.plugin_paths.get(name) {
+            LintProvenance::Plugin(path.clone())
+        } else if let Some(sep) = name.find("::") {
+            LintProvenance::Tool(name[..sep].to_string())
+        } else if from_plugin {
+            LintProvenance::Driver
+        } else {
+            LintProvenance::Rustc
+        }
+    }
+
     pub fn register_early_pass(
         &mut self,
         pass: impl Fn() -> EarlyLintPassObject + 'static + sync::Send + sync::Sync,
@@ -177,6 +267,9 @@ impl LintStore {
             if self.by_name.insert(lint.name_lower(), Id(id)).is_some() {
                 bug!("duplicate specification of lint {}", lint.name_lower())
             }
+            if let Some(path) = &self.registering_plugin {
+                self.plugin_paths.insert(lint.name_lower(), path.clone());
+            }
 
             if let Some(FutureIncompatibleInfo { edition, .. }) = lint.future_incompatible {
                 if let Some(edition) = edition {
@@ -240,6 +333,9 @@ impl LintStore {
         if !new {
             bug!("duplicate specification of lint group {}", name);
         }
+        if let Some(path) = &self.registering_plugin {
+            self.plugin_paths.insert(name.to_string(), path.clone());
+        }
     }
 
     pub fn register_renamed(&mut self, old_name: &str, new_name: &str) {
@@ -276,7 +372,60 @@ impl LintStore {
 
     /// Checks the validity of lint names derived from the command line
     pub fn check_lint_name_cmdline(&self, sess: &Session, lint_name: &str, level: Level) {
-        let db = match self.check_lint_name(lint_name, None) {
+        if let Some(mut db) = self.check_unattached_lint_name(sess, lint_name) {
+            let msg = format!(
+                "requested on the command line with `{} {}`",
+                match level {
+                    Level::Allow => "-A",
+                    Level::Warn => "-W",
+                    Level::Deny => "-D",
+                    Level::Forbid => "-F",
+                },
+                lint_name
+            );
+            db.note(&msg);
+            db.emit();
+        }
+    }
+
+    /// Checks the validity of lint names read from the environment variable `var`, as
+    /// requested with `--deny-lints-from-env`.
+    pub fn check_lint_name_from_env(&self, sess: &Session, lint_name: &str, var: &str) {
+        if let Some(mut db) = self.check_unattached_lint_name(sess, lint_name) {
+            db.note(&format!("requested by the `{}` environment variable", var));
+            db.emit();
+        }
+    }
+
+    /// Checks the validity of lint names from the file given to `--lint-spec`. The lints of tools
+    /// that aren't loaded are left for the tool to check, as they are in attributes.
+    pub fn check_lint_name_from_spec(
+        &self,
+        sess: &Session,
+        lint_name: &str,
+        tool_name: Option<Symbol>,
+    ) {
+        let db = match tool_name {
+            Some(tool_name) => match self.check_lint_name(lint_name, Some(tool_name)) {
+                CheckLintNameResult::Tool(Err((None, _))) => None,
+                _ => {
+                    self.check_unattached_lint_name(sess, &format!("{}::{}", tool_name, lint_name))
+                }
+            },
+            None => self.check_unattached_lint_name(sess, lint_name),
+        };
+        if let Some(mut db) = db {
+            db.note("requested in the file passed to `--lint-spec`");
+            db.emit();
+        }
+    }
+
+    fn check_unattached_lint_name<'s>(
+        &self,
+        sess: &'s Session,
+        lint_name: &str,
+    ) -> Option<DiagnosticBuilder<'s>> {
+        match self.check_lint_name(lint_name, None) {
             CheckLintNameResult::Ok(_) => None,
             CheckLintNameResult::Warning(ref msg, _) => Some(sess.struct_warn(msg)),
             CheckLintNameResult::NoLint(suggestion) => {
@@ -298,21 +447,6 @@ impl LintStore {
                 ))),
                 _ => None,
             },
-        };
-
-        if let Some(mut db) = db {
-            let msg = format!(
-                "requested on the command line with `{} {}`",
-                match level {
-                    Level::Allow => "-A",
-                    Level::Warn => "-W",
-                    Level::Deny => "-D",
-                    Level::Forbid => "-F",
-                },
-                lint_name
-            );
-            db.note(&msg);
-            db.emit();
         }
     }
 
@@ -387,15 +521,7 @@ impl LintStore {
         match self.by_name.get(&complete_name) {
             None => match self.lint_groups.get(&*complete_name) {
                 // Now we are sure, that this lint exists nowhere
-                None => {
-                    let symbols =
-                        self.by_name.keys().map(|name| Symbol::intern(&name)).collect::<Vec<_>>();
-
-                    let suggestion =
-                        find_best_match_for_name(symbols.iter(), &lint_name.to_lowercase(), None);
-
-                    CheckLintNameResult::NoLint(suggestion)
-                }
+                None => CheckLintNameResult::NoLint(self.suggest_lint_name(lint_name)),
                 Some(LintGroup { lint_ids, depr, .. }) => {
                     // Reaching this would be weird, but let's cover this case anyway
                     if let Some(LintAlias { name, silent }) = depr {
@@ -415,6 +541,36 @@ impl LintStore {
             _ => CheckLintNameResult::NoLint(None),
         }
     }
+
+    /// Finds the registered lint or lint group that `lint_name` most likely meant. Tool lints
+    /// are candidates too, so `needless_return` finds `clippy::needless_return` when the tool's
+    /// lints are registered.
+    fn suggest_lint_name(&self, lint_name: &str) -> Option<Symbol> {
+        let lint_name = lint_name.to_lowercase().replace('-', "_");
+        let lints = self.by_name.iter().filter_map(|(name, target)| match target {
+            Id(_) => Some(&name[..]),
+            Renamed(..) | Removed(_) => None,
+        });
+        let groups = self
+            .lint_groups
+            .iter()
+            .filter(|(_, group)| group.depr.is_none())
+            .map(|(&name, _)| name);
+        let candidates = lints.chain(groups).collect::<Vec<_>>();
+
+        // Misspellings that only differ in case and dashes, or that leave out the tool name,
+        // are not always close enough for the edit distance below.
+        let exact = candidates.iter().find(|&&name| {
+            name == lint_name
+                || name.find("::").map_or(false, |pos| name[pos + 2..] == lint_name[..])
+        });
+        if let Some(name) = exact {
+            return Some(Symbol::intern(name));
+        }
+
+        let symbols = candidates.iter().map(|name| Symbol::intern(name)).collect::<Vec<_>>();
+        find_best_match_for_name(symbols.iter(), &lint_name, None)
+    }
 }
 
 /// Context for lint checking after type checking.
@@ -459,6 +615,12 @@ pub struct EarlyContext<'a> {
     pub lint_store: &'a LintStore,
 
     pub buffered: LintBuffer,
+
+    crate_name: Symbol,
+
+    /// Whether this is the run before macro expansion, whose lints are recorded so that the
+    /// same lints buffered for the run after expansion aren't reported again.
+    pre_expansion: bool,
 }
 
 pub trait LintPassObject: Sized {}
@@ -667,17 +829,56 @@ impl<'a> EarlyContext<'a> {
         sess: &'a Session,
         lint_store: &'a LintStore,
         krate: &'a ast::Crate,
+        crate_name: &str,
         buffered: LintBuffer,
-        warn_about_weird_lints: bool,
+        pre_expansion: bool,
     ) -> EarlyContext<'a> {
         EarlyContext {
             sess,
             krate,
             lint_store,
-            builder: LintLevelSets::builder(sess, warn_about_weird_lints, lint_store),
+            // Unknown lints would otherwise be reported by both runs.
+            builder: LintLevelSets::builder(sess, pre_expansion, lint_store),
             buffered,
+            crate_name: Symbol::intern(crate_name),
+            pre_expansion,
         }
     }
+
+    /// Records that `lint` is being reported at the current node, returning `false` if the
+    /// same lint was already reported with the same primary span and message, by this early lint
+    /// run or the other one. Lints that are allowed here aren't recorded, so that they don't
+    /// hide the same lint reported where it isn't.
+    pub fn mark_lint_emitted(
+        &self,
+        lint: &'static Lint,
+        span: Option<&MultiSpan>,
+        msg: &str,
+    ) -> bool {
+        if self.builder.lint_level(lint).0 == Level::Allow {
+            return true;
+        }
+        self.sess.mark_early_lint_emitted(
+            LintId::of(lint),
+            span.and_then(|span| span.primary_span()),
+            msg,
+        )
+    }
+
+    /// The edition of the crate being checked.
+    pub fn edition(&self) -> Edition {
+        self.sess.edition()
+    }
+
+    /// The name of the crate being checked, as `tcx.crate_name(LOCAL_CRATE)` would give it.
+    pub fn crate_name(&self) -> Symbol {
+        self.crate_name
+    }
+
+    /// The types of crate being built, from `--crate-type` and `#![crate_type]`.
+    pub fn crate_types(&self) -> &[CrateType] {
+        self.sess.crate_types.borrow()
+    }
 }
 
 impl LintContext for LateContext<'_, '_> {
@@ -725,7 +926,11 @@ impl LintContext for EarlyContext<'_> {
         span: Option<S>,
         msg: &str,
     ) -> DiagnosticBuilder<'_> {
-        self.builder.struct_lint(lint, span.map(|s| s.into()), msg)
+        let span = span.map(|s| s.into());
+        if self.pre_expansion {
+            self.mark_lint_emitted(lint, span.as_ref(), msg);
+        }
+        self.builder.struct_lint(lint, span, msg)
     }
 }
 
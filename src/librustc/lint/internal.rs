@@ -1,7 +1,7 @@
 //! Some lints that are only useful in the compiler or crates that use compiler internals, such as
 //! Clippy.
 
-use crate::lint::{EarlyContext, EarlyLintPass, LateContext, LateLintPass, LintContext};
+use crate::lint::{EarlyContext, LateContext, LintContext};
 use rustc_data_structures::fx::FxHashMap;
 use rustc_errors::Applicability;
 use rustc_hir::{GenericArg, HirId, MutTy, Mutability, Path, PathSegment, QPath, Ty, TyKind};
@@ -32,19 +32,21 @@ impl DefaultHashTypes {
 
 impl_lint_pass!(DefaultHashTypes => [DEFAULT_HASH_TYPES]);
 
-impl EarlyLintPass for DefaultHashTypes {
-    fn check_ident(&mut self, cx: &EarlyContext<'_>, ident: Ident) {
-        if let Some(replace) = self.map.get(&ident.name) {
-            let msg = format!("Prefer {} over {}, it has better performance", replace, ident);
-            let mut db = cx.struct_span_lint(DEFAULT_HASH_TYPES, ident.span, &msg);
-            db.span_suggestion(
-                ident.span,
-                "use",
-                replace.to_string(),
-                Applicability::MaybeIncorrect, // FxHashMap, ... needs another import
-            );
-            db.note(&format!("a `use rustc_data_structures::fx::{}` may be necessary", replace))
-                .emit();
+impl_lint_pass_callbacks! {
+    impl EarlyLintPass for DefaultHashTypes {
+        fn check_ident(&mut self, cx: &EarlyContext<'_>, ident: Ident) {
+            if let Some(replace) = self.map.get(&ident.name) {
+                let msg = format!("Prefer {} over {}, it has better performance", replace, ident);
+                let mut db = cx.struct_span_lint(DEFAULT_HASH_TYPES, ident.span, &msg);
+                db.span_suggestion(
+                    ident.span,
+                    "use",
+                    replace.to_string(),
+                    Applicability::MaybeIncorrect, // FxHashMap, ... needs another import
+                );
+                db.note(&format!("a `use rustc_data_structures::fx::{}` may be necessary", replace))
+                    .emit();
+            }
         }
     }
 }
@@ -73,86 +75,88 @@ declare_lint_pass!(TyTyKind => [
     USAGE_OF_QUALIFIED_TY,
 ]);
 
-impl<'a, 'tcx> LateLintPass<'a, 'tcx> for TyTyKind {
-    fn check_path(&mut self, cx: &LateContext<'_, '_>, path: &'tcx Path<'tcx>, _: HirId) {
-        let segments = path.segments.iter().rev().skip(1).rev();
-
-        if let Some(last) = segments.last() {
-            let span = path.span.with_hi(last.ident.span.hi());
-            if lint_ty_kind_usage(cx, last) {
-                cx.struct_span_lint(USAGE_OF_TY_TYKIND, span, "usage of `ty::TyKind::<kind>`")
-                    .span_suggestion(
-                        span,
-                        "try using ty::<kind> directly",
-                        "ty".to_string(),
-                        Applicability::MaybeIncorrect, // ty maybe needs an import
-                    )
-                    .emit();
+impl_lint_pass_callbacks! {
+    impl<'a, 'tcx> LateLintPass<'a, 'tcx> for TyTyKind {
+        fn check_path(&mut self, cx: &LateContext<'_, '_>, path: &'tcx Path<'tcx>, _: HirId) {
+            let segments = path.segments.iter().rev().skip(1).rev();
+
+            if let Some(last) = segments.last() {
+                let span = path.span.with_hi(last.ident.span.hi());
+                if lint_ty_kind_usage(cx, last) {
+                    cx.struct_span_lint(USAGE_OF_TY_TYKIND, span, "usage of `ty::TyKind::<kind>`")
+                        .span_suggestion(
+                            span,
+                            "try using ty::<kind> directly",
+                            "ty".to_string(),
+                            Applicability::MaybeIncorrect, // ty maybe needs an import
+                        )
+                        .emit();
+                }
             }
         }
-    }
 
-    fn check_ty(&mut self, cx: &LateContext<'_, '_>, ty: &'tcx Ty<'tcx>) {
-        match &ty.kind {
-            TyKind::Path(qpath) => {
-                if let QPath::Resolved(_, path) = qpath {
-                    if let Some(last) = path.segments.iter().last() {
-                        if lint_ty_kind_usage(cx, last) {
-                            cx.struct_span_lint(
-                                USAGE_OF_TY_TYKIND,
-                                path.span,
-                                "usage of `ty::TyKind`",
-                            )
-                            .help("try using `Ty` instead")
-                            .emit();
-                        } else {
-                            if ty.span.from_expansion() {
-                                return;
-                            }
-                            if let Some(t) = is_ty_or_ty_ctxt(cx, ty) {
-                                if path.segments.len() > 1 {
-                                    cx.struct_span_lint(
-                                        USAGE_OF_QUALIFIED_TY,
-                                        path.span,
-                                        &format!("usage of qualified `ty::{}`", t),
-                                    )
-                                    .span_suggestion(
-                                        path.span,
-                                        "try using it unqualified",
-                                        t,
-                                        // The import probably needs to be changed
-                                        Applicability::MaybeIncorrect,
-                                    )
-                                    .emit();
+        fn check_ty(&mut self, cx: &LateContext<'_, '_>, ty: &'tcx Ty<'tcx>) {
+            match &ty.kind {
+                TyKind::Path(qpath) => {
+                    if let QPath::Resolved(_, path) = qpath {
+                        if let Some(last) = path.segments.iter().last() {
+                            if lint_ty_kind_usage(cx, last) {
+                                cx.struct_span_lint(
+                                    USAGE_OF_TY_TYKIND,
+                                    path.span,
+                                    "usage of `ty::TyKind`",
+                                )
+                                .help("try using `Ty` instead")
+                                .emit();
+                            } else {
+                                if ty.span.from_expansion() {
+                                    return;
+                                }
+                                if let Some(t) = is_ty_or_ty_ctxt(cx, ty) {
+                                    if path.segments.len() > 1 {
+                                        cx.struct_span_lint(
+                                            USAGE_OF_QUALIFIED_TY,
+                                            path.span,
+                                            &format!("usage of qualified `ty::{}`", t),
+                                        )
+                                        .span_suggestion(
+                                            path.span,
+                                            "try using it unqualified",
+                                            t,
+                                            // The import probably needs to be changed
+                                            Applicability::MaybeIncorrect,
+                                        )
+                                        .emit();
+                                    }
                                 }
                             }
                         }
                     }
                 }
-            }
-            TyKind::Rptr(_, MutTy { ty: inner_ty, mutbl: Mutability::Not }) => {
-                if let Some(impl_did) = cx.tcx.impl_of_method(ty.hir_id.owner_def_id()) {
-                    if cx.tcx.impl_trait_ref(impl_did).is_some() {
-                        return;
+                TyKind::Rptr(_, MutTy { ty: inner_ty, mutbl: Mutability::Not }) => {
+                    if let Some(impl_did) = cx.tcx.impl_of_method(ty.hir_id.owner_def_id()) {
+                        if cx.tcx.impl_trait_ref(impl_did).is_some() {
+                            return;
+                        }
+                    }
+                    if let Some(t) = is_ty_or_ty_ctxt(cx, &inner_ty) {
+                        cx.struct_span_lint(
+                            TY_PASS_BY_REFERENCE,
+                            ty.span,
+                            &format!("passing `{}` by reference", t),
+                        )
+                        .span_suggestion(
+                            ty.span,
+                            "try passing by value",
+                            t,
+                            // Changing type of function argument
+                            Applicability::MaybeIncorrect,
+                        )
+                        .emit();
                     }
                 }
-                if let Some(t) = is_ty_or_ty_ctxt(cx, &inner_ty) {
-                    cx.struct_span_lint(
-                        TY_PASS_BY_REFERENCE,
-                        ty.span,
-                        &format!("passing `{}` by reference", t),
-                    )
-                    .span_suggestion(
-                        ty.span,
-                        "try passing by value",
-                        t,
-                        // Changing type of function argument
-                        Applicability::MaybeIncorrect,
-                    )
-                    .emit();
-                }
+                _ => {}
             }
-            _ => {}
         }
     }
 }
@@ -215,23 +219,26 @@ declare_tool_lint! {
 
 declare_lint_pass!(LintPassImpl => [LINT_PASS_IMPL_WITHOUT_MACRO]);
 
-impl EarlyLintPass for LintPassImpl {
-    fn check_item(&mut self, cx: &EarlyContext<'_>, item: &Item) {
-        if let ItemKind::Impl(_, _, _, _, Some(lint_pass), _, _) = &item.kind {
-            if let Some(last) = lint_pass.path.segments.last() {
-                if last.ident.name == sym::LintPass {
-                    let expn_data = lint_pass.path.span.ctxt().outer_expn_data();
-                    let call_site = expn_data.call_site;
-                    if expn_data.kind.descr() != sym::impl_lint_pass
-                        && call_site.ctxt().outer_expn_data().kind.descr() != sym::declare_lint_pass
-                    {
-                        cx.struct_span_lint(
-                            LINT_PASS_IMPL_WITHOUT_MACRO,
-                            lint_pass.path.span,
-                            "implementing `LintPass` by hand",
-                        )
-                        .help("try using `declare_lint_pass!` or `impl_lint_pass!` instead")
-                        .emit();
+impl_lint_pass_callbacks! {
+    impl EarlyLintPass for LintPassImpl {
+        fn check_item(&mut self, cx: &EarlyContext<'_>, item: &Item) {
+            if let ItemKind::Impl(_, _, _, _, Some(lint_pass), _, _) = &item.kind {
+                if let Some(last) = lint_pass.path.segments.last() {
+                    if last.ident.name == sym::LintPass {
+                        let expn_data = lint_pass.path.span.ctxt().outer_expn_data();
+                        let call_site = expn_data.call_site;
+                        if expn_data.kind.descr() != sym::impl_lint_pass
+                            && call_site.ctxt().outer_expn_data().kind.descr()
+                                != sym::declare_lint_pass
+                        {
+                            cx.struct_span_lint(
+                                LINT_PASS_IMPL_WITHOUT_MACRO,
+                                lint_pass.path.span,
+                                "implementing `LintPass` by hand",
+                            )
+                            .help("try using `declare_lint_pass!` or `impl_lint_pass!` instead")
+                            .emit();
+                        }
                     }
                 }
             }
@@ -4,12 +4,13 @@ use crate::ich::StableHashingContext;
 use crate::lint::builtin;
 use crate::lint::context::{CheckLintNameResult, LintStore};
 use crate::lint::{self, Level, Lint, LintId, LintSource};
-use crate::session::Session;
+use crate::session::{config, Session};
 use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
 use rustc_errors::{struct_span_err, Applicability, DiagnosticBuilder};
 use rustc_hir::HirId;
 use rustc_span::source_map::MultiSpan;
+use rustc_span::Span;
 use rustc_span::symbol::{sym, Symbol};
 use syntax::ast;
 use syntax::attr;
@@ -55,6 +56,44 @@ impl LintLevelSets {
         let mut specs = FxHashMap::default();
         self.lint_cap = sess.opts.lint_cap.unwrap_or(Level::Forbid);
 
+        // The levels of `RUSTC_LINT_LEVELS` come first, as they're meant for one-off runs of a
+        // build that may well set its own levels.
+        let var = config::LINT_LEVELS_ENV_VAR;
+        for &(ref lint_name, level) in &sess.opts.lint_levels_from_env {
+            store.check_lint_name_from_env(sess, &lint_name, var);
+
+            let level = cmp::min(level, self.lint_cap);
+            let lint_name_val = Symbol::intern(lint_name);
+            let ids = match store.find_lints(&lint_name) {
+                Ok(ids) => ids,
+                Err(_) => continue, // errors handled in check_lint_name_from_env above
+            };
+            for id in ids {
+                specs.insert(id, (level, LintSource::LintLevelsEnv(lint_name_val)));
+            }
+        }
+
+        // The lint spec comes next, so that the flags can override the levels it sets, just as
+        // crate attributes can.
+        for entry in &sess.opts.lint_spec {
+            let tool_name = entry.tool.as_ref().map(|tool| Symbol::intern(tool));
+            store.check_lint_name_from_spec(sess, &entry.name, tool_name);
+
+            let level = cmp::min(entry.level, self.lint_cap);
+            let lint_name = match tool_name {
+                Some(tool_name) => format!("{}::{}", tool_name, entry.name),
+                None => entry.name.clone(),
+            };
+            let ids = match store.find_lints(&lint_name) {
+                Ok(ids) => ids,
+                Err(_) => continue, // errors handled in check_lint_name_from_spec above
+            };
+            let lint_name_val = Symbol::intern(&lint_name);
+            for id in ids {
+                specs.insert(id, (level, LintSource::LintSpec(lint_name_val)));
+            }
+        }
+
         for &(ref lint_name, level) in &sess.opts.lint_opts {
             store.check_lint_name_cmdline(sess, &lint_name, level);
 
@@ -74,6 +113,30 @@ impl LintLevelSets {
             }
         }
 
+        // These come after the flags so that they win over any `-A` or `-W` in `RUSTFLAGS`,
+        // wherever it ends up in the command line.
+        if let Some((ref var, ref lint_names)) = sess.opts.deny_lints_from_env {
+            let var_val = Symbol::intern(var);
+            for lint_name in lint_names {
+                store.check_lint_name_from_env(sess, &lint_name, var);
+
+                let level = cmp::min(Level::Deny, self.lint_cap);
+                let lint_name_val = Symbol::intern(lint_name);
+                let ids = match store.find_lints(&lint_name) {
+                    Ok(ids) => ids,
+                    Err(_) => continue, // errors handled in check_lint_name_from_env above
+                };
+                for id in ids {
+                    // Don't weaken a `-F`.
+                    if let Some(&(Level::Forbid, _)) = specs.get(&id) {
+                        continue;
+                    }
+                    let src = LintSource::Environment(lint_name_val, var_val);
+                    specs.insert(id, (level, src));
+                }
+            }
+        }
+
         self.list.push(LintSet::CommandLine { specs: specs });
     }
 
@@ -151,6 +214,17 @@ pub struct LintLevelsBuilder<'a> {
     id_to_set: FxHashMap<HirId, u32>,
     cur: u32,
     warn_about_weird_lints: bool,
+    allows: Vec<LintAllow>,
+}
+
+/// A lint name in an `allow` attribute, together with the level of the `unused_allows` lint
+/// at that attribute.
+#[derive(HashStable)]
+pub struct LintAllow {
+    pub span: Span,
+    pub name: Symbol,
+    pub level: Level,
+    pub src: LintSource,
 }
 
 pub struct BuilderPush {
@@ -171,6 +245,7 @@ impl<'a> LintLevelsBuilder<'a> {
             cur: 0,
             id_to_set: Default::default(),
             warn_about_weird_lints,
+            allows: Vec::new(),
         }
     }
 
@@ -190,6 +265,7 @@ impl<'a> LintLevelsBuilder<'a> {
     /// Don't forget to call `pop`!
     pub fn push(&mut self, attrs: &[ast::Attribute], store: &LintStore) -> BuilderPush {
         let mut specs = FxHashMap::default();
+        let mut allows = Vec::new();
         let sess = self.sess;
         let bad_attr = |span| struct_span_err!(sess, span, E0452, "malformed lint attribute input");
         for attr in attrs {
@@ -208,13 +284,13 @@ impl<'a> LintLevelsBuilder<'a> {
                 continue;
             }
 
-            // Before processing the lint names, look for a reason (RFC 2383)
+            // Before processing the lint names, look for a reason (RFC 2383) and an expiry
             // at the end.
             let mut reason = None;
-            let tail_li = &metas[metas.len() - 1];
-            if let Some(item) = tail_li.meta_item() {
+            let mut expiry = None;
+            while let Some(item) = metas.last().and_then(|li| li.meta_item()) {
                 match item.kind {
-                    ast::MetaItemKind::Word => {} // actual lint names handled later
+                    ast::MetaItemKind::Word => break, // actual lint names handled later
                     ast::MetaItemKind::NameValue(ref name_value) => {
                         if item.path == sym::reason {
                             // found reason, reslice meta list to exclude it
@@ -237,18 +313,67 @@ impl<'a> LintLevelsBuilder<'a> {
                                     .span_label(name_value.span, "reason must be a string literal")
                                     .emit();
                             }
+                        } else if item.path == sym::expires {
+                            metas = &metas[0..metas.len() - 1];
+                            let expired = match name_value.kind {
+                                ast::LitKind::Str(expiry, _) => expiry_passed(&expiry.as_str()),
+                                _ => None,
+                            };
+                            match expired {
+                                Some((passed, when)) => {
+                                    if self.warn_about_weird_lints
+                                        && !self.sess.features_untracked().lint_expiry
+                                    {
+                                        feature_err(
+                                            &self.sess.parse_sess,
+                                            sym::lint_expiry,
+                                            item.span,
+                                            "lint attribute expiry is experimental",
+                                        )
+                                        .emit();
+                                    }
+                                    if passed && self.warn_about_weird_lints {
+                                        expiry = Some((item.span, when));
+                                    }
+                                }
+                                None => {
+                                    bad_attr(name_value.span)
+                                        .span_label(
+                                            name_value.span,
+                                            "expiry must be a date like \"2024-12-31\" or a Rust \
+                                             version like \"1.45.0\"",
+                                        )
+                                        .emit();
+                                }
+                            }
                         } else {
                             bad_attr(item.span)
                                 .span_label(item.span, "bad attribute argument")
                                 .emit();
+                            break;
                         }
                     }
                     ast::MetaItemKind::List(_) => {
                         bad_attr(item.span).span_label(item.span, "bad attribute argument").emit();
+                        break;
                     }
                 }
             }
 
+            if let Some((expiry_span, when)) = expiry {
+                let lint = builtin::EXPIRED_LINT_ATTRIBUTES;
+                let (lvl, src) = self.sets.get_lint_level(lint, self.cur, Some(&specs), sess);
+                let msg = format!("this `{}` attribute expired {}", level.as_str(), when);
+                let mut err =
+                    lint::struct_lint_level(sess, lint, lvl, src, Some(expiry_span.into()), &msg);
+                if let Some(rationale) = reason {
+                    err.note(&rationale.as_str());
+                }
+                err.note("the attribute still applies, but is due to be revisited")
+                    .help("fix the code it covers and remove it, or move the expiry")
+                    .emit();
+            }
+
             for li in metas {
                 let meta_item = match li.meta_item() {
                     Some(meta_item) if meta_item.is_word() => meta_item,
@@ -261,6 +386,9 @@ impl<'a> LintLevelsBuilder<'a> {
                                 if item.path == sym::reason {
                                     err.span_label(sp, "reason in lint attribute must come last");
                                     add_label = false;
+                                } else if item.path == sym::expires {
+                                    err.span_label(sp, "expiry in lint attribute must come last");
+                                    add_label = false;
                                 }
                             }
                         }
@@ -292,6 +420,9 @@ impl<'a> LintLevelsBuilder<'a> {
                 let name = meta_item.path.segments.last().expect("empty lint name").ident.name;
                 match store.check_lint_name(&name.as_str(), tool_name) {
                     CheckLintNameResult::Ok(ids) => {
+                        if level == Level::Allow {
+                            allows.push((li.span(), name));
+                        }
                         let src = LintSource::Node(name, li.span(), reason);
                         for id in ids {
                             specs.insert(*id, (level, src));
@@ -302,6 +433,9 @@ impl<'a> LintLevelsBuilder<'a> {
                         match result {
                             Ok(ids) => {
                                 let complete_name = &format!("{}::{}", tool_name.unwrap(), name);
+                                if level == Level::Allow {
+                                    allows.push((li.span(), Symbol::intern(complete_name)));
+                                }
                                 let src = LintSource::Node(
                                     Symbol::intern(complete_name),
                                     li.span(),
@@ -417,9 +551,12 @@ impl<'a> LintLevelsBuilder<'a> {
                 _ => continue,
             };
             let forbidden_lint_name = match forbid_src {
-                LintSource::Default => id.to_string(),
+                LintSource::Default | LintSource::ExternCrate(_) => id.to_string(),
                 LintSource::Node(name, _, _) => name.to_string(),
-                LintSource::CommandLine(name) => name.to_string(),
+                LintSource::CommandLine(name)
+                | LintSource::Environment(name, _)
+                | LintSource::LintLevelsEnv(name)
+                | LintSource::LintSpec(name) => name.to_string(),
             };
             let (lint_attr_name, lint_attr_span) = match *src {
                 LintSource::Node(name, span, _) => (name, span),
@@ -436,22 +573,43 @@ impl<'a> LintLevelsBuilder<'a> {
             );
             diag_builder.span_label(lint_attr_span, "overruled by previous forbid");
             match forbid_src {
-                LintSource::Default => {}
+                LintSource::Default | LintSource::ExternCrate(_) => {}
                 LintSource::Node(_, forbid_source_span, reason) => {
                     diag_builder.span_label(forbid_source_span, "`forbid` level set here");
                     if let Some(rationale) = reason {
                         diag_builder.note(&rationale.as_str());
                     }
                 }
-                LintSource::CommandLine(_) => {
+                LintSource::CommandLine(_) | LintSource::Environment(..) => {
                     diag_builder.note("`forbid` lint level was set on command line");
                 }
+                LintSource::LintSpec(_) => {
+                    diag_builder.note("`forbid` lint level was set by `--lint-spec`");
+                }
+                LintSource::LintLevelsEnv(_) => {
+                    diag_builder.note(&format!(
+                        "`forbid` lint level was set by the `{}` environment variable",
+                        config::LINT_LEVELS_ENV_VAR
+                    ));
+                }
             }
             diag_builder.emit();
             // don't set a separate error for every lint in the group
             break;
         }
 
+        if !allows.is_empty() {
+            let lint = builtin::UNUSED_ALLOWS;
+            let (level, src) = self.sets.get_lint_level(lint, self.cur, Some(&specs), sess);
+            if level == Level::Allow {
+                lint::mark_allow_used(sess, src);
+            } else {
+                self.allows.extend(
+                    allows.into_iter().map(|(span, name)| LintAllow { span, name, level, src }),
+                );
+            }
+        }
+
         let prev = self.cur;
         if specs.len() > 0 {
             self.cur = self.sets.list.len() as u32;
@@ -474,10 +632,18 @@ impl<'a> LintLevelsBuilder<'a> {
         span: Option<MultiSpan>,
         msg: &str,
     ) -> DiagnosticBuilder<'a> {
+        self.sess.record_lint_check(LintId::of(lint));
         let (level, src) = self.sets.get_lint_level(lint, self.cur, None, self.sess);
         lint::struct_lint_level(self.sess, lint, level, src, span, msg)
     }
 
+    /// Returns the level of `lint` in the current scope, along with where it was set, for
+    /// passes that skip their work when the lints they emit are allowed.
+    pub fn lint_level(&self, lint: &'static Lint) -> (Level, LintSource) {
+        self.sess.record_lint_check(LintId::of(lint));
+        self.sets.get_lint_level(lint, self.cur, None, self.sess)
+    }
+
     /// Registers the ID provided with the current set of lints stored in
     /// this context.
     pub fn register_id(&mut self, id: HirId) {
@@ -489,13 +655,68 @@ impl<'a> LintLevelsBuilder<'a> {
     }
 
     pub fn build_map(self) -> LintLevelMap {
-        LintLevelMap { sets: self.sets, id_to_set: self.id_to_set }
+        LintLevelMap { sets: self.sets, id_to_set: self.id_to_set, allows: self.allows }
     }
 }
 
+/// Whether the `expires` value of a lint attribute has passed, along with how to say when it did,
+/// or `None` if it is neither a `YYYY-MM-DD` date nor a Rust version.
+fn expiry_passed(expiry: &str) -> Option<(bool, String)> {
+    let numbers = |sep, lens: &[usize]| -> Option<Vec<u32>> {
+        let parts: Vec<&str> = expiry.split(sep).collect();
+        if !lens.is_empty() && parts.iter().map(|part| part.len()).ne(lens.iter().cloned()) {
+            return None;
+        }
+        parts.iter().map(|part| part.parse().ok()).collect()
+    };
+
+    if let Some(date) = numbers('-', &[4, 2, 2]) {
+        if !(1..=12).contains(&date[1]) || !(1..=31).contains(&date[2]) {
+            return None;
+        }
+        let passed = (date[0], date[1], date[2]) <= today();
+        return Some((passed, format!("on {}", expiry)));
+    }
+
+    let mut version = numbers('.', &[])?;
+    if version.len() < 2 || version.len() > 3 {
+        return None;
+    }
+    version.resize(3, 0);
+    // Like deprecations, an expiry only passes with a known release.
+    let passed = option_env!("CFG_RELEASE").map_or(false, |rustc| {
+        let rustc: Vec<u32> =
+            rustc.split(|c| c == '.' || c == '-').flat_map(|s| s.parse()).take(3).collect();
+        version <= rustc
+    });
+    Some((passed, format!("with Rust {}", expiry)))
+}
+
+/// Today's date in UTC, as year, month and day.
+fn today() -> (u32, u32, u32) {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    // The civil-from-days conversion, counting in 400-year eras from 0000-03-01 so that leap
+    // days fall at the end of the year.
+    let days = secs / 86400 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year as u32, month as u32, day as u32)
+}
+
 pub struct LintLevelMap {
     sets: LintLevelSets,
     id_to_set: FxHashMap<HirId, u32>,
+    /// The `allow` attributes that `unused_allows` should check.
+    pub allows: Vec<LintAllow>,
 }
 
 impl LintLevelMap {
@@ -519,7 +740,7 @@ impl LintLevelMap {
 impl<'a> HashStable<StableHashingContext<'a>> for LintLevelMap {
     #[inline]
     fn hash_stable(&self, hcx: &mut StableHashingContext<'a>, hasher: &mut StableHasher) {
-        let LintLevelMap { ref sets, ref id_to_set } = *self;
+        let LintLevelMap { ref sets, ref id_to_set, ref allows } = *self;
 
         id_to_set.hash_stable(hcx, hasher);
 
@@ -545,6 +766,8 @@ impl<'a> HashStable<StableHashingContext<'a>> for LintLevelMap {
                     }
                 }
             }
+
+            allows.hash_stable(hcx, hasher);
         })
     }
 }
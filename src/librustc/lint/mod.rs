@@ -23,19 +23,21 @@ pub use self::LintSource::*;
 
 use crate::ty::TyCtxt;
 use rustc_data_structures::sync;
-use rustc_errors::{DiagnosticBuilder, DiagnosticId};
+use rustc_errors::{Applicability, DiagnosticBuilder, DiagnosticId};
 use rustc_hir as hir;
+use rustc_session::config::{self, AllowSuggestions};
 use rustc_session::lint::builtin::HardwiredLints;
 use rustc_session::{DiagnosticMessageId, Session};
 use rustc_span::hygiene::MacroKind;
 use rustc_span::source_map::{DesugaringKind, ExpnKind, MultiSpan};
 use rustc_span::symbol::Symbol;
 use rustc_span::Span;
+use std::cmp;
 use syntax::ast;
 
 pub use crate::lint::context::{
     add_elided_lifetime_in_path_suggestion, CheckLintNameResult, EarlyContext, LateContext,
-    LintContext, LintStore,
+    LintContext, LintProvenance, LintStore,
 };
 
 pub use rustc_session::lint::builtin;
@@ -126,12 +128,71 @@ macro_rules! declare_late_lint_pass {
     ([], [$hir:tt], [$($methods:tt)*]) => (
         pub trait LateLintPass<'a, $hir>: LintPass {
             expand_lint_pass_methods!(&LateContext<'a, $hir>, [$($methods)*]);
+
+            /// The callbacks this pass implements. Passes registered with the `LintStore` are
+            /// only called back for these, which saves a virtual call per node for the others.
+            /// Implement the pass with `impl_lint_pass_callbacks!` rather than override this, so
+            /// that the set can't miss a callback.
+            fn interests(&self) -> LateLintInterests {
+                LateLintInterests::ALL
+            }
         }
     )
 }
 
 late_lint_methods!(declare_late_lint_pass, [], ['tcx]);
 
+/// Declares an enum with a variant per callback of a lint pass, and a set of those callbacks.
+macro_rules! declare_lint_interests {
+    (
+        $(#[$callback_attr:meta])* $callback:ident,
+        $(#[$interests_attr:meta])* $interests:ident,
+        [$($name:ident),*]
+    ) => (
+        $(#[$callback_attr])*
+        #[allow(non_camel_case_types)]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum $callback {
+            $($name,)*
+        }
+
+        $(#[$interests_attr])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub struct $interests(u64);
+
+        impl $interests {
+            pub const NONE: Self = $interests(0);
+            pub const ALL: Self = $interests(0 $(| (1 << $callback::$name as u64))*);
+
+            pub const fn with(self, callback: $callback) -> Self {
+                $interests(self.0 | (1 << callback as u64))
+            }
+
+            pub const fn union(self, other: Self) -> Self {
+                $interests(self.0 | other.0)
+            }
+
+            pub fn contains(self, callback: $callback) -> bool {
+                self.0 & (1 << callback as u64) != 0
+            }
+        }
+    )
+}
+
+macro_rules! declare_late_lint_interests {
+    ([], [$hir:tt], [$($(#[$attr:meta])* fn $name:ident($($param:ident: $arg:ty),*);)*]) => (
+        declare_lint_interests!(
+            /// A callback of `LateLintPass`.
+            LateLintCallback,
+            /// A set of `LateLintPass` callbacks, as returned by `LateLintPass::interests`.
+            LateLintInterests,
+            [$($name),*]
+        );
+    )
+}
+
+late_lint_methods!(declare_late_lint_interests, [], ['tcx]);
+
 impl LateLintPass<'_, '_> for HardwiredLints {}
 
 #[macro_export]
@@ -180,6 +241,10 @@ macro_rules! declare_combined_late_lint_pass {
             fn name(&self) -> &'static str {
                 panic!()
             }
+
+            fn lints(&self) -> LintArray {
+                Self::get_lints()
+            }
         }
     )
 }
@@ -198,6 +263,9 @@ macro_rules! early_lint_methods {
             fn check_foreign_item_post(a: &ast::ForeignItem);
             fn check_item(a: &ast::Item);
             fn check_item_post(a: &ast::Item);
+            fn check_enum_def(a: &ast::EnumDef, b: &ast::Item);
+            fn check_union_def(a: &ast::VariantData, b: &ast::Item);
+            fn check_trait_alias(a: &ast::GenericBounds, b: &ast::Item);
             fn check_local(a: &ast::Local);
             fn check_block(a: &ast::Block);
             fn check_block_post(a: &ast::Block);
@@ -207,10 +275,42 @@ macro_rules! early_lint_methods {
             fn check_pat_post(a: &ast::Pat);
             fn check_expr(a: &ast::Expr);
             fn check_expr_post(a: &ast::Expr);
+
+            /// Called for every field of a struct expression, shorthand or not, within the lint
+            /// attributes of the field.
+            fn check_expr_field(a: &ast::Field);
+
+            /// Called for every closure, after `check_expr` and within the lint attributes of the
+            /// closure expression, with the expression, its capture clause, asyncness,
+            /// declaration and body.
+            fn check_closure(
+                a: &ast::Expr,
+                b: ast::CaptureBy,
+                c: ast::IsAsync,
+                d: &ast::FnDecl,
+                e: &ast::Expr
+            );
+
+            /// Counterpart to `check_closure`, called once the closure body is visited and before
+            /// `check_expr_post`.
+            fn check_closure_post(
+                a: &ast::Expr,
+                b: ast::CaptureBy,
+                c: ast::IsAsync,
+                d: &ast::FnDecl,
+                e: &ast::Expr
+            );
+
+            /// Called for every anonymous constant, such as an array length, a const generic
+            /// argument or an enum discriminant, before its expression.
+            fn check_anon_const(a: &ast::AnonConst);
+
             fn check_ty(a: &ast::Ty);
             fn check_generic_param(a: &ast::GenericParam);
             fn check_generics(a: &ast::Generics);
             fn check_where_predicate(a: &ast::WherePredicate);
+            fn check_generic_arg(a: &ast::GenericArg);
+            fn check_assoc_ty_constraint(a: &ast::AssocTyConstraint);
             fn check_poly_trait_ref(a: &ast::PolyTraitRef,
                                     b: &ast::TraitBoundModifier);
             fn check_fn(a: syntax::visit::FnKind<'_>, b: &ast::FnDecl, c: Span, d_: ast::NodeId);
@@ -220,6 +320,11 @@ macro_rules! early_lint_methods {
                 c: Span,
                 d: ast::NodeId
             );
+
+            /// Called for the header of every function and method, including trait methods
+            /// without a body, with the span and id of the function.
+            fn check_fn_header(a: &ast::FnHeader, b: Span, c: ast::NodeId);
+
             fn check_trait_item(a: &ast::AssocItem);
             fn check_trait_item_post(a: &ast::AssocItem);
             fn check_impl_item(a: &ast::AssocItem);
@@ -229,8 +334,23 @@ macro_rules! early_lint_methods {
             fn check_struct_field(a: &ast::StructField);
             fn check_variant(a: &ast::Variant);
             fn check_variant_post(a: &ast::Variant);
+
+            /// Called for the visibility of every item, associated item, foreign item, field and
+            /// variant, with the id of its owner.
+            fn check_vis(a: &ast::Visibility, b: ast::NodeId);
+
             fn check_lifetime(a: &ast::Lifetime);
+
+            /// Called for the labels of loops and blocks, and for those of `break` and `continue`.
+            fn check_label(a: &ast::Label);
+
             fn check_path(a: &ast::Path, b: ast::NodeId);
+
+            /// Called for every tree of a `use` item, from the item's own down to the nested
+            /// simple, renamed and glob ones, with the id of the tree. The id of the outermost
+            /// tree is that of the item.
+            fn check_use_tree(a: &ast::UseTree, b: ast::NodeId);
+
             fn check_attribute(a: &ast::Attribute);
             fn check_mac_def(a: &ast::MacroDef, b: ast::NodeId);
             fn check_mac(a: &ast::Mac);
@@ -255,12 +375,68 @@ macro_rules! declare_early_lint_pass {
     ([], [$($methods:tt)*]) => (
         pub trait EarlyLintPass: LintPass {
             expand_early_lint_pass_methods!(&EarlyContext<'_>, [$($methods)*]);
+
+            /// The callbacks this pass implements. Passes registered with the `LintStore` are
+            /// only called back for these, which saves a virtual call per node for the others.
+            /// Implement the pass with `impl_lint_pass_callbacks!` rather than override this, so
+            /// that the set can't miss a callback.
+            fn interests(&self) -> EarlyLintInterests {
+                EarlyLintInterests::ALL
+            }
         }
     )
 }
 
 early_lint_methods!(declare_early_lint_pass, []);
 
+macro_rules! declare_early_lint_interests {
+    ([], [$($(#[$attr:meta])* fn $name:ident($($param:ident: $arg:ty),*);)*]) => (
+        declare_lint_interests!(
+            /// A callback of `EarlyLintPass`.
+            EarlyLintCallback,
+            /// A set of `EarlyLintPass` callbacks, as returned by `EarlyLintPass::interests`.
+            EarlyLintInterests,
+            [$($name),*]
+        );
+    )
+}
+
+early_lint_methods!(declare_early_lint_interests, []);
+
+/// Implements `EarlyLintPass` or `LateLintPass` with the given callbacks, along with an
+/// `interests` listing exactly those callbacks.
+#[macro_export]
+macro_rules! impl_lint_pass_callbacks {
+    (
+        impl $(<$($gen:lifetime),*>)? EarlyLintPass for $ty:ty {
+            $($(#[$attr:meta])* fn $name:ident $params:tt $body:block)*
+        }
+    ) => (
+        impl $(<$($gen),*>)? $crate::lint::EarlyLintPass for $ty {
+            $($(#[$attr])* fn $name $params $body)*
+
+            fn interests(&self) -> $crate::lint::EarlyLintInterests {
+                $crate::lint::EarlyLintInterests::NONE
+                    $(.with($crate::lint::EarlyLintCallback::$name))*
+            }
+        }
+    );
+    (
+        impl<$($gen:lifetime),*> LateLintPass<$($arg:lifetime),*> for $ty:ty {
+            $($(#[$attr:meta])* fn $name:ident $params:tt $body:block)*
+        }
+    ) => (
+        impl<$($gen),*> $crate::lint::LateLintPass<$($arg),*> for $ty {
+            $($(#[$attr])* fn $name $params $body)*
+
+            fn interests(&self) -> $crate::lint::LateLintInterests {
+                $crate::lint::LateLintInterests::NONE
+                    $(.with($crate::lint::LateLintCallback::$name))*
+            }
+        }
+    );
+}
+
 #[macro_export]
 macro_rules! expand_combined_early_lint_pass_method {
     ([$($passes:ident),*], $self: ident, $name: ident, $params:tt) => ({
@@ -307,6 +483,10 @@ macro_rules! declare_combined_early_lint_pass {
             fn name(&self) -> &'static str {
                 panic!()
             }
+
+            fn lints(&self) -> LintArray {
+                Self::get_lints()
+            }
         }
     )
 }
@@ -328,15 +508,38 @@ pub enum LintSource {
 
     /// Lint level was set by a command-line flag.
     CommandLine(Symbol),
+
+    /// Lint was denied by being listed in the environment variable (the second `Symbol`) passed
+    /// to `--deny-lints-from-env`.
+    Environment(Symbol, Symbol),
+
+    /// Lint level was set by the `RUSTC_LINT_LEVELS` environment variable.
+    LintLevelsEnv(Symbol),
+
+    /// Lint level was set in the file passed to `--lint-spec`.
+    LintSpec(Symbol),
+
+    /// Lint level was set for code from the named crate with `--extern-lint-level`.
+    ExternCrate(Symbol),
 }
 
 pub type LevelSource = (Level, LintSource);
 
+/// A lint queued with `TyCtxt::inject_lint`, emitted by `TyCtxt::emit_injected_lints`.
+pub struct InjectedLint {
+    pub lint: &'static Lint,
+    pub hir_id: hir::HirId,
+    pub span: MultiSpan,
+    pub msg: String,
+    /// Adds the notes and suggestions of the lint, once it is known to be emitted.
+    pub decorate: Box<dyn for<'a> FnOnce(&mut DiagnosticBuilder<'a>) + sync::Send>,
+}
+
 mod context;
 pub mod internal;
 mod levels;
 
-pub use self::levels::{LintLevelMap, LintLevelSets, LintLevelsBuilder};
+pub use self::levels::{LintAllow, LintLevelMap, LintLevelSets, LintLevelsBuilder};
 
 pub fn struct_lint_level<'a>(
     sess: &'a Session,
@@ -346,8 +549,23 @@ pub fn struct_lint_level<'a>(
     span: Option<MultiSpan>,
     msg: &str,
 ) -> DiagnosticBuilder<'a> {
+    // `--extern-lint-level` overrides the level of lints in code from the crates it names.
+    let extern_level =
+        span.as_ref().and_then(|span| sess.extern_lint_level(LintId::of(lint), span));
+    let (level, src) = match extern_level {
+        Some((extern_level, krate)) => {
+            let level = cmp::min(extern_level, sess.opts.lint_cap.unwrap_or(Level::Forbid));
+            (level, LintSource::ExternCrate(krate))
+        }
+        None => (level, src),
+    };
+
     let mut err = match (level, span) {
-        (Level::Allow, _) => return sess.diagnostic().struct_dummy(),
+        (Level::Allow, _) => {
+            sess.record_lint_level(LintId::of(lint), level);
+            mark_allow_used(sess, src);
+            return sess.diagnostic().struct_dummy();
+        }
         (Level::Warn, Some(span)) => sess.struct_span_warn(span, msg),
         (Level::Warn, None) => sess.struct_warn(msg),
         (Level::Deny, Some(span)) | (Level::Forbid, Some(span)) => sess.struct_span_err(span, msg),
@@ -368,8 +586,10 @@ pub fn struct_lint_level<'a>(
 
         // If this is a future incompatible lint it'll become a hard error, so
         // we have to emit *something*. Also allow lints to whitelist themselves
-        // on a case-by-case basis for emission in a foreign macro.
-        if future_incompatible.is_none() && !lint.report_in_external_macro {
+        // on a case-by-case basis for emission in a foreign macro, and keep those
+        // whose level was set for the macro's crate with `--extern-lint-level`.
+        if future_incompatible.is_none() && !lint.report_in_external_macro && extern_level.is_none()
+        {
             err.cancel();
             // Don't continue further, since we don't want to have
             // `diag_span_note_once` called for a diagnostic that isn't emitted.
@@ -377,14 +597,46 @@ pub fn struct_lint_level<'a>(
         }
     }
 
+    // Under `--lint-changed`, only warnings on the lines of the patch being reviewed are
+    // reported. Errors still fail the build wherever they are.
+    if level == Level::Warn
+        && !err.span.primary_spans().iter().all(|s| s.is_dummy())
+        && !err.span.primary_spans().iter().any(|s| sess.in_lint_changed_lines(*s))
+    {
+        err.cancel();
+        return err;
+    }
+
+    sess.record_lint_level(lint_id, level);
+    if level == Level::Warn {
+        sess.count_lint_warning(lint_id);
+    }
+
+    // Past `-Z lint-emission-cap`, warnings are only counted, to be summarized at the end.
+    if level == Level::Warn && !sess.lint_within_emission_cap(lint_id) {
+        err.cancel();
+        return err;
+    }
+    sess.count_lint_emission();
+
+    // Errors always point at what raised the lint's level, so the policy can be found from any
+    // of them. Warnings only do so the first time the lint is reported.
+    let always_note = match level {
+        Level::Deny | Level::Forbid => true,
+        Level::Allow | Level::Warn => false,
+    };
+    let note = |err: &mut DiagnosticBuilder<'a>, msg: &str| {
+        if always_note {
+            err.note(msg);
+        } else {
+            sess.diag_note_once(err, DiagnosticMessageId::from(lint), msg);
+        }
+    };
+
     let name = lint.name_lower();
     match src {
         LintSource::Default => {
-            sess.diag_note_once(
-                &mut err,
-                DiagnosticMessageId::from(lint),
-                &format!("`#[{}({})]` on by default", level.as_str(), name),
-            );
+            note(&mut err, &format!("`#[{}({})]` on by default", level.as_str(), name));
         }
         LintSource::CommandLine(lint_flag_val) => {
             let flag = match level {
@@ -395,9 +647,8 @@ pub fn struct_lint_level<'a>(
             };
             let hyphen_case_lint_name = name.replace("_", "-");
             if lint_flag_val.as_str() == name {
-                sess.diag_note_once(
+                note(
                     &mut err,
-                    DiagnosticMessageId::from(lint),
                     &format!(
                         "requested on the command line with `{} {}`",
                         flag, hyphen_case_lint_name
@@ -405,41 +656,99 @@ pub fn struct_lint_level<'a>(
                 );
             } else {
                 let hyphen_case_flag_val = lint_flag_val.as_str().replace("_", "-");
-                sess.diag_note_once(
+                note(
                     &mut err,
-                    DiagnosticMessageId::from(lint),
                     &format!(
-                        "`{} {}` implied by `{} {}`",
-                        flag, hyphen_case_lint_name, flag, hyphen_case_flag_val
+                        "`{}` implied by `{}`, set by `{} {}` on the command line",
+                        name, lint_flag_val, flag, hyphen_case_flag_val
                     ),
                 );
             }
         }
+        LintSource::Environment(lint_name, var) => {
+            if lint_name.as_str() == name {
+                note(
+                    &mut err,
+                    &format!(
+                        "requested by the `{}` environment variable (see `--deny-lints-from-env`)",
+                        var
+                    ),
+                );
+            } else {
+                note(
+                    &mut err,
+                    &format!(
+                        "`{}` implied by `{}`, requested by the `{}` environment variable",
+                        name, lint_name, var
+                    ),
+                );
+            }
+        }
+        LintSource::LintLevelsEnv(lint_name) => {
+            let var = config::LINT_LEVELS_ENV_VAR;
+            if lint_name.as_str() == name {
+                note(&mut err, &format!("requested by the `{}` environment variable", var));
+            } else {
+                note(
+                    &mut err,
+                    &format!(
+                        "`{}` implied by `{}`, requested by the `{}` environment variable",
+                        name, lint_name, var
+                    ),
+                );
+            }
+        }
+        LintSource::LintSpec(lint_name) => {
+            if lint_name.as_str() == name {
+                note(&mut err, "requested by `--lint-spec`");
+            } else {
+                note(
+                    &mut err,
+                    &format!("`{}` implied by `{}`, requested by `--lint-spec`", name, lint_name),
+                );
+            }
+        }
+        LintSource::ExternCrate(krate) => {
+            note(
+                &mut err,
+                &format!("requested for code from `{}` with `--extern-lint-level`", krate),
+            );
+        }
         LintSource::Node(lint_attr_name, src, reason) => {
             if let Some(rationale) = reason {
                 err.note(&rationale.as_str());
             }
-            sess.diag_span_note_once(
-                &mut err,
-                DiagnosticMessageId::from(lint),
-                src,
-                "lint level defined here",
-            );
-            if lint_attr_name.as_str() != name {
-                let level_str = level.as_str();
-                sess.diag_note_once(
+            if always_note {
+                err.span_note(src, "lint level defined here");
+            } else {
+                sess.diag_span_note_once(
                     &mut err,
                     DiagnosticMessageId::from(lint),
+                    src,
+                    "lint level defined here",
+                );
+            }
+            if lint_attr_name.as_str() != name {
+                // Spell out the whole chain, so that it's clear which group to look at when the
+                // attribute is far away from the code being linted.
+                let loc = sess.source_map().lookup_char_pos(src.lo());
+                note(
+                    &mut err,
                     &format!(
-                        "`#[{}({})]` implied by `#[{}({})]`",
-                        level_str, name, level_str, lint_attr_name
+                        "`{}` implied by `{}`, set by `#[{}({})]` at {}:{}",
+                        name,
+                        lint_attr_name,
+                        level.as_str(),
+                        lint_attr_name,
+                        loc.file.name,
+                        loc.line
                     ),
                 );
             }
         }
     }
 
-    err.code(DiagnosticId::Lint(name));
+    err.code(DiagnosticId::Lint { name, docs_url: lint_docs_url(lint) });
 
     if let Some(future_incompatible) = future_incompatible {
         const STANDARD_MESSAGE: &str = "this was previously accepted by the compiler but is being phased out; \
@@ -466,11 +775,103 @@ pub fn struct_lint_level<'a>(
     return err;
 }
 
+/// Returns the page documenting `lint`, for emitters that can link to it.
+pub fn lint_docs_url(lint: &'static Lint) -> Option<String> {
+    let name = lint.name_lower();
+    if !lint.is_plugin {
+        let listing = match lint.default_level {
+            Level::Allow => "allowed-by-default",
+            Level::Warn => "warn-by-default",
+            Level::Deny | Level::Forbid => "deny-by-default",
+        };
+        return Some(format!(
+            "https://doc.rust-lang.org/rustc/lints/listing/{}.html#{}",
+            listing,
+            name.replace("_", "-")
+        ));
+    }
+    if name.starts_with("clippy::") {
+        return Some(format!(
+            "https://rust-lang.github.io/rust-clippy/master/index.html#{}",
+            &name["clippy::".len()..]
+        ));
+    }
+    None
+}
+
+/// Records that the attribute `src` points to, if any, suppressed a lint. Code that skips
+/// emitting a lint by checking its level up front should call this when it finds `Allow`.
+pub fn mark_allow_used(sess: &Session, src: LintSource) {
+    if let LintSource::Node(_, span, _) = src {
+        sess.used_lint_allows.lock().insert(span);
+    }
+}
+
 pub fn maybe_lint_level_root(tcx: TyCtxt<'_>, id: hir::HirId) -> bool {
     let attrs = tcx.hir().attrs(id);
     attrs.iter().any(|attr| Level::from_symbol(attr.name_or_empty()).is_some())
 }
 
+/// Tells where to put `#[allow(lint)]` to allow a warning: on the innermost node around `id`
+/// that can carry lint attributes. `-Z suggest-allow-attributes` picks between a one-line help,
+/// a rendered suggestion for editors to offer as a quick fix, or nothing.
+pub fn suggest_allow_attribute(
+    tcx: TyCtxt<'_>,
+    err: &mut DiagnosticBuilder<'_>,
+    lint: &'static Lint,
+    level: Level,
+    mut id: hir::HirId,
+) {
+    let style = tcx.sess.opts.debugging_opts.suggest_allow_attributes;
+    if level != Level::Warn || style == AllowSuggestions::Off {
+        return;
+    }
+    if err.cancelled() {
+        return;
+    }
+
+    // Walk outward to the innermost node that can carry a lint attribute, so that the allow
+    // covers as little code as possible.
+    let hir = tcx.hir();
+    let (span, descr) = loop {
+        let found = match hir.find(id) {
+            Some(hir::Node::Item(item)) => Some((item.span, item.kind.descriptive_variant())),
+            Some(hir::Node::ForeignItem(item)) => Some((item.span, "foreign item")),
+            Some(hir::Node::TraitItem(item)) => Some((item.span, "associated item")),
+            Some(hir::Node::ImplItem(item)) => Some((item.span, "associated item")),
+            Some(hir::Node::Variant(variant)) => Some((variant.span, "variant")),
+            Some(hir::Node::Field(field)) => Some((field.span, "field")),
+            Some(hir::Node::Local(local)) => Some((local.span, "`let` statement")),
+            Some(hir::Node::Stmt(stmt)) => Some((stmt.span, "statement")),
+            Some(hir::Node::Arm(arm)) => Some((arm.span, "match arm")),
+            Some(hir::Node::Crate) | None => return,
+            _ => None,
+        };
+        // Attributes can't be inserted into code a macro generated, so keep looking outward.
+        match found {
+            Some((span, descr)) if !span.from_expansion() && !span.is_dummy() => {
+                break (span, descr);
+            }
+            _ => {}
+        }
+        let parent_id = hir.get_parent_node(id);
+        if parent_id == id {
+            return;
+        }
+        id = parent_id;
+    };
+
+    let name = lint.name_lower();
+    let msg = format!("to allow this warning, add `#[allow({})]` to the enclosing {}", name, descr);
+    let margin = tcx.sess.source_map().span_to_margin(span).unwrap_or(0);
+    let sugg = format!("#[allow({})]\n{}", name, " ".repeat(margin));
+    if style == AllowSuggestions::Rendered {
+        err.span_suggestion(span.shrink_to_lo(), &msg, sugg, Applicability::MaybeIncorrect);
+    } else {
+        err.span_suggestion_hidden(span.shrink_to_lo(), &msg, sugg, Applicability::MaybeIncorrect);
+    }
+}
+
 /// Returns whether `span` originates in a foreign crate's external macro.
 ///
 /// This is used to test whether a lint should not even begin to figure out whether it should
@@ -485,6 +886,16 @@ pub fn in_external_macro(sess: &Session, span: Span) -> bool {
                 // Dummy span for the `def_site` means it's an external macro.
                 return true;
             }
+            // Macros loaded from other crates are external too, unless they come from one of
+            // the crates named by `-Z lint-macros-from`.
+            if let Some(&(_, original_span)) =
+                sess.imported_macro_spans.borrow().get(&expn_data.def_site)
+            {
+                let def_file = sess.source_map().lookup_source_file(original_span.lo());
+                if sess.linted_macro_crates.borrow().contains(&def_file.crate_of_origin) {
+                    return false;
+                }
+            }
             match sess.source_map().span_to_snippet(expn_data.def_site) {
                 Ok(code) => !code.starts_with("macro_rules"),
                 // No snippet means external macro or compiler-builtin expansion.
@@ -57,7 +57,7 @@ use rustc_data_structures::sharded::ShardedHashMap;
 use rustc_data_structures::stable_hasher::{
     hash_stable_hashmap, HashStable, StableHasher, StableVec,
 };
-use rustc_data_structures::sync::{Lock, Lrc, WorkerLocal};
+use rustc_data_structures::sync::{self, Lock, Lrc, WorkerLocal};
 use rustc_errors::DiagnosticBuilder;
 use rustc_index::vec::{Idx, IndexVec};
 use rustc_macros::HashStable;
@@ -322,6 +322,8 @@ pub struct GeneratorInteriorTypeCause<'tcx> {
     pub span: Span,
     /// Span of the scope of the captured binding.
     pub scope_span: Option<Span>,
+    /// Span of the yield (or `.await`) that the binding is live across, if known.
+    pub yield_span: Option<Span>,
 }
 
 #[derive(RustcEncodable, RustcDecodable, Debug)]
@@ -1021,6 +1023,9 @@ pub struct GlobalCtxt<'tcx> {
     layout_interner: ShardedHashMap<&'tcx LayoutDetails, ()>,
 
     output_filenames: Arc<OutputFilenames>,
+
+    /// The lints queued with `inject_lint`.
+    injected_lints: Lock<Vec<lint::InjectedLint>>,
 }
 
 impl<'tcx> TyCtxt<'tcx> {
@@ -1221,6 +1226,7 @@ impl<'tcx> TyCtxt<'tcx> {
             allocation_interner: Default::default(),
             alloc_map: Lock::new(interpret::AllocMap::new()),
             output_filenames: Arc::new(output_filenames.clone()),
+            injected_lints: Default::default(),
         }
     }
 
@@ -2614,6 +2620,7 @@ impl<'tcx> TyCtxt<'tcx> {
         lint: &'static Lint,
         mut id: hir::HirId,
     ) -> (lint::Level, lint::LintSource) {
+        self.sess.record_lint_check(lint::LintId::of(lint));
         let sets = self.lint_levels(LOCAL_CRATE);
         loop {
             if let Some(pair) = sets.level_and_source(lint, id, self.sess) {
@@ -2635,7 +2642,42 @@ impl<'tcx> TyCtxt<'tcx> {
         msg: &str,
     ) -> DiagnosticBuilder<'tcx> {
         let (level, src) = self.lint_level_at_node(lint, hir_id);
-        lint::struct_lint_level(self.sess, lint, level, src, Some(span.into()), msg)
+        let mut err = lint::struct_lint_level(self.sess, lint, level, src, Some(span.into()), msg);
+        lint::suggest_allow_attribute(self, &mut err, lint, level, hir_id);
+        err
+    }
+
+    /// Queues `lint` for emission at `hir_id`, for drivers that lint the crate from their own
+    /// analyses once the compiler's are done. The lint is emitted by `emit_injected_lints` at
+    /// the level set for `hir_id`, and like any other lint is dropped when `span` is in a macro
+    /// of another crate; `decorate` is only called for the lints that are emitted.
+    pub fn inject_lint<S: Into<MultiSpan>>(
+        self,
+        lint: &'static Lint,
+        hir_id: HirId,
+        span: S,
+        msg: &str,
+        decorate: impl for<'a> FnOnce(&mut DiagnosticBuilder<'a>) + sync::Send + 'static,
+    ) {
+        self.injected_lints.lock().push(lint::InjectedLint {
+            lint,
+            hir_id,
+            span: span.into(),
+            msg: msg.to_string(),
+            decorate: Box::new(decorate),
+        });
+    }
+
+    /// Emits the lints queued with `inject_lint` so far, in the order they were queued.
+    pub fn emit_injected_lints(self) {
+        let injected_lints = mem::take(&mut *self.injected_lints.lock());
+        for lint::InjectedLint { lint, hir_id, span, msg, decorate } in injected_lints {
+            let mut err = self.struct_span_lint_hir(lint, hir_id, span, &msg);
+            if !err.cancelled() {
+                decorate(&mut err);
+            }
+            err.emit();
+        }
     }
 
     pub fn struct_lint_node(
@@ -2645,7 +2687,9 @@ impl<'tcx> TyCtxt<'tcx> {
         msg: &str,
     ) -> DiagnosticBuilder<'tcx> {
         let (level, src) = self.lint_level_at_node(lint, id);
-        lint::struct_lint_level(self.sess, lint, level, src, None, msg)
+        let mut err = lint::struct_lint_level(self.sess, lint, level, src, None, msg);
+        lint::suggest_allow_attribute(self, &mut err, lint, level, id);
+        err
     }
 
     pub fn in_scope_traits(self, id: HirId) -> Option<&'tcx StableVec<TraitCandidate>> {
@@ -946,6 +946,7 @@ impl<'hir> LoweringContext<'_, 'hir> {
             expr: self.lower_expr(&f.expr),
             span: f.span,
             is_shorthand: f.is_shorthand,
+            attrs: self.lower_attrs(&f.attrs),
         }
     }
 
@@ -1421,7 +1422,7 @@ impl<'hir> LoweringContext<'_, 'hir> {
     }
 
     fn field(&mut self, ident: Ident, expr: &'hir hir::Expr<'hir>, span: Span) -> hir::Field<'hir> {
-        hir::Field { hir_id: self.next_id(), ident, span, expr, is_shorthand: false }
+        hir::Field { hir_id: self.next_id(), ident, span, expr, is_shorthand: false, attrs: &[] }
     }
 
     fn arm(&mut self, pat: &'hir hir::Pat<'hir>, expr: &'hir hir::Expr<'hir>) -> hir::Arm<'hir> {
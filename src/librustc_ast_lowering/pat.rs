@@ -54,6 +54,7 @@ impl<'a, 'hir> LoweringContext<'a, 'hir> {
                     ident: f.ident,
                     pat: self.lower_pat(&f.pat),
                     is_shorthand: f.is_shorthand,
+                    attrs: self.lower_attrs(&f.attrs),
                     span: f.span,
                 }));
                 hir::PatKind::Struct(qpath, fs, etc)
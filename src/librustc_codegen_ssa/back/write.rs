@@ -422,6 +422,7 @@ pub fn start_async_codegen<B: ExtraBackendMethods>(
             }
             OutputType::Mir => {}
             OutputType::DepInfo => {}
+            OutputType::LintReport | OutputType::LintReportJunit | OutputType::LintMetadata => {}
         }
     }
 
@@ -585,7 +586,13 @@ fn produce_final_output_artifacts(
                 user_wants_objects = true;
                 copy_if_one_unit(OutputType::Object, true);
             }
-            OutputType::Mir | OutputType::Metadata | OutputType::Exe | OutputType::DepInfo => {}
+            OutputType::Mir
+            | OutputType::Metadata
+            | OutputType::Exe
+            | OutputType::DepInfo
+            | OutputType::LintReport
+            | OutputType::LintReportJunit
+            | OutputType::LintMetadata => {}
         }
     }
 
@@ -33,6 +33,7 @@ impl Svh {
     }
 }
 
+#[cfg_attr(not(bootstrap), allow(inconsistent_trait_impls))]
 impl Hash for Svh {
     fn hash<H>(&self, state: &mut H)
     where
@@ -34,6 +34,7 @@ use rustc::session::{early_error, early_warn};
 use rustc::ty::TyCtxt;
 use rustc::util::common::ErrorReported;
 use rustc_codegen_utils::codegen_backend::CodegenBackend;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::profiling::print_time_passes_entry;
 use rustc_data_structures::sync::SeqCst;
 use rustc_errors::{registry::Registry, PResult};
@@ -44,10 +45,11 @@ use rustc_interface::{interface, Queries};
 use rustc_metadata::locator;
 use rustc_save_analysis as save;
 use rustc_save_analysis::DumpHandler;
-use rustc_serialize::json::ToJson;
+use rustc_serialize::json::{self, ToJson};
 
 use std::borrow::Cow;
 use std::cmp::max;
+use std::collections::BTreeMap;
 use std::default::Default;
 use std::env;
 use std::ffi::OsString;
@@ -115,6 +117,8 @@ pub trait Callbacks {
     }
     /// Called after analysis. Return value instructs the compiler whether to
     /// continue the compilation afterwards (defaults to `Compilation::Continue`)
+    ///
+    /// Lints queued with `TyCtxt::inject_lint` are emitted once this returns.
     fn after_analysis<'tcx>(
         &mut self,
         _compiler: &interface::Compiler,
@@ -342,6 +346,14 @@ pub fn run_compiler(
                     describe_lints(&sess, &lint_store, true);
                     return early_exit();
                 }
+                let lint_store_prints: Vec<_> =
+                    sess.opts.prints.iter().filter(|&&req| prints_lint_store(req)).collect();
+                if !lint_store_prints.is_empty() {
+                    for &req in lint_store_prints {
+                        print_lint_store(&sess, &lint_store, req);
+                    }
+                    return early_exit();
+                }
             }
 
             queries.expansion()?;
@@ -395,7 +407,10 @@ pub fn run_compiler(
 
             queries.global_ctxt()?.peek_mut().enter(|tcx| tcx.analysis(LOCAL_CRATE))?;
 
-            if callbacks.after_analysis(compiler, queries) == Compilation::Stop {
+            let compilation = callbacks.after_analysis(compiler, queries);
+            // Emit the lints the callbacks injected from their own analyses.
+            queries.global_ctxt()?.peek_mut().enter(|tcx| tcx.emit_injected_lints());
+            if compilation == Compilation::Stop {
                 return early_exit();
             }
 
@@ -418,6 +433,8 @@ pub fn run_compiler(
             linker.link()?
         }
 
+        sess.check_lint_warning_threshold()?;
+
         if sess.opts.debugging_opts.perf_stats {
             sess.print_perf_stats();
         }
@@ -661,6 +678,15 @@ impl RustcDefaultCalls {
                 }
                 Sysroot => println!("{}", sess.sysroot.display()),
                 TargetSpec => println!("{}", sess.target.target.to_json().pretty()),
+                // Printed once the plugins of the crate have registered their lints.
+                LintsJson | LintGroupsJson if input.is_some() => {}
+                LintsJson | LintGroupsJson => {
+                    let lint_store = rustc_lint::new_lint_store(
+                        sess.opts.debugging_opts.no_interleave_lints,
+                        sess.unstable_options(),
+                    );
+                    print_lint_store(sess, &lint_store, *req);
+                }
                 FileNames | CrateName => {
                     let input = input.unwrap_or_else(|| {
                         early_error(ErrorOutputType::default(), "no input file provided")
@@ -728,6 +754,9 @@ impl RustcDefaultCalls {
                 PrintRequest::NativeStaticLibs => {}
             }
         }
+        if input.is_some() && sess.opts.prints.iter().any(|&req| prints_lint_store(req)) {
+            return Compilation::Continue;
+        }
         return Compilation::Stop;
     }
 }
@@ -822,6 +851,10 @@ Available lint options:
     -F <foo>           Forbid <foo> \
               (deny <foo> and all attempts to override)
 
+    -W help=<filter>   List only the lints matching <filter>: a lint group, \
+              `tool:<name>` or
+                       `no-plugins`, several of which can be separated by commas
+
 "
     );
 
@@ -839,18 +872,72 @@ Available lint options:
         lints
     }
 
-    let (plugin, builtin): (Vec<_>, _) =
-        lint_store.get_lints().iter().cloned().partition(|&lint| lint.is_plugin);
+    let filter = &sess.opts.lint_help_filter;
+    let group_members = filter
+        .groups
+        .iter()
+        .flat_map(|group| match lint_store.find_lints(group) {
+            Ok(lint_ids) => lint_ids,
+            Err(_) => sess.fatal(&format!("unknown lint group: `{}`", group.replace("_", "-"))),
+        })
+        .collect::<FxHashSet<_>>();
+    let tool_prefix = filter.tool.as_ref().map(|tool| format!("{}::", tool));
+    let is_listed = |name: &str, builtin: bool| {
+        (filter.plugins || builtin)
+            && tool_prefix.as_ref().map_or(true, |prefix| name.starts_with(&prefix[..]))
+    };
+
+    // Plugins may declare their lints with `declare_lint!` too, so `is_plugin` isn't enough.
+    let is_builtin =
+        |lint: &'static Lint| lint_store.lint_provenance(lint) == lint::LintProvenance::Rustc;
+    let is_builtin_group =
+        |name: &str| lint_store.group_provenance(name) == lint::LintProvenance::Rustc;
+    let (plugin, builtin): (Vec<_>, _) = lint_store
+        .get_lints()
+        .iter()
+        .cloned()
+        .filter(|&lint| is_listed(&lint.name_lower(), is_builtin(lint)))
+        .filter(|&lint| {
+            filter.groups.is_empty() || group_members.contains(&lint::LintId::of(lint))
+        })
+        .partition(|&lint| !is_builtin(lint));
     let plugin = sort_lints(sess, plugin);
     let builtin = sort_lints(sess, builtin);
 
-    let (plugin_groups, builtin_groups): (Vec<_>, _) =
-        lint_store.get_lint_groups().iter().cloned().partition(|&(.., p)| p);
+    let all_groups = lint_store.get_lint_groups();
+    let (plugin_groups, builtin_groups): (Vec<_>, _) = all_groups
+        .iter()
+        .cloned()
+        .filter(|&(name, ..)| is_listed(name, is_builtin_group(name)))
+        .filter(|&(name, ..)| filter.groups.is_empty() || filter.groups.iter().any(|g| g == name))
+        .partition(|&(name, ..)| !is_builtin_group(name));
     let plugin_groups = sort_lint_groups(plugin_groups);
     let builtin_groups = sort_lint_groups(builtin_groups);
 
+    // Every group a lint belongs to, so each entry can say how to toggle it together with others.
+    let mut groups_of_lint: FxHashMap<lint::LintId, Vec<String>> = FxHashMap::default();
+    for (name, lint_ids, _) in all_groups {
+        for lint_id in lint_ids {
+            groups_of_lint.entry(lint_id).or_default().push(name.replace("_", "-"));
+        }
+    }
+    let groups_of_lint: FxHashMap<_, _> = groups_of_lint
+        .into_iter()
+        .map(|(lint_id, mut groups)| {
+            groups.sort();
+            (lint_id, groups.join(", "))
+        })
+        .collect();
+    let groups_of = |lint: &'static Lint| {
+        groups_of_lint.get(&lint::LintId::of(lint)).map_or("", |groups| &groups[..])
+    };
+
     let max_name_len =
         plugin.iter().chain(&builtin).map(|&s| s.name.chars().count()).max().unwrap_or(0);
+    let max_groups_len = max(
+        "groups".len(),
+        plugin.iter().chain(&builtin).map(|&s| groups_of(s).chars().count()).max().unwrap_or(0),
+    );
     let padded = |x: &str| {
         let mut s = " ".repeat(max_name_len - x.chars().count());
         s.push_str(x);
@@ -858,13 +945,34 @@ Available lint options:
     };
 
     println!("Lint checks provided by rustc:\n");
-    println!("    {}  {:7.7}  {}", padded("name"), "default", "meaning");
-    println!("    {}  {:7.7}  {}", padded("----"), "-------", "-------");
+    println!(
+        "    {}  {:7.7}  {:groups$}  {}",
+        padded("name"),
+        "default",
+        "groups",
+        "meaning",
+        groups = max_groups_len
+    );
+    println!(
+        "    {}  {:7.7}  {:groups$}  {}",
+        padded("----"),
+        "-------",
+        "------",
+        "-------",
+        groups = max_groups_len
+    );
 
-    let print_lints = |lints: Vec<&Lint>| {
+    let print_lints = |lints: Vec<&'static Lint>| {
         for lint in lints {
             let name = lint.name_lower().replace("_", "-");
-            println!("    {}  {:7.7}  {}", padded(&name), lint.default_level.as_str(), lint.desc);
+            println!(
+                "    {}  {:7.7}  {:groups$}  {}",
+                padded(&name),
+                lint.default_level(sess.edition()).as_str(),
+                groups_of(lint),
+                lint.desc,
+                groups = max_groups_len
+            );
         }
         println!("\n");
     };
@@ -890,7 +998,9 @@ Available lint options:
     println!("Lint groups provided by rustc:\n");
     println!("    {}  {}", padded("name"), "sub-lints");
     println!("    {}  {}", padded("----"), "---------");
-    println!("    {}  {}", padded("warnings"), "all lints that are set to issue warnings");
+    if filter.groups.is_empty() && filter.tool.is_none() {
+        println!("    {}  {}", padded("warnings"), "all lints that are set to issue warnings");
+    }
 
     let print_lint_groups = |lints: Vec<(&'static str, Vec<lint::LintId>)>| {
         for (name, to) in lints {
@@ -907,6 +1017,10 @@ Available lint options:
 
     print_lint_groups(builtin_groups);
 
+    if !filter.plugins {
+        return;
+    }
+
     match (loaded_plugins, plugin.len(), plugin_groups.len()) {
         (false, 0, _) | (false, _, 0) => {
             println!(
@@ -916,19 +1030,191 @@ Available lint options:
         }
         (false, ..) => panic!("didn't load lint plugins but got them anyway!"),
         (true, 0, 0) => println!("This crate does not load any lint plugins or lint groups."),
-        (true, l, g) => {
-            if l > 0 {
-                println!("Lint checks provided by plugins loaded by this crate:\n");
-                print_lints(plugin);
+        (true, ..) => {
+            // One section for each plugin, tool or driver the lints come from.
+            let mut lints_by_provenance = BTreeMap::new();
+            for lint in plugin {
+                let provenance = lint_store.lint_provenance(lint);
+                lints_by_provenance.entry(provenance).or_insert_with(Vec::new).push(lint);
             }
-            if g > 0 {
-                println!("Lint groups provided by plugins loaded by this crate:\n");
-                print_lint_groups(plugin_groups);
+            for (provenance, lints) in lints_by_provenance {
+                println!("Lint checks provided by {}:\n", provenance);
+                print_lints(lints);
+            }
+
+            let mut groups_by_provenance = BTreeMap::new();
+            for group in plugin_groups {
+                let provenance = lint_store.group_provenance(group.0);
+                groups_by_provenance.entry(provenance).or_insert_with(Vec::new).push(group);
+            }
+            for (provenance, groups) in groups_by_provenance {
+                println!("Lint groups provided by {}:\n", provenance);
+                print_lint_groups(groups);
             }
         }
     }
 }
 
+/// Whether `req` prints the lints of the lint store, which has to wait for the plugins of the
+/// crate, if there is one, to register theirs.
+fn prints_lint_store(req: PrintRequest) -> bool {
+    match req {
+        PrintRequest::LintsJson | PrintRequest::LintGroupsJson => true,
+        _ => false,
+    }
+}
+
+fn print_lint_store(sess: &Session, lint_store: &lint::LintStore, req: PrintRequest) {
+    match req {
+        PrintRequest::LintsJson => println!("{}", lints_json(sess, lint_store).pretty()),
+        PrintRequest::LintGroupsJson => println!("{}", lint_groups_json(lint_store).pretty()),
+        _ => unreachable!("`{:?}` does not print the lint store", req),
+    }
+}
+
+/// Maps every lint group in `lint_store` to its lints for `--print lint-groups-json`, along with
+/// the renamed and removed lints, so that the lint names in a configuration file can be checked
+/// against the compiler before a build.
+fn lint_groups_json(lint_store: &lint::LintStore) -> json::Json {
+    let groups = lint_store
+        .get_lint_groups()
+        .into_iter()
+        .map(|(name, lint_ids, _)| {
+            let mut lints: Vec<_> = lint_ids.iter().map(|id| id.to_string()).collect();
+            lints.sort();
+            (name.to_lowercase(), lints.to_json())
+        })
+        .collect();
+    let renamed = lint_store
+        .get_renamed_lints()
+        .into_iter()
+        .map(|(old_name, new_name)| (old_name.to_string(), new_name.to_json()))
+        .collect();
+    let removed = lint_store
+        .get_removed_lints()
+        .into_iter()
+        .map(|(name, reason)| (name.to_string(), reason.to_json()))
+        .collect();
+
+    let mut object = BTreeMap::new();
+    object.insert("groups".to_string(), json::Json::Object(groups));
+    object.insert("renamed".to_string(), json::Json::Object(renamed));
+    object.insert("removed".to_string(), json::Json::Object(removed));
+    json::Json::Object(object)
+}
+
+/// Describes where a lint or lint group comes from for `--print lints-json`, as an object with
+/// the `kind` of source and its `name`: the tool's name or the plugin's path.
+fn provenance_json(provenance: lint::LintProvenance) -> json::Json {
+    let (kind, name) = match provenance {
+        lint::LintProvenance::Rustc => ("rustc", None),
+        lint::LintProvenance::Plugin(path) => ("plugin", Some(path)),
+        lint::LintProvenance::Tool(tool) => ("tool", Some(tool)),
+        lint::LintProvenance::Driver => ("driver", None),
+    };
+    let mut object = BTreeMap::new();
+    object.insert("kind".to_string(), kind.to_json());
+    object.insert("name".to_string(), name.map_or(json::Json::Null, |name| name.to_json()));
+    json::Json::Object(object)
+}
+
+/// The doc comment of a lint without the space that follows `///` on each line, which
+/// `declare_lint!` keeps.
+fn lint_doc(doc: &str) -> String {
+    doc.lines()
+        .map(|line| if line.starts_with(' ') { &line[1..] } else { line })
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+/// Describes every lint and lint group in `lint_store` for `--print lints-json`, so that the lint
+/// listings in the documentation can be generated from the `declare_lint!` invocations.
+fn lints_json(sess: &Session, lint_store: &lint::LintStore) -> json::Json {
+    let level_json = |level: lint::Level| json::Json::String(level.as_str().to_string());
+    let mut groups_of_lint: FxHashMap<lint::LintId, Vec<json::Json>> = FxHashMap::default();
+    let mut groups = lint_store.get_lint_groups();
+    groups.sort_by_key(|&(name, ..)| name);
+
+    let groups = groups
+        .into_iter()
+        .map(|(name, lint_ids, from_plugin)| {
+            let name = name.to_lowercase();
+            for &lint_id in &lint_ids {
+                groups_of_lint.entry(lint_id).or_default().push(name.to_json());
+            }
+            let mut group = BTreeMap::new();
+            group.insert("name".to_string(), name.to_json());
+            group.insert(
+                "lints".to_string(),
+                json::Json::Array(lint_ids.iter().map(|id| id.to_string().to_json()).collect()),
+            );
+            group.insert("is_plugin".to_string(), from_plugin.to_json());
+            group.insert("source".to_string(), provenance_json(lint_store.group_provenance(&name)));
+            json::Json::Object(group)
+        })
+        .collect();
+
+    let mut lints = lint_store.get_lints().to_vec();
+    lints.sort_by_key(|lint| lint.name_lower());
+    let lints = lints
+        .into_iter()
+        .map(|lint| {
+            let mut object = BTreeMap::new();
+            object.insert("name".to_string(), lint.name_lower().to_json());
+            object.insert("default_level".to_string(), level_json(lint.default_level));
+            object.insert(
+                "edition_level".to_string(),
+                lint.edition_lint_opts.map_or(json::Json::Null, |(edition, level)| {
+                    let mut object = BTreeMap::new();
+                    object.insert("edition".to_string(), edition.to_string().to_json());
+                    object.insert("level".to_string(), level_json(level));
+                    json::Json::Object(object)
+                }),
+            );
+            object.insert(
+                "level_for_current_edition".to_string(),
+                level_json(lint.default_level(sess.edition())),
+            );
+            object.insert("desc".to_string(), lint.desc.to_json());
+            object.insert("doc".to_string(), lint_doc(lint.doc).to_json());
+            object.insert(
+                "groups".to_string(),
+                json::Json::Array(
+                    groups_of_lint.get(&lint::LintId::of(lint)).cloned().unwrap_or_default(),
+                ),
+            );
+            object.insert(
+                "future_incompatible".to_string(),
+                lint.future_incompatible.map_or(json::Json::Null, |info| {
+                    let mut object = BTreeMap::new();
+                    object.insert("reference".to_string(), info.reference.to_json());
+                    object.insert(
+                        "edition".to_string(),
+                        info.edition.map_or(json::Json::Null, |e| e.to_string().to_json()),
+                    );
+                    json::Json::Object(object)
+                }),
+            );
+            object.insert(
+                "report_in_external_macro".to_string(),
+                lint.report_in_external_macro.to_json(),
+            );
+            object.insert("is_plugin".to_string(), lint.is_plugin.to_json());
+            object.insert("source".to_string(), provenance_json(lint_store.lint_provenance(lint)));
+            object.insert(
+                "docs_url".to_string(),
+                lint::lint_docs_url(lint).map_or(json::Json::Null, |url| url.to_json()),
+            );
+            json::Json::Object(object)
+        })
+        .collect();
+
+    let mut object = BTreeMap::new();
+    object.insert("lints".to_string(), json::Json::Array(lints));
+    object.insert("groups".to_string(), json::Json::Array(groups));
+    json::Json::Object(object)
+}
+
 fn describe_debug_flags() {
     println!("\nAvailable options:\n");
     print_flag_list("-Z", config::DB_OPTIONS);
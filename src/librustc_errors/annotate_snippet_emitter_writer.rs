@@ -93,7 +93,7 @@ impl<'a> DiagnosticConverter<'a> {
                 title: Some(Annotation {
                     label: Some(self.message.to_string()),
                     id: self.code.clone().map(|c| match c {
-                        DiagnosticId::Error(val) | DiagnosticId::Lint(val) => val,
+                        DiagnosticId::Error(val) | DiagnosticId::Lint { name: val, .. } => val,
                     }),
                     annotation_type: Self::annotation_type_for_level(self.level),
                 }),
@@ -27,7 +27,8 @@ pub struct Diagnostic {
 #[derive(Clone, Debug, PartialEq, Eq, Hash, RustcEncodable, RustcDecodable)]
 pub enum DiagnosticId {
     Error(String),
-    Lint(String),
+    /// A lint, along with the page documenting it, if there is one.
+    Lint { name: String, docs_url: Option<String> },
 }
 
 /// For example a note attached to an error.
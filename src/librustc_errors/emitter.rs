@@ -23,6 +23,7 @@ use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::sync::Lrc;
 use std::borrow::Cow;
 use std::cmp::{max, min, Reverse};
+use std::env;
 use std::io;
 use std::io::prelude::*;
 use std::path::Path;
@@ -503,6 +504,9 @@ pub struct EmitterWriter {
     terminal_width: Option<usize>,
 
     external_macro_backtrace: bool,
+
+    /// Whether to link lint names to their documentation with OSC 8 escape sequences.
+    terminal_links: bool,
 }
 
 #[derive(Debug)]
@@ -521,6 +525,9 @@ impl EmitterWriter {
         terminal_width: Option<usize>,
         external_macro_backtrace: bool,
     ) -> EmitterWriter {
+        let terminal_links = color_config.suggests_using_colors()
+            && atty::is(atty::Stream::Stderr)
+            && terminal_supports_hyperlinks();
         let dst = Destination::from_stderr(color_config);
         EmitterWriter {
             dst,
@@ -530,6 +537,7 @@ impl EmitterWriter {
             ui_testing: false,
             terminal_width,
             external_macro_backtrace,
+            terminal_links,
         }
     }
 
@@ -550,6 +558,7 @@ impl EmitterWriter {
             ui_testing: false,
             terminal_width,
             external_macro_backtrace,
+            terminal_links: false,
         }
     }
 
@@ -1181,11 +1190,25 @@ impl EmitterWriter {
             if *level != Level::FailureNote && !level_str.is_empty() {
                 buffer.append(0, &level_str, Style::Level(level.clone()));
             }
-            // only render error codes, not lint codes
-            if let Some(DiagnosticId::Error(ref code)) = *code {
-                buffer.append(0, "[", Style::Level(level.clone()));
-                buffer.append(0, &code, Style::Level(level.clone()));
-                buffer.append(0, "]", Style::Level(level.clone()));
+            // only render error codes, not lint codes, unless the lint name can be a link to its
+            // documentation
+            match *code {
+                Some(DiagnosticId::Error(ref code)) => {
+                    buffer.append(0, "[", Style::Level(level.clone()));
+                    buffer.append(0, &code, Style::Level(level.clone()));
+                    buffer.append(0, "]", Style::Level(level.clone()));
+                }
+                Some(DiagnosticId::Lint { ref name, docs_url: Some(ref url) })
+                    if self.terminal_links =>
+                {
+                    // The escape sequences don't take up any room, so it doesn't matter that the
+                    // buffer counts them as characters on this line.
+                    let link = format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, name);
+                    buffer.append(0, "[", Style::Level(level.clone()));
+                    buffer.append(0, &link, Style::Level(level.clone()));
+                    buffer.append(0, "]", Style::Level(level.clone()));
+                }
+                _ => {}
             }
             if *level != Level::FailureNote && !level_str.is_empty() {
                 buffer.append(0, ": ", header_style);
@@ -1968,6 +1991,33 @@ pub enum WritableDst<'a> {
     ColoredRaw(Ansi<&'a mut (dyn Write + Send)>),
 }
 
+/// Whether the terminal is known to render OSC 8 hyperlinks, as the ones that don't would
+/// print the escape sequences instead.
+fn terminal_supports_hyperlinks() -> bool {
+    if let Some(force) = env::var_os("FORCE_HYPERLINK") {
+        return force != "0";
+    }
+    if env::var_os("CI").is_some() {
+        return false;
+    }
+    if env::var_os("DOMTERM").is_some() || env::var_os("WT_SESSION").is_some() {
+        return true;
+    }
+    // VTE based terminals support them since 0.50.
+    if let Some(version) = env::var("VTE_VERSION").ok().and_then(|v| v.parse::<u32>().ok()) {
+        if version >= 5000 {
+            return true;
+        }
+    }
+    match env::var("TERM_PROGRAM").as_ref().map(|program| &program[..]) {
+        Ok("Hyper") | Ok("iTerm.app") | Ok("terminology") | Ok("WezTerm") | Ok("vscode") => {
+            return true;
+        }
+        _ => {}
+    }
+    env::var("TERM").map_or(false, |term| term == "xterm-kitty")
+}
+
 impl Destination {
     fn from_stderr(color: ColorConfig) -> Destination {
         let choice = color.to_color_choice();
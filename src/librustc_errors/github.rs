@@ -0,0 +1,112 @@
+//! An emitter printing diagnostics as GitHub Actions workflow commands.
+//!
+//! Each diagnostic becomes one `::error` or `::warning` line, which GitHub turns into an
+//! annotation on the line of the pull request the diagnostic points at:
+//!
+//! ```text
+//! ::warning file=a.rs,line=2,col=9,endLine=2,endColumn=10::unused_variables: unused variable: `x`
+//! ```
+
+use crate::emitter::Emitter;
+use crate::{Diagnostic, DiagnosticId, Level, SuggestionStyle};
+
+use rustc_data_structures::sync::Lrc;
+use rustc_span::source_map::SourceMap;
+use std::io::{self, Write};
+
+pub struct GithubEmitter {
+    dst: Box<dyn Write + Send>,
+    sm: Lrc<SourceMap>,
+}
+
+impl GithubEmitter {
+    pub fn stderr(source_map: Lrc<SourceMap>) -> GithubEmitter {
+        GithubEmitter { dst: Box::new(io::stderr()), sm: source_map }
+    }
+
+    pub fn new(dst: Box<dyn Write + Send>, source_map: Lrc<SourceMap>) -> GithubEmitter {
+        GithubEmitter { dst, sm: source_map }
+    }
+
+    /// The `file`, `line` and `col` properties of the annotation, if the diagnostic has a
+    /// primary span in a real file.
+    fn location(&self, diag: &Diagnostic) -> Option<String> {
+        let span = diag.span.primary_span().filter(|span| !span.is_dummy())?;
+        let lo = self.sm.lookup_char_pos(span.lo());
+        let hi = self.sm.lookup_char_pos(span.hi());
+        if !lo.file.is_real_file() {
+            return None;
+        }
+        Some(format!(
+            "file={},line={},col={},endLine={},endColumn={}",
+            escape_property(&lo.file.name.to_string()),
+            lo.line,
+            lo.col.0 + 1,
+            hi.line,
+            hi.col.0 + 1,
+        ))
+    }
+}
+
+impl Emitter for GithubEmitter {
+    fn emit_diagnostic(&mut self, diag: &Diagnostic) {
+        let command = match diag.level {
+            Level::Bug | Level::Fatal | Level::Error => "error",
+            Level::Warning => "warning",
+            Level::Note | Level::Help => "notice",
+            Level::Cancelled | Level::FailureNote => return,
+        };
+
+        let mut message = match diag.code {
+            Some(DiagnosticId::Lint { ref name, .. }) | Some(DiagnosticId::Error(ref name)) => {
+                format!("{}: {}", name, diag.message())
+            }
+            None => diag.message(),
+        };
+        for child in &diag.children {
+            message.push_str(&format!("\n{}: {}", child.level, child.message()));
+        }
+        for sugg in &diag.suggestions {
+            let show_code = match sugg.style {
+                SuggestionStyle::CompletelyHidden => continue,
+                SuggestionStyle::ShowCode | SuggestionStyle::ShowAlways => true,
+                SuggestionStyle::HideCodeInline | SuggestionStyle::HideCodeAlways => false,
+            };
+            match &sugg.substitutions[..] {
+                [substitution] if show_code && substitution.parts.len() == 1 => {
+                    let snippet = &substitution.parts[0].snippet;
+                    message.push_str(&format!("\nhelp: {}: `{}`", sugg.msg, snippet));
+                }
+                _ => message.push_str(&format!("\nhelp: {}", sugg.msg)),
+            }
+        }
+
+        let result = match self.location(diag) {
+            Some(location) => {
+                writeln!(self.dst, "::{} {}::{}", command, location, escape_data(&message))
+            }
+            None => writeln!(self.dst, "::{}::{}", command, escape_data(&message)),
+        };
+        if let Err(e) = result {
+            panic!("failed to print diagnostics: {:?}", e);
+        }
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        Some(&self.sm)
+    }
+
+    fn should_show_explain(&self) -> bool {
+        false
+    }
+}
+
+/// Escapes the message of a workflow command, which ends at the end of the line.
+fn escape_data(s: &str) -> String {
+    s.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Escapes the value of a workflow command property, which also ends at a `,` or `:`.
+fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
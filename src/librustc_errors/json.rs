@@ -420,7 +420,7 @@ impl DiagnosticCode {
         s.map(|s| {
             let s = match s {
                 DiagnosticId::Error(s) => s,
-                DiagnosticId::Lint(s) => s,
+                DiagnosticId::Lint { name, .. } => name,
             };
             let explanation =
                 je.registry.as_ref().and_then(|registry| registry.find_description(&s));
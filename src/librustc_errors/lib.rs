@@ -19,7 +19,8 @@ use rustc_data_structures::stable_hasher::StableHasher;
 use rustc_data_structures::sync::{self, Lock, Lrc};
 use rustc_data_structures::AtomicRef;
 use rustc_span::source_map::SourceMap;
-use rustc_span::{Loc, MultiSpan, Span};
+use rustc_span::hygiene::{ExpnId, ExpnKind, MacroKind};
+use rustc_span::{Loc, MultiSpan, Span, DUMMY_SP};
 
 use std::borrow::Cow;
 use std::panic;
@@ -32,6 +33,7 @@ pub mod annotate_snippet_emitter_writer;
 mod diagnostic;
 mod diagnostic_builder;
 pub mod emitter;
+pub mod github;
 pub mod json;
 mod lock;
 pub mod registry;
@@ -298,6 +300,22 @@ struct HandlerInner {
     /// The stashed diagnostics count towards the total error count.
     /// When `.abort_if_errors()` is called, these are also emitted.
     stashed_diagnostics: FxIndexMap<(Span, StashKey), Diagnostic>,
+
+    /// The last lint emitted from a macro expansion, held back so that consecutive instances of
+    /// the same lint in the same expansion can be reported as one.
+    pending_lint: Option<PendingLint>,
+
+    /// Lints held back by `-Z sort-lints`.
+    sorted_lints: Vec<Diagnostic>,
+}
+
+struct PendingLint {
+    lint: String,
+    expn: ExpnId,
+    /// The invocation of the macro the lint was emitted in.
+    call_site: Span,
+    diagnostic: Diagnostic,
+    count: usize,
 }
 
 /// A key denoting where from a diagnostic was stashed.
@@ -306,6 +324,28 @@ pub enum StashKey {
     ItemNoType,
 }
 
+/// For a lint reported inside a function-like macro, returns the lint's name and the outermost
+/// such expansion (with its invocation) that the primary span comes from.
+fn lint_expansion(diagnostic: &Diagnostic) -> Option<(String, ExpnId, Span)> {
+    let lint = match diagnostic.code {
+        Some(DiagnosticId::Lint { ref name, .. }) => name.clone(),
+        _ => return None,
+    };
+    let mut span = diagnostic.span.primary_span()?;
+    let mut outermost = None;
+    loop {
+        let expn_data = span.ctxt().outer_expn_data();
+        match expn_data.kind {
+            ExpnKind::Macro(MacroKind::Bang, _) => {
+                outermost = Some((span.ctxt().outer_expn(), expn_data.call_site));
+                span = expn_data.call_site;
+            }
+            _ => break,
+        }
+    }
+    outermost.map(|(expn, call_site)| (lint, expn, call_site))
+}
+
 fn default_track_diagnostic(_: &Diagnostic) {}
 
 pub static TRACK_DIAGNOSTICS: AtomicRef<fn(&Diagnostic)> =
@@ -330,11 +370,16 @@ pub struct HandlerFlags {
     pub external_macro_backtrace: bool,
     /// If true, identical diagnostics are reported only once.
     pub deduplicate_diagnostics: bool,
+    /// If true, lints are held back until compilation ends or aborts, and then emitted
+    /// ordered by file, position and lint name.
+    /// (rustc: see `-Z sort-lints`)
+    pub sort_lints: bool,
 }
 
 impl Drop for HandlerInner {
     fn drop(&mut self) {
         self.emit_stashed_diagnostics();
+        self.emit_sorted_lints();
 
         if !self.has_errors() {
             let bugs = std::mem::replace(&mut self.delayed_span_bugs, Vec::new());
@@ -406,6 +451,8 @@ impl Handler {
                 emitted_diagnostic_codes: Default::default(),
                 emitted_diagnostics: Default::default(),
                 stashed_diagnostics: Default::default(),
+                pending_lint: None,
+                sorted_lints: Vec::new(),
             }),
         }
     }
@@ -467,6 +514,14 @@ impl Handler {
         self.inner.borrow_mut().emit_stashed_diagnostics();
     }
 
+    /// Emits every diagnostic held back so far: the stashed ones, a lint waiting for its
+    /// repetitions in the same macro expansion, and the lints sorted by `-Z sort-lints`.
+    pub fn flush_diagnostics(&self) {
+        let mut inner = self.inner.borrow_mut();
+        inner.emit_stashed_diagnostics();
+        inner.emit_sorted_lints();
+    }
+
     /// Construct a dummy builder with `Level::Cancelled`.
     ///
     /// Using this will neither report anything to the user (e.g. a warning),
@@ -710,6 +765,7 @@ impl HandlerInner {
     fn emit_stashed_diagnostics(&mut self) {
         let diags = self.stashed_diagnostics.drain(..).map(|x| x.1).collect::<Vec<_>>();
         diags.iter().for_each(|diag| self.emit_diagnostic(diag));
+        self.emit_pending_lint();
     }
 
     fn emit_diagnostic(&mut self, diagnostic: &Diagnostic) {
@@ -727,6 +783,86 @@ impl HandlerInner {
             self.emitted_diagnostic_codes.insert(code.clone());
         }
 
+        if let Some((lint, expn, call_site)) = lint_expansion(diagnostic) {
+            if let Some(pending) = &mut self.pending_lint {
+                if pending.lint == lint && pending.expn == expn {
+                    pending.count += 1;
+                    if diagnostic.is_error() {
+                        self.bump_err_count();
+                    }
+                    return;
+                }
+            }
+            self.emit_pending_lint();
+            let diagnostic = diagnostic.clone();
+            let is_error = diagnostic.is_error();
+            self.pending_lint = Some(PendingLint { lint, expn, call_site, diagnostic, count: 1 });
+            if is_error {
+                self.bump_err_count();
+            }
+            return;
+        }
+        self.emit_pending_lint();
+
+        self.emit_unbuffered(diagnostic);
+        if diagnostic.is_error() {
+            self.bump_err_count();
+        }
+    }
+
+    /// Emits the lint held back by `emit_diagnostic`, reporting it at the macro invocation if it
+    /// was repeated. Its errors were already counted when it was received.
+    fn emit_pending_lint(&mut self) {
+        let PendingLint { call_site, mut diagnostic, count, .. } = match self.pending_lint.take() {
+            Some(pending) => pending,
+            None => return,
+        };
+        if count > 1 {
+            // The suggestions and labels are about the first instance only.
+            diagnostic.span = MultiSpan::from_span(call_site);
+            diagnostic.sort_span = call_site;
+            diagnostic.suggestions.clear();
+            diagnostic.note(&format!("repeated {} times in this macro expansion", count));
+        }
+        self.emit_unbuffered(&diagnostic);
+    }
+
+    fn emit_unbuffered(&mut self, diagnostic: &Diagnostic) {
+        if self.flags.sort_lints {
+            if let Some(DiagnosticId::Lint { .. }) = diagnostic.code {
+                self.sorted_lints.push(diagnostic.clone());
+                return;
+            }
+        }
+        self.emit_to_emitter(diagnostic);
+    }
+
+    /// Emits the lints held back under `-Z sort-lints`, so that their order doesn't depend on
+    /// the order the passes that reported them ran in.
+    fn emit_sorted_lints(&mut self) {
+        let mut lints = std::mem::replace(&mut self.sorted_lints, Vec::new());
+        let source_map = self.emitter.source_map().cloned();
+        lints.sort_by_cached_key(|diagnostic| {
+            let span = diagnostic.span.primary_span().unwrap_or(DUMMY_SP);
+            let (file, lo, hi) = match source_map {
+                Some(ref sm) if !span.is_dummy() => {
+                    let file = sm.lookup_source_file(span.lo());
+                    (file.name.to_string(), span.lo() - file.start_pos, span.hi() - file.start_pos)
+                }
+                _ => (String::new(), span.lo(), span.hi()),
+            };
+            let lint = match diagnostic.code {
+                Some(DiagnosticId::Lint { ref name, .. }) => name.clone(),
+                _ => String::new(),
+            };
+            (file, lo, hi, lint)
+        });
+        for lint in &lints {
+            self.emit_to_emitter(lint);
+        }
+    }
+
+    fn emit_to_emitter(&mut self, diagnostic: &Diagnostic) {
         let already_emitted = |this: &mut Self| {
             use std::hash::Hash;
             let mut hasher = StableHasher::new();
@@ -743,12 +879,10 @@ impl HandlerInner {
                 self.deduplicated_err_count += 1;
             }
         }
-        if diagnostic.is_error() {
-            self.bump_err_count();
-        }
     }
 
     fn emit_artifact_notification(&mut self, path: &Path, artifact_type: &str) {
+        self.emit_pending_lint();
         self.emitter.emit_artifact_notification(path, artifact_type);
     }
 
@@ -758,6 +892,7 @@ impl HandlerInner {
 
     fn print_error_count(&mut self, registry: &Registry) {
         self.emit_stashed_diagnostics();
+        self.emit_sorted_lints();
 
         let s = match self.deduplicated_err_count {
             0 => return,
@@ -823,6 +958,7 @@ impl HandlerInner {
         self.emit_stashed_diagnostics();
 
         if self.has_errors() {
+            self.emit_sorted_lints();
             FatalError.raise();
         }
     }
@@ -550,6 +550,16 @@ declare_features! (
     /// Allows `T: ?const Trait` syntax in bounds.
     (active, const_trait_bound_opt_out, "1.42.0", Some(67794), None),
 
+    /// Allows `#[exhaustive]` to silence the `exhaustive_public_types` lint.
+    (active, exhaustive, "1.42.0", None, None),
+
+    /// Allows `expires` in lint attributes, to be reminded to revisit them.
+    (active, lint_expiry, "1.42.0", None, None),
+
+    /// Allows `#[rustc_blocking]` and `#[rustc_executor_entry_point]`, which tell the
+    /// `blocking_in_async` lint what blocks and what runs on an async executor.
+    (active, blocking_functions, "1.42.0", None, None),
+
     // -------------------------------------------------------------------------
     // feature-group-end: actual feature gates
     // -------------------------------------------------------------------------
@@ -328,6 +328,16 @@ pub const BUILTIN_ATTRIBUTES: &[BuiltinAttribute] = &[
 
     gated!(ffi_returns_twice, Whitelisted, template!(Word), experimental!(ffi_returns_twice)),
     gated!(track_caller, Whitelisted, template!(Word), experimental!(track_caller)),
+    gated!(exhaustive, Whitelisted, template!(Word), experimental!(exhaustive)),
+    // For the `blocking_in_async` lint.
+    gated!(
+        rustc_blocking, Whitelisted, template!(Word), blocking_functions,
+        experimental!(rustc_blocking),
+    ),
+    gated!(
+        rustc_executor_entry_point, Whitelisted, template!(Word), blocking_functions,
+        experimental!(rustc_executor_entry_point),
+    ),
     gated!(
         register_attr, CrateLevel, template!(List: "attr1, attr2, ..."),
         experimental!(register_attr),
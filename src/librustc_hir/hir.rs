@@ -821,6 +821,7 @@ pub struct FieldPat<'hir> {
     /// The pattern the field is destructured to.
     pub pat: &'hir Pat<'hir>,
     pub is_shorthand: bool,
+    pub attrs: &'hir [Attribute],
     pub span: Span,
 }
 
@@ -1171,6 +1172,7 @@ pub struct Field<'hir> {
     pub expr: &'hir Expr<'hir>,
     pub span: Span,
     pub is_shorthand: bool,
+    pub attrs: &'hir [Attribute],
 }
 
 #[derive(Copy, Clone, PartialEq, RustcEncodable, RustcDecodable, Debug, HashStable_Generic)]
@@ -179,6 +179,12 @@ pub fn run_compiler_in_existing_thread_pool<R>(
 
     let r = {
         let _sess_abort_error = OnDrop(|| {
+            compiler.sess.report_capped_lints();
+            compiler.sess.print_lint_summary();
+            compiler.sess.print_lint_coverage();
+            // The lints held back by the handler are part of the reports.
+            compiler.sess.diagnostic().flush_diagnostics();
+            rustc_lint::write_lint_reports(&compiler.sess);
             compiler.sess.diagnostic().print_error_count(registry);
         });
 
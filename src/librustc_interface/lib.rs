@@ -13,6 +13,7 @@ extern crate libc;
 
 mod callbacks;
 pub mod interface;
+pub mod lint_testing;
 mod passes;
 mod proc_macro_decls;
 mod queries;
@@ -0,0 +1,100 @@
+//! Runs lint passes over a source snippet and returns the diagnostics they render, so that lint
+//! passes can have focused unit tests next to their code instead of a UI test for every case.
+//!
+//! ```ignore (needs a sysroot)
+//! let output = lint_testing::check_early_pass("fn lintme() {}", &[&TEST_LINT], || box Pass);
+//! assert!(output.contains("warning: item is named 'lintme'"));
+//! ```
+//!
+//! The snippet is compiled as a library named `lint_test`, through analysis, with the built-in
+//! lints running along with the given pass; it is shown as `<lint_test.rs>` in the diagnostics.
+
+use crate::interface::{self, Config};
+use crate::util::Sink;
+
+use rustc::lint::{EarlyLintPassObject, LateLintPassObject, Lint, LintStore};
+use rustc::session::config::{self, CrateType, Input};
+use rustc::session::{DiagnosticOutput, Session};
+use rustc_errors::registry::Registry;
+use rustc_errors::FatalErrorMarker;
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_span::FileName;
+use std::panic;
+use std::sync::{Arc, Mutex};
+
+/// Compiles `src` with `pass` as an early lint pass, `lints` being the lints it emits, and
+/// returns the rendered diagnostics.
+pub fn check_early_pass(
+    src: &str,
+    lints: &[&'static Lint],
+    pass: fn() -> EarlyLintPassObject,
+) -> String {
+    let lints = lints.to_vec();
+    check_lints(src, move |_, store| {
+        store.register_lints(&lints);
+        store.register_early_pass(pass);
+    })
+}
+
+/// Compiles `src` with `pass` as a late lint pass, `lints` being the lints it emits, and
+/// returns the rendered diagnostics.
+pub fn check_late_pass(
+    src: &str,
+    lints: &[&'static Lint],
+    pass: fn() -> LateLintPassObject,
+) -> String {
+    let lints = lints.to_vec();
+    check_lints(src, move |_, store| {
+        store.register_lints(&lints);
+        store.register_late_pass(pass);
+    })
+}
+
+/// Compiles `src` after calling `register_lints` on the lint store, as drivers do through
+/// `Config::register_lints`, and returns the rendered diagnostics. Passing a callback that does
+/// nothing runs the built-in lints alone.
+pub fn check_lints(
+    src: &str,
+    register_lints: impl Fn(&Session, &mut LintStore) + Send + Sync + 'static,
+) -> String {
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let config = Config {
+        opts: config::Options { crate_types: vec![CrateType::Rlib], ..config::Options::default() },
+        crate_cfg: Default::default(),
+        input: Input::Str {
+            name: FileName::Custom("lint_test.rs".to_string()),
+            input: src.to_string(),
+        },
+        input_path: None,
+        output_dir: None,
+        output_file: None,
+        file_loader: None,
+        diagnostic_output: DiagnosticOutput::Raw(Box::new(Sink(output.clone()))),
+        stderr: None,
+        crate_name: Some("lint_test".to_string()),
+        lint_caps: Default::default(),
+        register_lints: Some(Box::new(register_lints)),
+        override_queries: None,
+        registry: Registry::new(&[]),
+    };
+
+    // Errors abort the compilation by unwinding, which still leaves their diagnostics rendered.
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        interface::run_compiler(config, |compiler| {
+            compiler.enter(|queries| {
+                queries.global_ctxt()?.peek_mut().enter(|tcx| tcx.analysis(LOCAL_CRATE))
+            })
+        })
+    }));
+    if let Err(value) = result {
+        if !value.is::<FatalErrorMarker>() {
+            panic::resume_unwind(value);
+        }
+    }
+
+    let output = output.lock().unwrap();
+    String::from_utf8_lossy(&output).into_owned()
+}
+
+#[cfg(test)]
+mod tests;
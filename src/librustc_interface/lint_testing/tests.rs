@@ -0,0 +1,71 @@
+use super::*;
+
+use rustc::lint::{EarlyContext, EarlyLintPass, LateContext, LateLintPass, LintContext};
+use rustc_hir as hir;
+use rustc_session::{declare_lint, declare_lint_pass};
+use syntax::ast;
+
+// The snippets are `no_core`, so that the tests don't need a sysroot.
+const NO_CORE: &str = "#![feature(lang_items, no_core)]\n#![no_core]\n\n#[lang = \"sized\"]\n\
+                       pub trait Sized {}\n";
+
+fn lintme(attrs: &str) -> String {
+    format!("{}{}\npub fn lintme() {{}}\n", attrs, NO_CORE)
+}
+
+declare_lint! {
+    TEST_LINT,
+    Warn,
+    "detects items named `lintme`"
+}
+
+declare_lint_pass!(EarlyPass => [TEST_LINT]);
+
+impl EarlyLintPass for EarlyPass {
+    fn check_item(&mut self, cx: &EarlyContext<'_>, it: &ast::Item) {
+        if it.ident.name.as_str() == "lintme" {
+            cx.span_lint(TEST_LINT, it.span, "item is named `lintme`");
+        }
+    }
+}
+
+declare_lint_pass!(LatePass => [TEST_LINT]);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for LatePass {
+    fn check_item(&mut self, cx: &LateContext<'_, '_>, it: &hir::Item<'_>) {
+        if it.ident.name.as_str() == "lintme" {
+            cx.span_lint(TEST_LINT, it.span, "item is named `lintme`");
+        }
+    }
+}
+
+#[test]
+fn early_pass() {
+    let output = check_early_pass(&lintme(""), &[&TEST_LINT], || box EarlyPass);
+    assert!(output.starts_with("warning: item is named `lintme`\n --> <lint_test.rs>:7:1\n"));
+    assert!(output.contains("= note: `#[warn(test_lint)]` on by default"));
+}
+
+#[test]
+fn late_pass() {
+    let output = check_late_pass(&lintme(""), &[&TEST_LINT], || box LatePass);
+    assert!(output.starts_with("warning: item is named `lintme`\n --> <lint_test.rs>:7:1\n"));
+}
+
+#[test]
+fn lint_levels() {
+    let output =
+        check_early_pass(&lintme("#![deny(test_lint)]\n"), &[&TEST_LINT], || box EarlyPass);
+    assert!(output.starts_with("error: item is named `lintme`\n"));
+    assert!(output.contains("error: aborting due to previous error"));
+
+    let output =
+        check_early_pass(&lintme("#![allow(test_lint)]\n"), &[&TEST_LINT], || box EarlyPass);
+    assert_eq!(output, "");
+}
+
+#[test]
+fn builtin_lints() {
+    let output = check_lints(&format!("{}\nfn unused() {{}}\n", NO_CORE), |_, _| {});
+    assert!(output.starts_with("warning: function is never used: `unused`\n"));
+}
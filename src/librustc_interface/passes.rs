@@ -21,6 +21,7 @@ use rustc_builtin_macros;
 use rustc_codegen_ssa::back::link::emit_metadata;
 use rustc_codegen_utils::codegen_backend::CodegenBackend;
 use rustc_codegen_utils::link::filename_for_metadata;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync::{par_iter, Lrc, Once, ParallelIterator, WorkerLocal};
 use rustc_data_structures::{box_region_allow_access, declare_box_region_type, parallel};
 use rustc_errors::PResult;
@@ -204,11 +205,55 @@ pub fn register_plugins<'a>(
         sess.time("plugin_loading", || plugin::load::load_plugins(sess, metadata_loader, &krate));
     sess.time("plugin_registration", || {
         let mut registry = plugin::Registry { lint_store: &mut lint_store };
-        for registrar in registrars {
+        for (registrar, path) in registrars {
+            registry.lint_store.set_registering_plugin(Some(path.display().to_string()));
             registrar(&mut registry);
         }
+        registry.lint_store.set_registering_plugin(None);
     });
 
+    if sess.opts.debugging_opts.lint_coverage {
+        let lints = lint_store.get_lints().iter().map(|&lint| lint::LintId::of(lint)).collect();
+        sess.lint_coverage_lints.set(lints);
+    }
+
+    // Plugin lints can be named too, so this has to wait until they are registered.
+    let threshold_lints = &sess.opts.debugging_opts.lint_warning_threshold_lints;
+    if !threshold_lints.is_empty() {
+        let mut lint_ids = FxHashSet::default();
+        for lint_name in threshold_lints {
+            match lint_store.find_lints(&lint_name.replace("-", "_")) {
+                Ok(ids) => lint_ids.extend(ids),
+                Err(_) => sess.err(&format!(
+                    "unknown lint or lint group in `-Z lint-warning-threshold-lints`: `{}`",
+                    lint_name
+                )),
+            }
+        }
+        sess.lint_warning_threshold_lints.set(lint_ids);
+    }
+
+    let extern_lint_levels = &sess.opts.extern_lint_levels;
+    if !extern_lint_levels.is_empty() {
+        let mut levels = FxHashMap::default();
+        for (krate, lint_name, level) in extern_lint_levels {
+            match lint_store.find_lints(lint_name) {
+                Ok(ids) => {
+                    let lints =
+                        levels.entry(Symbol::intern(krate)).or_insert_with(FxHashMap::default);
+                    for id in ids {
+                        lints.insert(id, *level);
+                    }
+                }
+                Err(_) => sess.err(&format!(
+                    "unknown lint or lint group in `--extern-lint-level`: `{}`",
+                    lint_name
+                )),
+            }
+        }
+        sess.extern_lint_levels.set(levels);
+    }
+
     Ok((krate, Lrc::new(lint_store)))
 }
 
@@ -225,6 +270,7 @@ fn configure_and_expand_inner<'a>(
             sess,
             lint_store,
             &krate,
+            crate_name,
             true,
             None,
             rustc_lint::BuiltinCombinedPreExpansionLintPass::new(),
@@ -424,6 +470,7 @@ pub fn lower_to_hir<'res, 'tcx>(
     resolver: &'res mut Resolver<'_>,
     dep_graph: &'res DepGraph,
     krate: &'res ast::Crate,
+    crate_name: &str,
     arena: &'tcx Arena<'tcx>,
 ) -> Result<map::Forest<'tcx>> {
     // Lower AST to HIR.
@@ -447,6 +494,7 @@ pub fn lower_to_hir<'res, 'tcx>(
             sess,
             lint_store,
             &krate,
+            crate_name,
             false,
             Some(std::mem::take(resolver.lint_buffer())),
             rustc_lint::BuiltinCombinedEarlyLintPass::new(),
@@ -532,6 +580,12 @@ fn escape_dep_filename(filename: &FileName) -> String {
     filename.to_string().replace(" ", "\\ ")
 }
 
+/// Escapes the value of an environment variable for a `# env-dep:` line of the dep-info file,
+/// which would otherwise end at a newline.
+fn escape_dep_env(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\n', "\\n").replace('\r', "\\r")
+}
+
 fn write_out_deps(
     sess: &Session,
     boxed_resolver: &Steal<Rc<RefCell<BoxedResolver>>>,
@@ -584,6 +638,19 @@ fn write_out_deps(
         for path in files {
             writeln!(file, "{}:", path)?;
         }
+
+        // The lint levels of `RUSTC_LINT_LEVELS` change the output too, so tell build systems
+        // reading the dep-info, as Cargo does, to rebuild when the variable is set, changed or
+        // unset.
+        match env::var(config::LINT_LEVELS_ENV_VAR) {
+            Ok(value) => writeln!(
+                file,
+                "# env-dep:{}={}",
+                config::LINT_LEVELS_ENV_VAR,
+                escape_dep_env(&value)
+            )?,
+            Err(_) => writeln!(file, "# env-dep:{}", config::LINT_LEVELS_ENV_VAR)?,
+        }
         Ok(())
     })();
 
@@ -648,6 +715,7 @@ pub fn prepare_outputs(
     }
 
     write_out_deps(sess, boxed_resolver, &outputs, &output_paths);
+    sess.lint_report_outputs.set((crate_name.to_string(), outputs.clone()));
 
     let only_dep_info = sess.opts.output_types.contains_key(&OutputType::DepInfo)
         && sess.opts.output_types.len() == 1;
@@ -967,6 +1035,10 @@ pub fn start_codegen<'tcx>(
         codegen_backend.codegen_crate(tcx, metadata, need_metadata_module)
     });
 
+    // Collecting the mono items emits the last lints of the crate, like those of const
+    // propagation, so the `allow` attributes can only be judged now.
+    tcx.sess.time("unused_allow_checking", || rustc_lint::check_unused_allows(tcx));
+
     if log_enabled!(::log::Level::Info) {
         println!("Post-codegen");
         tcx.print_debug_stats();
@@ -219,6 +219,7 @@ impl<'tcx> Queries<'tcx> {
             let krate = &peeked.0;
             let resolver = peeked.1.steal();
             let lint_store = &peeked.2;
+            let crate_name = self.crate_name()?.peek().clone();
             let hir = resolver.borrow_mut().access(|resolver| {
                 passes::lower_to_hir(
                     self.session(),
@@ -226,6 +227,7 @@ impl<'tcx> Queries<'tcx> {
                     resolver,
                     &*self.dep_graph()?.peek(),
                     &krate,
+                    &crate_name,
                     &self.arena,
                 )
             })?;
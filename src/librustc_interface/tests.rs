@@ -6,8 +6,8 @@ use rustc::lint;
 use rustc::middle::cstore;
 use rustc::session::config::{build_configuration, build_session_options, to_crate_config};
 use rustc::session::config::{rustc_optgroups, ErrorOutputType, ExternLocation, Options, Passes};
-use rustc::session::config::{ExternEntry, LinkerPluginLto, LtoCli, SwitchWithOptPath};
-use rustc::session::config::{Externs, OutputType, OutputTypes, SymbolManglingVersion};
+use rustc::session::config::{ExternEntry, Externs, LinkerPluginLto, LintHelpFilter, LtoCli};
+use rustc::session::config::{OutputType, OutputTypes, SwitchWithOptPath, SymbolManglingVersion};
 use rustc::session::search_paths::SearchPath;
 use rustc::session::{build_session, Session};
 use rustc_data_structures::fx::FxHashSet;
@@ -107,6 +107,41 @@ fn test_can_print_warnings() {
     });
 }
 
+#[test]
+fn test_lint_help_filter() {
+    syntax::with_default_globals(|| {
+        let matches = optgroups().parse(&["-Whelp".to_string()]).unwrap();
+        let (sessopts, _) = build_session_options_and_crate_config(matches);
+        assert!(sessopts.describe_lints);
+        assert_eq!(sessopts.lint_help_filter, LintHelpFilter::default());
+    });
+
+    syntax::with_default_globals(|| {
+        let matches = optgroups()
+            .parse(&["-Whelp=rust-2018-idioms,no-plugins".to_string(), "-Wunused".to_string()])
+            .unwrap();
+        let (sessopts, _) = build_session_options_and_crate_config(matches);
+        assert!(sessopts.describe_lints);
+        assert_eq!(sessopts.lint_help_filter.groups, vec![String::from("rust_2018_idioms")]);
+        assert_eq!(sessopts.lint_help_filter.tool, None);
+        assert!(!sessopts.lint_help_filter.plugins);
+        assert_eq!(sessopts.lint_opts, vec![(String::from("unused"), lint::Warn)]);
+    });
+
+    syntax::with_default_globals(|| {
+        let matches = optgroups().parse(&["-Whelp=tool:clippy".to_string()]).unwrap();
+        let (sessopts, _) = build_session_options_and_crate_config(matches);
+        assert_eq!(sessopts.lint_help_filter.tool.as_ref().unwrap(), "clippy");
+        assert!(sessopts.lint_help_filter.plugins);
+    });
+
+    syntax::with_default_globals(|| {
+        let matches = optgroups().parse(&["-Whelp=unused,Nonstandard-Style".to_string()]).unwrap();
+        let (sessopts, _) = build_session_options_and_crate_config(matches);
+        assert_eq!(sessopts.lint_help_filter.groups, vec!["unused", "nonstandard_style"]);
+    });
+}
+
 #[test]
 fn test_output_types_tracking_hash_different_paths() {
     let mut v1 = Options::default();
@@ -628,6 +663,44 @@ fn test_debugging_options_tracking_hash() {
     opts = reference.clone();
     opts.debugging_opts.symbol_mangling_version = SymbolManglingVersion::V0;
     assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+    opts = reference.clone();
+    opts.debugging_opts.private_dependencies = vec![String::from("some_dep")];
+    assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+    // The lints emitted during codegen aren't emitted again when incremental builds reuse its
+    // results, so the options they read must be tracked.
+    opts = reference.clone();
+    opts.debugging_opts.stack_frame_threshold = Some(1024);
+    assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+    opts = reference.clone();
+    opts.debugging_opts.large_future_threshold = 1024;
+    assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+    opts = reference.clone();
+    opts.debugging_opts.large_copy_threshold = 1024;
+    assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+    opts = reference.clone();
+    opts.debugging_opts.required_traits = vec![String::from("Debug")];
+    assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+    opts = reference.clone();
+    opts.debugging_opts.missing_docs_kinds = vec![String::from("fn")];
+    assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+    opts = reference.clone();
+    opts.debugging_opts.instantiation_threshold = 8;
+    assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+    opts = reference.clone();
+    opts.debugging_opts.large_const_data_threshold = 1024;
+    assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+    opts = reference.clone();
+    opts.debugging_opts.portable_pointer_widths = vec![16, 32, 64];
+    assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
 }
 
 #[test]
@@ -0,0 +1,219 @@
+use rustc::lint::{LateContext, LateLintPass, LintContext};
+use rustc::traits;
+use rustc::ty;
+use rustc_hir as hir;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::DefId;
+use rustc_hir::Node;
+use rustc_span::symbol::sym;
+use rustc_span::{MultiSpan, DUMMY_SP};
+use syntax::attr;
+
+/// Whether `hir_id` is inside an `async` block or `async fn`, without a
+/// non-async closure in between.
+pub(crate) fn in_async_body(cx: &LateContext<'_, '_>, hir_id: hir::HirId) -> bool {
+    match innermost_closure(cx, hir_id) {
+        Some((_, body_id)) => is_async_body(cx, body_id),
+        None => false,
+    }
+}
+
+/// The innermost closure, async block or `async fn` body around `hir_id`, if any, as the
+/// `HirId` of the closure expression and its body.
+fn innermost_closure(
+    cx: &LateContext<'_, '_>,
+    mut hir_id: hir::HirId,
+) -> Option<(hir::HirId, hir::BodyId)> {
+    let hir = cx.tcx.hir();
+    loop {
+        let parent_id = hir.get_parent_node(hir_id);
+        if parent_id == hir_id {
+            return None;
+        }
+        hir_id = parent_id;
+        match hir.find(hir_id) {
+            Some(Node::Expr(hir::Expr {
+                kind: hir::ExprKind::Closure(_, _, body_id, _, _),
+                ..
+            })) => return Some((hir_id, *body_id)),
+            Some(Node::Item(_)) | Some(Node::TraitItem(_)) | Some(Node::ImplItem(_)) | None => {
+                return None;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_async_body(cx: &LateContext<'_, '_>, body_id: hir::BodyId) -> bool {
+    match cx.tcx.hir().body(body_id).generator_kind {
+        Some(hir::GeneratorKind::Async(_)) => true,
+        _ => false,
+    }
+}
+
+declare_lint! {
+    pub NON_SEND_ACROSS_AWAIT,
+    Allow,
+    "detects values of non-`Send` types that are held across an `.await`"
+}
+
+declare_lint_pass!(
+    /// Checks for non-`Send` values that live across an await point of an async fn,
+    /// which makes the resulting future non-`Send`.
+    NonSendAcrossAwait => [NON_SEND_ACROSS_AWAIT]
+);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for NonSendAcrossAwait {
+    fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, expr: &'tcx hir::Expr<'tcx>) {
+        let body_id = match expr.kind {
+            hir::ExprKind::Closure(_, _, body_id, _, _) => body_id,
+            _ => return,
+        };
+        let body = cx.tcx.hir().body(body_id);
+        let kind = match body.generator_kind {
+            Some(hir::GeneratorKind::Async(kind)) => kind,
+            _ => return,
+        };
+
+        // By default only exported async fns are checked, as those are the ones whose
+        // futures are most likely to end up being spawned on a multi-threaded executor.
+        if !cx.sess().opts.debugging_opts.non_send_across_await_all {
+            let is_exported_fn = kind == hir::AsyncGeneratorKind::Fn
+                && cx.access_levels.is_exported(cx.tcx.hir().get_parent_item(expr.hir_id));
+            if !is_exported_fn {
+                return;
+            }
+        }
+
+        let send_trait = match cx.tcx.get_diagnostic_item(sym::send_trait) {
+            Some(def_id) => def_id,
+            None => return,
+        };
+
+        // FIXME: the tables only keep the interior types of one generator per body, so
+        // ignore anything that was not recorded for this one.
+        for cause in &cx.tables.generator_interior_types {
+            if !body.value.span.contains(cause.span) {
+                continue;
+            }
+
+            let ty = cx.tcx.erase_late_bound_regions(&ty::Binder::bind(cause.ty));
+            let ty = cx.tcx.erase_regions(&ty);
+            let is_send = cx.tcx.infer_ctxt().enter(|infcx| {
+                traits::type_known_to_meet_bound_modulo_regions(
+                    &infcx,
+                    cx.param_env,
+                    ty,
+                    send_trait,
+                    DUMMY_SP,
+                )
+            });
+            if is_send {
+                continue;
+            }
+
+            let mut spans = MultiSpan::from_span(cause.span);
+            spans.push_span_label(cause.span, format!("has type `{}` which is not `Send`", ty));
+            if let Some(yield_span) = cause.yield_span {
+                spans.push_span_label(
+                    yield_span,
+                    "await occurs here, while the value is alive".into(),
+                );
+            }
+            if let Some(scope_span) = cause.scope_span {
+                spans.push_span_label(
+                    cx.sess().source_map().end_point(scope_span),
+                    "the value is later dropped here".into(),
+                );
+            }
+
+            let mut err = cx.struct_span_lint(
+                NON_SEND_ACROSS_AWAIT,
+                spans,
+                "non-`Send` value is held across an await point",
+            );
+            err.note("this makes the enclosing future non-`Send`");
+            err.help("consider dropping the value or moving it into a narrower scope");
+            err.emit();
+        }
+    }
+}
+
+declare_lint! {
+    pub BLOCKING_IN_ASYNC,
+    Warn,
+    "detects calls to blocking functions inside async code"
+}
+
+declare_lint_pass!(
+    /// Checks for calls to functions marked `#[rustc_blocking]` inside async blocks and fns, and
+    /// inside closures passed to `#[rustc_executor_entry_point]` functions, where they stall the
+    /// executor thread instead of yielding.
+    BlockingInAsync => [BLOCKING_IN_ASYNC]
+);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for BlockingInAsync {
+    fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, expr: &'tcx hir::Expr<'tcx>) {
+        let def_id = match callee_def_id(cx, expr) {
+            Some(def_id) => def_id,
+            None => return,
+        };
+        if !attr::contains_name(&cx.tcx.get_attrs(def_id), sym::rustc_blocking) {
+            return;
+        }
+        let on_executor = match innermost_closure(cx, expr.hir_id) {
+            Some((closure_id, body_id)) => {
+                is_async_body(cx, body_id) || passed_to_executor(cx, closure_id)
+            }
+            None => false,
+        };
+        if !on_executor {
+            return;
+        }
+
+        let msg =
+            format!("call to blocking function `{}` in async code", cx.tcx.def_path_str(def_id));
+        cx.struct_span_lint(BLOCKING_IN_ASYNC, expr.span, &msg)
+            .note("blocking calls stall the executor thread and keep other tasks from running")
+            .help(
+                "use the async equivalent provided by your runtime, or move the call to a \
+                 dedicated thread",
+            )
+            .emit();
+    }
+}
+
+/// The function or method that `expr` calls, if it is a call whose callee is known.
+fn callee_def_id(cx: &LateContext<'_, '_>, expr: &hir::Expr<'_>) -> Option<DefId> {
+    match expr.kind {
+        hir::ExprKind::Call(ref callee, _) => match callee.kind {
+            hir::ExprKind::Path(ref qpath) => match cx.tables.qpath_res(qpath, callee.hir_id) {
+                Res::Def(DefKind::Fn, def_id) | Res::Def(DefKind::Method, def_id) => Some(def_id),
+                _ => None,
+            },
+            _ => None,
+        },
+        hir::ExprKind::MethodCall(..) => cx.tables.type_dependent_def_id(expr.hir_id),
+        _ => None,
+    }
+}
+
+/// Whether the closure `closure_id` is passed straight to a function marked
+/// `#[rustc_executor_entry_point]`, which runs it on the executor thread.
+fn passed_to_executor(cx: &LateContext<'_, '_>, closure_id: hir::HirId) -> bool {
+    let hir = cx.tcx.hir();
+    let call = match hir.find(hir.get_parent_node(closure_id)) {
+        Some(Node::Expr(call)) => call,
+        _ => return false,
+    };
+    let is_argument = match call.kind {
+        hir::ExprKind::Call(_, args) | hir::ExprKind::MethodCall(_, _, args) => {
+            args.iter().any(|arg| arg.hir_id == closure_id)
+        }
+        _ => false,
+    };
+    is_argument
+        && callee_def_id(cx, call).map_or(false, |def_id| {
+            attr::contains_name(&cx.tcx.get_attrs(def_id), sym::rustc_executor_entry_point)
+        })
+}
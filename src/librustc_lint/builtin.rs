@@ -23,22 +23,29 @@
 
 use rustc::hir::map::Map;
 use rustc::lint::{self, EarlyContext, EarlyLintPass, LateContext, LateLintPass, LintContext};
+use rustc::traits;
 use rustc::traits::misc::can_type_implement_copy;
-use rustc::ty::{self, layout::VariantIdx, Ty, TyCtxt};
-use rustc_data_structures::fx::FxHashSet;
+use rustc::ty::subst::{GenericArgKind, InternalSubsts, Subst, SubstsRef};
+use rustc::ty::{self, layout::VariantIdx, Instance, Ty, TyCtxt};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_errors::{Applicability, DiagnosticBuilder};
 use rustc_feature::Stability;
 use rustc_feature::{deprecated_attributes, AttributeGate, AttributeTemplate, AttributeType};
 use rustc_hir as hir;
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::DefId;
+use rustc_hir::intravisit::{self, Visitor};
 use rustc_hir::{GenericParamKind, PatKind};
 use rustc_hir::{HirIdSet, Node};
+use rustc_serialize::json::as_pretty_json;
+use rustc_session::config::CrateType;
 use rustc_session::lint::FutureIncompatibleInfo;
 use rustc_span::edition::Edition;
+use rustc_span::hygiene::{ExpnKind, MacroKind};
 use rustc_span::source_map::Spanned;
 use rustc_span::symbol::{kw, sym, Symbol};
-use rustc_span::{BytePos, Span};
+use rustc_span::{BytePos, MultiSpan, Span};
+use rustc_target::spec::abi::Abi;
 use syntax::ast::{self, Expr};
 use syntax::attr::{self, HasAttrs};
 use syntax::print::pprust::{self, expr_to_string};
@@ -48,12 +55,25 @@ use syntax::visit::FnKind;
 use crate::nonstandard_style::{method_context, MethodLateContext};
 
 use log::debug;
+use std::collections::{BTreeMap, VecDeque};
 use std::fmt::Write;
+use std::fs;
+use std::mem;
 
 // hardwired lints from librustc
 pub use lint::builtin::*;
 
 declare_lint! {
+    /// The `while_true` lint detects `while true { }`.
+    ///
+    /// ```rust,no_run
+    /// while true {
+    ///     // ...
+    /// }
+    /// ```
+    ///
+    /// `loop` says the same thing more directly, and lets the compiler know that the body runs
+    /// at least once, which matters for initialization checking and the type of the expression.
     WHILE_TRUE,
     Warn,
     "suggest using `loop { }` instead of `while true { }`"
@@ -210,7 +230,46 @@ declare_lint! {
     "usage of `unsafe` code"
 }
 
-declare_lint_pass!(UnsafeCode => [UNSAFE_CODE]);
+declare_lint! {
+    pub BROAD_UNSAFE_CODE_ALLOWS,
+    Allow,
+    "`allow(unsafe_code)` on a whole module or crate rather than on the code that needs it"
+}
+
+#[derive(Default)]
+pub struct UnsafeCode {
+    /// The names of the items around the node being checked, for `-Z unsafe-report`.
+    item_names: Vec<String>,
+
+    /// The `unsafe` code seen so far, for `-Z unsafe-report`.
+    usages: Vec<UnsafeUsage>,
+}
+
+impl_lint_pass!(UnsafeCode => [UNSAFE_CODE, BROAD_UNSAFE_CODE_ALLOWS]);
+
+/// The `-Z unsafe-report` inventory.
+#[derive(RustcEncodable)]
+struct UnsafeReport {
+    usages: Vec<UnsafeUsage>,
+}
+
+/// An `unsafe` block, function, impl or trait, as it appears in the `-Z unsafe-report` inventory.
+#[derive(RustcEncodable)]
+struct UnsafeUsage {
+    /// "block", "fn", "impl" or "trait".
+    kind: &'static str,
+    /// The path of the `unsafe` item, or of the item containing the `unsafe` block, starting
+    /// with `crate`.
+    item: String,
+    file_name: String,
+    /// 1-based.
+    line_start: usize,
+    line_end: usize,
+    /// 1-based, character offset.
+    column_start: usize,
+    column_end: usize,
+    in_macro: bool,
+}
 
 impl UnsafeCode {
     fn report_unsafe(&self, cx: &EarlyContext<'_>, span: Span, desc: &'static str) {
@@ -221,9 +280,53 @@ impl UnsafeCode {
 
         cx.span_lint(UNSAFE_CODE, span, desc);
     }
+
+    /// Reports the `allow(unsafe_code)` attributes among the attributes of a module or crate.
+    fn check_broad_allows(&self, cx: &EarlyContext<'_>, attrs: &[ast::Attribute], scope: &str) {
+        for attr in attrs.iter().filter(|attr| attr.check_name(sym::allow)) {
+            let items = attr.meta_item_list().unwrap_or_default();
+            for item in items.iter().filter(|item| item.check_name(sym::unsafe_code)) {
+                let msg = format!("`allow(unsafe_code)` applies to the whole {}", scope);
+                cx.struct_span_lint(BROAD_UNSAFE_CODE_ALLOWS, item.span(), &msg)
+                    .help(
+                        "allow `unsafe_code` on the functions, impls or blocks that contain the \
+                         unsafe code instead, so that each exemption can be reviewed on its own",
+                    )
+                    .emit();
+            }
+        }
+    }
+
+    /// Records `unsafe` code for `-Z unsafe-report`, whether or not `unsafe_code` warns about it.
+    fn record_unsafe(&mut self, cx: &EarlyContext<'_>, span: Span, kind: &'static str) {
+        if cx.sess.opts.debugging_opts.unsafe_report.is_none() {
+            return;
+        }
+        let lo = cx.sess.source_map().lookup_char_pos(span.lo());
+        let hi = cx.sess.source_map().lookup_char_pos(span.hi());
+        let mut item = String::from("crate");
+        for name in self.item_names.iter().filter(|name| !name.is_empty()) {
+            item.push_str("::");
+            item.push_str(name);
+        }
+        self.usages.push(UnsafeUsage {
+            kind,
+            item,
+            file_name: lo.file.name.to_string(),
+            line_start: lo.line,
+            line_end: hi.line,
+            column_start: lo.col.0 + 1,
+            column_end: hi.col.0 + 1,
+            in_macro: span.from_expansion(),
+        });
+    }
 }
 
 impl EarlyLintPass for UnsafeCode {
+    fn check_crate(&mut self, cx: &EarlyContext<'_>, krate: &ast::Crate) {
+        self.check_broad_allows(cx, &krate.attrs, "crate");
+    }
+
     fn check_attribute(&mut self, cx: &EarlyContext<'_>, attr: &ast::Attribute) {
         if attr.check_name(sym::allow_internal_unsafe) {
             self.report_unsafe(
@@ -241,24 +344,46 @@ impl EarlyLintPass for UnsafeCode {
             // Don't warn about generated blocks; that'll just pollute the output.
             if blk.rules == ast::BlockCheckMode::Unsafe(ast::UserProvided) {
                 self.report_unsafe(cx, blk.span, "usage of an `unsafe` block");
+                self.record_unsafe(cx, blk.span, "block");
             }
         }
     }
 
     fn check_item(&mut self, cx: &EarlyContext<'_>, it: &ast::Item) {
+        let name = match it.kind {
+            ast::ItemKind::Impl(_, _, _, _, ref trait_ref, ref self_ty, _) => match trait_ref {
+                Some(trait_ref) => format!(
+                    "<{} as {}>",
+                    pprust::ty_to_string(self_ty),
+                    pprust::path_to_string(&trait_ref.path)
+                ),
+                None => format!("<{}>", pprust::ty_to_string(self_ty)),
+            },
+            _ => it.ident.to_string(),
+        };
+        self.item_names.push(name);
+
         match it.kind {
+            ast::ItemKind::Mod(..) => self.check_broad_allows(cx, &it.attrs, "module"),
+
             ast::ItemKind::Trait(_, ast::Unsafety::Unsafe, ..) => {
-                self.report_unsafe(cx, it.span, "declaration of an `unsafe` trait")
+                self.report_unsafe(cx, it.span, "declaration of an `unsafe` trait");
+                self.record_unsafe(cx, it.span, "trait");
             }
 
             ast::ItemKind::Impl(ast::Unsafety::Unsafe, ..) => {
-                self.report_unsafe(cx, it.span, "implementation of an `unsafe` trait")
+                self.report_unsafe(cx, it.span, "implementation of an `unsafe` trait");
+                self.record_unsafe(cx, it.span, "impl");
             }
 
             _ => return,
         }
     }
 
+    fn check_item_post(&mut self, _: &EarlyContext<'_>, _: &ast::Item) {
+        self.item_names.pop();
+    }
+
     fn check_fn(
         &mut self,
         cx: &EarlyContext<'_>,
@@ -269,12 +394,14 @@ impl EarlyLintPass for UnsafeCode {
     ) {
         match fk {
             FnKind::ItemFn(_, ast::FnHeader { unsafety: ast::Unsafety::Unsafe, .. }, ..) => {
-                self.report_unsafe(cx, span, "declaration of an `unsafe` function")
+                self.report_unsafe(cx, span, "declaration of an `unsafe` function");
+                self.record_unsafe(cx, span, "fn");
             }
 
             FnKind::Method(_, sig, ..) => {
                 if sig.header.unsafety == ast::Unsafety::Unsafe {
-                    self.report_unsafe(cx, span, "implementation of an `unsafe` method")
+                    self.report_unsafe(cx, span, "implementation of an `unsafe` method");
+                    self.record_unsafe(cx, span, "fn");
                 }
             }
 
@@ -283,12 +410,37 @@ impl EarlyLintPass for UnsafeCode {
     }
 
     fn check_trait_item(&mut self, cx: &EarlyContext<'_>, item: &ast::AssocItem) {
+        self.item_names.push(item.ident.to_string());
         if let ast::AssocItemKind::Fn(ref sig, None) = item.kind {
             if sig.header.unsafety == ast::Unsafety::Unsafe {
-                self.report_unsafe(cx, item.span, "declaration of an `unsafe` method")
+                self.report_unsafe(cx, item.span, "declaration of an `unsafe` method");
+                self.record_unsafe(cx, item.span, "fn");
             }
         }
     }
+
+    fn check_trait_item_post(&mut self, _: &EarlyContext<'_>, _: &ast::AssocItem) {
+        self.item_names.pop();
+    }
+
+    fn check_impl_item(&mut self, _: &EarlyContext<'_>, item: &ast::AssocItem) {
+        self.item_names.push(item.ident.to_string());
+    }
+
+    fn check_impl_item_post(&mut self, _: &EarlyContext<'_>, _: &ast::AssocItem) {
+        self.item_names.pop();
+    }
+
+    fn check_crate_post(&mut self, cx: &EarlyContext<'_>, _: &ast::Crate) {
+        let path = match cx.sess.opts.debugging_opts.unsafe_report {
+            Some(ref path) => path,
+            None => return,
+        };
+        let report = UnsafeReport { usages: mem::take(&mut self.usages) };
+        if let Err(e) = fs::write(path, format!("{}\n", as_pretty_json(&report))) {
+            cx.sess.err(&format!("failed to write unsafe report `{}`: {}", path.display(), e));
+        }
+    }
 }
 
 declare_lint! {
@@ -304,6 +456,9 @@ pub struct MissingDoc {
 
     /// Private traits or trait items that leaked through. Don't check their methods.
     private_traits: FxHashSet<hir::HirId>,
+
+    /// Number of documented and checked items of each kind, for `-Z missing-docs-coverage`.
+    coverage: BTreeMap<&'static str, (usize, usize)>,
 }
 
 impl_lint_pass!(MissingDoc => [MISSING_DOCS]);
@@ -334,19 +489,26 @@ fn has_doc(attr: &ast::Attribute) -> bool {
 
 impl MissingDoc {
     pub fn new() -> MissingDoc {
-        MissingDoc { doc_hidden_stack: vec![false], private_traits: FxHashSet::default() }
+        MissingDoc {
+            doc_hidden_stack: vec![false],
+            private_traits: FxHashSet::default(),
+            coverage: BTreeMap::new(),
+        }
     }
 
     fn doc_hidden(&self) -> bool {
         *self.doc_hidden_stack.last().expect("empty doc_hidden_stack")
     }
 
+    /// Checks an item for documentation. `kind` is the name to select the item with
+    /// in `-Z missing-docs-kinds`, `desc` is how it is referred to in the diagnostic.
     fn check_missing_docs_attrs(
-        &self,
+        &mut self,
         cx: &LateContext<'_, '_>,
         id: Option<hir::HirId>,
         attrs: &[ast::Attribute],
         sp: Span,
+        kind: &'static str,
         desc: &'static str,
     ) {
         // If we're building a test harness, then warning about
@@ -369,8 +531,17 @@ impl MissingDoc {
             }
         }
 
+        let kinds = &cx.sess().opts.debugging_opts.missing_docs_kinds;
+        if !kinds.is_empty() && !kinds.iter().any(|k| k == kind) {
+            return;
+        }
+
         let has_doc = attrs.iter().any(|a| has_doc(a));
-        if !has_doc {
+        let coverage = self.coverage.entry(kind).or_insert((0, 0));
+        coverage.1 += 1;
+        if has_doc {
+            coverage.0 += 1;
+        } else {
             cx.span_lint(
                 MISSING_DOCS,
                 cx.tcx.sess.source_map().def_span(sp),
@@ -378,6 +549,25 @@ impl MissingDoc {
             );
         }
     }
+
+    /// Prints the documentation coverage of the crate as a single line of JSON.
+    fn print_coverage(&self) {
+        let (documented, total) =
+            self.coverage.values().fold((0, 0), |(d, t), &(kd, kt)| (d + kd, t + kt));
+        let kinds = self
+            .coverage
+            .iter()
+            .map(|(kind, (documented, total))| {
+                format!("\"{}\":{{\"documented\":{},\"total\":{}}}", kind, documented, total)
+            })
+            .collect::<Vec<_>>();
+        println!(
+            "{{\"documented\":{},\"total\":{},\"kinds\":{{{}}}}}",
+            documented,
+            total,
+            kinds.join(",")
+        );
+    }
 }
 
 impl<'a, 'tcx> LateLintPass<'a, 'tcx> for MissingDoc {
@@ -398,27 +588,33 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for MissingDoc {
     }
 
     fn check_crate(&mut self, cx: &LateContext<'_, '_>, krate: &hir::Crate<'_>) {
-        self.check_missing_docs_attrs(cx, None, &krate.attrs, krate.span, "crate");
+        self.check_missing_docs_attrs(cx, None, &krate.attrs, krate.span, "crate", "crate");
 
         for macro_def in krate.exported_macros {
-            let has_doc = macro_def.attrs.iter().any(|a| has_doc(a));
-            if !has_doc {
-                cx.span_lint(
-                    MISSING_DOCS,
-                    cx.tcx.sess.source_map().def_span(macro_def.span),
-                    "missing documentation for macro",
-                );
-            }
+            self.check_missing_docs_attrs(
+                cx,
+                None,
+                &macro_def.attrs,
+                macro_def.span,
+                "macro",
+                "macro",
+            );
+        }
+    }
+
+    fn check_crate_post(&mut self, cx: &LateContext<'_, '_>, _: &hir::Crate<'_>) {
+        if cx.sess().opts.debugging_opts.missing_docs_coverage {
+            self.print_coverage();
         }
     }
 
     fn check_item(&mut self, cx: &LateContext<'_, '_>, it: &hir::Item<'_>) {
-        let desc = match it.kind {
-            hir::ItemKind::Fn(..) => "a function",
-            hir::ItemKind::Mod(..) => "a module",
-            hir::ItemKind::Enum(..) => "an enum",
-            hir::ItemKind::Struct(..) => "a struct",
-            hir::ItemKind::Union(..) => "a union",
+        let (kind, desc) = match it.kind {
+            hir::ItemKind::Fn(..) => ("fn", "a function"),
+            hir::ItemKind::Mod(..) => ("mod", "a module"),
+            hir::ItemKind::Enum(..) => ("enum", "an enum"),
+            hir::ItemKind::Struct(..) => ("struct", "a struct"),
+            hir::ItemKind::Union(..) => ("union", "a union"),
             hir::ItemKind::Trait(.., trait_item_refs) => {
                 // Issue #11592: traits are always considered exported, even when private.
                 if let hir::VisibilityKind::Inherited = it.vis.node {
@@ -428,9 +624,9 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for MissingDoc {
                     }
                     return;
                 }
-                "a trait"
+                ("trait", "a trait")
             }
-            hir::ItemKind::TyAlias(..) => "a type alias",
+            hir::ItemKind::TyAlias(..) => ("type", "a type alias"),
             hir::ItemKind::Impl(.., Some(ref trait_ref), _, impl_item_refs) => {
                 // If the trait is private, add the impl items to `private_traits` so they don't get
                 // reported for missing docs.
@@ -449,12 +645,12 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for MissingDoc {
                 }
                 return;
             }
-            hir::ItemKind::Const(..) => "a constant",
-            hir::ItemKind::Static(..) => "a static",
+            hir::ItemKind::Const(..) => ("const", "a constant"),
+            hir::ItemKind::Static(..) => ("static", "a static"),
             _ => return,
         };
 
-        self.check_missing_docs_attrs(cx, Some(it.hir_id), &it.attrs, it.span, desc);
+        self.check_missing_docs_attrs(cx, Some(it.hir_id), &it.attrs, it.span, kind, desc);
     }
 
     fn check_trait_item(&mut self, cx: &LateContext<'_, '_>, trait_item: &hir::TraitItem<'_>) {
@@ -462,10 +658,10 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for MissingDoc {
             return;
         }
 
-        let desc = match trait_item.kind {
-            hir::TraitItemKind::Const(..) => "an associated constant",
-            hir::TraitItemKind::Method(..) => "a trait method",
-            hir::TraitItemKind::Type(..) => "an associated type",
+        let (kind, desc) = match trait_item.kind {
+            hir::TraitItemKind::Const(..) => ("assoc-const", "an associated constant"),
+            hir::TraitItemKind::Method(..) => ("method", "a trait method"),
+            hir::TraitItemKind::Type(..) => ("assoc-type", "an associated type"),
         };
 
         self.check_missing_docs_attrs(
@@ -473,6 +669,7 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for MissingDoc {
             Some(trait_item.hir_id),
             &trait_item.attrs,
             trait_item.span,
+            kind,
             desc,
         );
     }
@@ -483,29 +680,37 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for MissingDoc {
             return;
         }
 
-        let desc = match impl_item.kind {
-            hir::ImplItemKind::Const(..) => "an associated constant",
-            hir::ImplItemKind::Method(..) => "a method",
-            hir::ImplItemKind::TyAlias(_) => "an associated type",
-            hir::ImplItemKind::OpaqueTy(_) => "an associated `impl Trait` type",
+        let (kind, desc) = match impl_item.kind {
+            hir::ImplItemKind::Const(..) => ("assoc-const", "an associated constant"),
+            hir::ImplItemKind::Method(..) => ("method", "a method"),
+            hir::ImplItemKind::TyAlias(_) => ("assoc-type", "an associated type"),
+            hir::ImplItemKind::OpaqueTy(_) => ("assoc-type", "an associated `impl Trait` type"),
         };
         self.check_missing_docs_attrs(
             cx,
             Some(impl_item.hir_id),
             &impl_item.attrs,
             impl_item.span,
+            kind,
             desc,
         );
     }
 
     fn check_struct_field(&mut self, cx: &LateContext<'_, '_>, sf: &hir::StructField<'_>) {
         if !sf.is_positional() {
-            self.check_missing_docs_attrs(cx, Some(sf.hir_id), &sf.attrs, sf.span, "a struct field")
+            self.check_missing_docs_attrs(
+                cx,
+                Some(sf.hir_id),
+                &sf.attrs,
+                sf.span,
+                "field",
+                "a struct field",
+            )
         }
     }
 
     fn check_variant(&mut self, cx: &LateContext<'_, '_>, v: &hir::Variant<'_>) {
-        self.check_missing_docs_attrs(cx, Some(v.id), &v.attrs, v.span, "a variant");
+        self.check_missing_docs_attrs(cx, Some(v.id), &v.attrs, v.span, "variant", "a variant");
     }
 }
 
@@ -570,12 +775,115 @@ declare_lint! {
     "detects missing implementations of fmt::Debug"
 }
 
+declare_lint! {
+    MISSING_TRAIT_IMPLEMENTATIONS,
+    Allow,
+    "detects missing implementations of the traits listed in `-Z required-traits`"
+}
+
 #[derive(Default)]
 pub struct MissingDebugImplementations {
     impling_types: Option<HirIdSet>,
+    /// The traits from `-Z required-traits`, with the local types implementing each of them.
+    required_traits: Option<Vec<(Symbol, DefId, HirIdSet)>>,
 }
 
-impl_lint_pass!(MissingDebugImplementations => [MISSING_DEBUG_IMPLEMENTATIONS]);
+impl_lint_pass!(
+    MissingDebugImplementations => [MISSING_DEBUG_IMPLEMENTATIONS, MISSING_TRAIT_IMPLEMENTATIONS]
+);
+
+impl MissingDebugImplementations {
+    /// Types of the local crate with an explicit impl of `trait_def_id`.
+    fn impling_types(cx: &LateContext<'_, '_>, trait_def_id: DefId) -> HirIdSet {
+        let mut impls = HirIdSet::default();
+        cx.tcx.for_each_impl(trait_def_id, |d| {
+            if let Some(ty_def) = cx.tcx.type_of(d).ty_adt_def() {
+                if let Some(hir_id) = cx.tcx.hir().as_local_hir_id(ty_def.did) {
+                    impls.insert(hir_id);
+                }
+            }
+        });
+        impls
+    }
+
+    fn required_traits(cx: &LateContext<'_, '_>) -> Vec<(Symbol, DefId, HirIdSet)> {
+        let tcx = cx.tcx;
+        let lang_items = tcx.lang_items();
+        let mut required_traits = Vec::new();
+        for name in &cx.sess().opts.debugging_opts.required_traits {
+            let trait_def_id = match &name[..] {
+                "Clone" => lang_items.clone_trait(),
+                "Copy" => lang_items.copy_trait(),
+                "Debug" => tcx.get_diagnostic_item(sym::debug_trait),
+                "Hash" => tcx.get_diagnostic_item(sym::hash_trait),
+                "Ord" => tcx.get_diagnostic_item(sym::ord_trait),
+                "PartialEq" => lang_items.eq_trait(),
+                "PartialOrd" => lang_items.partial_ord_trait(),
+                "Send" => tcx.get_diagnostic_item(sym::send_trait),
+                "Sync" => tcx.get_diagnostic_item(sym::sync_trait),
+                _ => {
+                    cx.sess()
+                        .warn(&format!("unsupported trait `{}` in `-Z required-traits`", name));
+                    continue;
+                }
+            };
+            if let Some(trait_def_id) = trait_def_id {
+                // Auto traits are checked through trait selection instead.
+                let impls = if tcx.trait_is_auto(trait_def_id) {
+                    HirIdSet::default()
+                } else {
+                    MissingDebugImplementations::impling_types(cx, trait_def_id)
+                };
+                required_traits.push((Symbol::intern(name), trait_def_id, impls));
+            }
+        }
+        required_traits
+    }
+
+    fn check_required_traits(&mut self, cx: &LateContext<'_, '_>, item: &hir::Item<'_>) {
+        if self.required_traits.is_none() {
+            self.required_traits = Some(MissingDebugImplementations::required_traits(cx));
+        }
+
+        let def_id = cx.tcx.hir().local_def_id(item.hir_id);
+        let span = cx.sess().source_map().def_span(item.span);
+        for &(name, trait_def_id, ref impls) in self.required_traits.as_ref().unwrap() {
+            let implemented = if cx.tcx.trait_is_auto(trait_def_id) {
+                // FIXME: generic types are only `Send` or `Sync` for some instantiations, which
+                // can't be expressed here, so they are skipped.
+                if cx.tcx.generics_of(def_id).count() != 0 {
+                    continue;
+                }
+                let ty = cx.tcx.type_of(def_id);
+                cx.tcx.infer_ctxt().enter(|infcx| {
+                    traits::type_known_to_meet_bound_modulo_regions(
+                        &infcx,
+                        cx.param_env,
+                        ty,
+                        trait_def_id,
+                        span,
+                    )
+                })
+            } else {
+                impls.contains(&item.hir_id)
+            };
+            if implemented {
+                continue;
+            }
+
+            let msg = if cx.tcx.trait_is_auto(trait_def_id) {
+                format!("type is not `{}`", name)
+            } else {
+                format!(
+                    "type does not implement `{}`; consider adding `#[derive({})]` \
+                     or a manual implementation",
+                    name, name
+                )
+            };
+            cx.span_lint(MISSING_TRAIT_IMPLEMENTATIONS, span, &msg);
+        }
+    }
+}
 
 impl<'a, 'tcx> LateLintPass<'a, 'tcx> for MissingDebugImplementations {
     fn check_item(&mut self, cx: &LateContext<'_, '_>, item: &hir::Item<'_>) {
@@ -588,22 +896,17 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for MissingDebugImplementations {
             _ => return,
         }
 
+        if !cx.sess().opts.debugging_opts.required_traits.is_empty() {
+            self.check_required_traits(cx, item);
+        }
+
         let debug = match cx.tcx.get_diagnostic_item(sym::debug_trait) {
             Some(debug) => debug,
             None => return,
         };
 
         if self.impling_types.is_none() {
-            let mut impls = HirIdSet::default();
-            cx.tcx.for_each_impl(debug, |d| {
-                if let Some(ty_def) = cx.tcx.type_of(d).ty_adt_def() {
-                    if let Some(hir_id) = cx.tcx.hir().as_local_hir_id(ty_def.did) {
-                        impls.insert(hir_id);
-                    }
-                }
-            });
-
-            self.impling_types = Some(impls);
+            self.impling_types = Some(MissingDebugImplementations::impling_types(cx, debug));
             debug!("{:?}", self.impling_types);
         }
 
@@ -891,6 +1194,91 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for InvalidNoMangleItems {
     }
 }
 
+declare_lint! {
+    DUPLICATE_EXPORTED_SYMBOLS,
+    Deny,
+    "detects `#[no_mangle]` and `#[export_name]` items that export the same symbol"
+}
+
+/// Symbols the standard library exports itself; defining them in a crate that
+/// links `std` makes the final link fail.
+const STD_EXPORTED_SYMBOLS: &[&str] = &[
+    "__rust_alloc",
+    "__rust_alloc_zeroed",
+    "__rust_dealloc",
+    "__rust_realloc",
+    "__rust_start_panic",
+    "__rust_maybe_catch_panic",
+    "rust_begin_unwind",
+    "rust_eh_personality",
+    "rust_panic",
+];
+
+#[derive(Default)]
+pub struct DuplicateExportedSymbols {
+    exported: FxHashMap<Symbol, Span>,
+    links_std: bool,
+}
+
+impl_lint_pass!(DuplicateExportedSymbols => [DUPLICATE_EXPORTED_SYMBOLS]);
+
+impl DuplicateExportedSymbols {
+    fn check_symbol(
+        &mut self,
+        cx: &LateContext<'_, '_>,
+        attrs: &[ast::Attribute],
+        ident: ast::Ident,
+        span: Span,
+    ) {
+        let symbol = match attr::first_attr_value_str_by_name(attrs, sym::export_name) {
+            Some(name) => name,
+            None if attr::contains_name(attrs, sym::no_mangle) => ident.name,
+            None => return,
+        };
+        let span = cx.tcx.sess.source_map().def_span(span);
+
+        if self.links_std && STD_EXPORTED_SYMBOLS.contains(&&*symbol.as_str()) {
+            let msg = format!("symbol `{}` is already exported by the standard library", symbol);
+            cx.struct_span_lint(DUPLICATE_EXPORTED_SYMBOLS, span, &msg).emit();
+            return;
+        }
+
+        if let Some(&prev_span) = self.exported.get(&symbol) {
+            let mut err = cx.struct_span_lint(
+                DUPLICATE_EXPORTED_SYMBOLS,
+                MultiSpan::from_spans(vec![prev_span, span]),
+                &format!("symbol `{}` is exported more than once", symbol),
+            );
+            err.span_label(prev_span, format!("`{}` first exported here", symbol));
+            err.span_label(span, format!("`{}` exported again here", symbol));
+            err.emit();
+        } else {
+            self.exported.insert(symbol, span);
+        }
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for DuplicateExportedSymbols {
+    fn check_crate(&mut self, _: &LateContext<'_, '_>, krate: &hir::Crate<'_>) {
+        self.links_std = !attr::contains_name(&krate.attrs, sym::no_std);
+    }
+
+    fn check_item(&mut self, cx: &LateContext<'_, '_>, it: &hir::Item<'_>) {
+        match it.kind {
+            hir::ItemKind::Fn(..) | hir::ItemKind::Static(..) => {
+                self.check_symbol(cx, &it.attrs, it.ident, it.span)
+            }
+            _ => {}
+        }
+    }
+
+    fn check_impl_item(&mut self, cx: &LateContext<'_, '_>, it: &hir::ImplItem<'_>) {
+        if let hir::ImplItemKind::Method(..) = it.kind {
+            self.check_symbol(cx, &it.attrs, it.ident, it.span);
+        }
+    }
+}
+
 declare_lint! {
     MUTABLE_TRANSMUTES,
     Deny,
@@ -1442,7 +1830,7 @@ impl KeywordIdents {
         UnderMacro(under_macro): UnderMacro,
         ident: ast::Ident,
     ) {
-        let next_edition = match cx.sess.edition() {
+        let next_edition = match cx.edition() {
             Edition::Edition2015 => {
                 match ident.name {
                     kw::Async | kw::Await | kw::Try => Edition::Edition2018,
@@ -2049,3 +2437,1386 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for InvalidValue {
         }
     }
 }
+
+declare_lint! {
+    pub INCONSISTENT_TRAIT_IMPLS,
+    Warn,
+    "detects types that derive one of a pair of related traits and implement the other manually"
+}
+
+declare_lint! {
+    pub NON_CANONICAL_PARTIAL_ORD,
+    Allow,
+    "detects `PartialOrd` impls of types implementing `Ord` that don't delegate to `Ord`"
+}
+
+declare_lint_pass!(
+    /// Checks for `PartialEq`/`Hash` and `PartialOrd`/`Ord` pairs where one trait is derived
+    /// and the other implemented by hand, as the two implementations easily disagree, and for
+    /// hand-written `PartialOrd` impls that could agree with `Ord` by construction but don't.
+    InconsistentTraitImpls => [INCONSISTENT_TRAIT_IMPLS, NON_CANONICAL_PARTIAL_ORD]
+);
+
+impl InconsistentTraitImpls {
+    /// The `partial_cmp` of a `PartialOrd` impl, unless it is `Some(self.cmp(other))` or
+    /// `Some(Ord::cmp(self, other))`.
+    fn non_delegating_partial_cmp<'tcx>(
+        cx: &LateContext<'_, 'tcx>,
+        impl_item_refs: &[hir::ImplItemRef<'_>],
+    ) -> Option<&'tcx hir::ImplItem<'tcx>> {
+        let item_ref = impl_item_refs.iter().find(|item| item.ident.name == sym::partial_cmp)?;
+        let impl_item = cx.tcx.hir().impl_item(item_ref.id);
+        let body_id = match impl_item.kind {
+            hir::ImplItemKind::Method(_, body_id) => body_id,
+            _ => return None,
+        };
+        let body = cx.tcx.hir().body(body_id);
+        let tables = cx.tcx.body_tables(body_id);
+        let ord_trait = cx.tcx.get_diagnostic_item(sym::ord_trait);
+
+        let is_param = |expr: &hir::Expr<'_>, param: &hir::Param<'_>| match expr.kind {
+            hir::ExprKind::Path(ref qpath) => {
+                tables.qpath_res(qpath, expr.hir_id) == Res::Local(param.pat.hir_id)
+            }
+            _ => false,
+        };
+        let is_ord_cmp = |def_id: Option<DefId>| {
+            def_id.map_or(false, |def_id| {
+                ord_trait.is_some()
+                    && cx.tcx.trait_of_item(def_id) == ord_trait
+                    && cx.tcx.item_name(def_id) == sym::cmp
+            })
+        };
+        let delegates = |expr: &hir::Expr<'_>| {
+            let (callee, args) = match expr.kind {
+                hir::ExprKind::Call(ref callee, ref args) => (callee, args),
+                _ => return false,
+            };
+            let is_some = match callee.kind {
+                hir::ExprKind::Path(ref qpath) => match tables.qpath_res(qpath, callee.hir_id) {
+                    Res::Def(DefKind::Ctor(..), ctor_id) => cx
+                        .match_def_path(ctor_id, &[sym::core, sym::option, sym::Option, sym::Some]),
+                    _ => false,
+                },
+                _ => false,
+            };
+            let cmp_args = match args.get(0).map(|arg| &arg.kind) {
+                Some(hir::ExprKind::MethodCall(_, _, cmp_args))
+                    if is_ord_cmp(tables.type_dependent_def_id(args[0].hir_id)) =>
+                {
+                    cmp_args
+                }
+                Some(hir::ExprKind::Call(cmp, cmp_args)) => match cmp.kind {
+                    hir::ExprKind::Path(ref qpath)
+                        if is_ord_cmp(tables.qpath_res(qpath, cmp.hir_id).opt_def_id()) =>
+                    {
+                        cmp_args
+                    }
+                    _ => return false,
+                },
+                _ => return false,
+            };
+            is_some
+                && args.len() == 1
+                && cmp_args.len() == 2
+                && body.params.len() == 2
+                && is_param(&cmp_args[0], &body.params[0])
+                && is_param(&cmp_args[1], &body.params[1])
+        };
+
+        match body.value.kind {
+            hir::ExprKind::Block(ref block, _) => match block.expr {
+                Some(ref expr) if block.stmts.is_empty() && delegates(expr) => None,
+                _ => Some(impl_item),
+            },
+            _ => Some(impl_item),
+        }
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for InconsistentTraitImpls {
+    fn check_item(&mut self, cx: &LateContext<'a, 'tcx>, item: &'tcx hir::Item<'tcx>) {
+        let impl_item_refs = match item.kind {
+            hir::ItemKind::Impl(.., impl_item_refs) => impl_item_refs,
+            _ => return,
+        };
+        // Only manual impls are reported, so that every pair is reported once.
+        if attr::contains_name(&item.attrs, sym::automatically_derived) {
+            return;
+        }
+        let impl_def_id = cx.tcx.hir().local_def_id(item.hir_id);
+        let trait_ref = match cx.tcx.impl_trait_ref(impl_def_id) {
+            Some(trait_ref) => trait_ref,
+            None => return,
+        };
+        let trait_def_id = trait_ref.def_id;
+        let self_ty = trait_ref.self_ty();
+        // `impl PartialEq<Other> for T` is unrelated to how `T` hashes.
+        if trait_ref.substs.types().skip(1).any(|ty| ty != self_ty) {
+            return;
+        }
+
+        let lang_items = cx.tcx.lang_items();
+        let pairs = [
+            (lang_items.eq_trait(), cx.tcx.get_diagnostic_item(sym::hash_trait), "hash maps"),
+            (lang_items.partial_ord_trait(), cx.tcx.get_diagnostic_item(sym::ord_trait), "sorting"),
+        ];
+        let other = pairs.iter().find_map(|&(a, b, users)| {
+            if a == Some(trait_def_id) {
+                Some((b?, users))
+            } else if b == Some(trait_def_id) {
+                Some((a?, users))
+            } else {
+                None
+            }
+        });
+        let (other_trait, users) = match other {
+            Some(other) => other,
+            None => return,
+        };
+
+        let adt_def = match self_ty.ty_adt_def() {
+            Some(adt_def) if adt_def.did.is_local() => adt_def,
+            _ => return,
+        };
+        let mut other_impl = None;
+        let mut derived_impl = None;
+        cx.tcx.for_each_relevant_impl(other_trait, self_ty, |impl_def_id| {
+            let is_same_type =
+                cx.tcx.type_of(impl_def_id).ty_adt_def().map_or(false, |other| other == adt_def);
+            if is_same_type {
+                other_impl = Some(impl_def_id);
+                if cx.tcx.has_attr(impl_def_id, sym::automatically_derived) {
+                    derived_impl = Some(impl_def_id);
+                }
+            }
+        });
+        let partial_cmp = if lang_items.partial_ord_trait() == Some(trait_def_id) {
+            match InconsistentTraitImpls::non_delegating_partial_cmp(cx, impl_item_refs) {
+                Some(partial_cmp) => Some(partial_cmp),
+                // `Some(self.cmp(other))` agrees with `Ord` whether or not it is derived.
+                None => return,
+            }
+        } else {
+            None
+        };
+        let derived_impl = match (derived_impl, other_impl, partial_cmp) {
+            (Some(derived_impl), ..) => derived_impl,
+            (None, Some(ord_impl), Some(partial_cmp)) => {
+                let span = cx.tcx.sess.source_map().def_span(partial_cmp.span);
+                let msg = format!(
+                    "`partial_cmp` of `{}` does not delegate to its `Ord` implementation",
+                    self_ty
+                );
+                cx.struct_span_lint(NON_CANONICAL_PARTIAL_ORD, span, &msg)
+                    .span_label(cx.tcx.def_span(ord_impl), "`Ord` is implemented here")
+                    .note(
+                        "`PartialOrd` and `Ord` must agree with each other, or sorting will \
+                         misbehave",
+                    )
+                    .help("return `Some(self.cmp(other))`, which agrees with `Ord` by construction")
+                    .emit();
+                return;
+            }
+            _ => return,
+        };
+
+        let (manual_name, derived_name) =
+            (cx.tcx.item_name(trait_def_id), cx.tcx.item_name(other_trait));
+        let span = cx.tcx.sess.source_map().def_span(item.span);
+        let msg = format!(
+            "`{}` is implemented manually for `{}`, but `{}` is derived",
+            manual_name, self_ty, derived_name
+        );
+        let mut err = cx.struct_span_lint(INCONSISTENT_TRAIT_IMPLS, span, &msg);
+        err.span_label(
+            cx.tcx.def_span(derived_impl),
+            format!("`{}` is derived here", derived_name),
+        );
+        err.note(&format!(
+            "`{}` and `{}` must agree with each other, or {} will misbehave",
+            manual_name, derived_name, users
+        ));
+        err.help(&format!("implement `{}` manually as well, or derive both", derived_name));
+        if partial_cmp.is_some() {
+            err.help("or return `Some(self.cmp(other))` from `partial_cmp`");
+        }
+        err.emit();
+    }
+}
+
+declare_lint! {
+    pub DEREF_CYCLES,
+    Warn,
+    "detects `Deref` implementations that lead back to the type implementing them"
+}
+
+declare_lint_pass!(
+    /// Checks for `Deref` impls whose `Target` is `Self`, or derefs back to `Self` through other
+    /// `Deref` impls, as autoderef then recurses until the recursion limit wherever the type is
+    /// used, instead of reporting the impls.
+    DerefCycles => [DEREF_CYCLES]
+);
+
+impl DerefCycles {
+    /// The `Target` of the `Deref` impl `impl_def_id`, for the generic arguments `substs`.
+    fn target<'tcx>(
+        tcx: TyCtxt<'tcx>,
+        impl_def_id: DefId,
+        substs: SubstsRef<'tcx>,
+    ) -> Option<Ty<'tcx>> {
+        let target = tcx
+            .associated_items(impl_def_id)
+            .find(|item| item.kind == ty::AssocKind::Type && item.ident.name == sym::Target)?;
+        Some(tcx.type_of(target.def_id).subst(tcx, substs))
+    }
+
+    /// The `Deref` impl autoderef uses for `ty` and its generic arguments, if it is one for every
+    /// instance of the type, so that it applies whatever the generic arguments of `ty` are.
+    fn deref_impl<'tcx>(
+        tcx: TyCtxt<'tcx>,
+        deref_trait: DefId,
+        ty: Ty<'tcx>,
+    ) -> Option<(DefId, SubstsRef<'tcx>)> {
+        let (adt_def, substs) = match ty.kind {
+            ty::Adt(adt_def, substs) => (adt_def, substs),
+            _ => return None,
+        };
+        let mut deref_impl = None;
+        tcx.for_each_relevant_impl(deref_trait, ty, |impl_def_id| {
+            let impl_substs = match tcx.type_of(impl_def_id).kind {
+                ty::Adt(impl_adt_def, impl_substs) if impl_adt_def == adt_def => impl_substs,
+                _ => return,
+            };
+            // Map the generic parameters of the impl to the arguments of `ty`.
+            let mut args = FxHashMap::default();
+            for (impl_arg, arg) in impl_substs.iter().zip(substs.iter()) {
+                let index = match impl_arg.unpack() {
+                    GenericArgKind::Lifetime(_) => continue,
+                    GenericArgKind::Type(ty) => match ty.kind {
+                        ty::Param(param) => param.index,
+                        _ => return,
+                    },
+                    GenericArgKind::Const(ct) => match ct.val {
+                        ty::ConstKind::Param(param) => param.index,
+                        _ => return,
+                    },
+                };
+                args.insert(index, *arg);
+            }
+            let substs = InternalSubsts::for_item(tcx, impl_def_id, |param, _| {
+                args.get(&param.index).cloned().unwrap_or_else(|| tcx.mk_param_from_def(param))
+            });
+            deref_impl = Some((impl_def_id, substs));
+        });
+        deref_impl
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for DerefCycles {
+    fn check_crate(&mut self, cx: &LateContext<'a, 'tcx>, _: &'tcx hir::Crate<'tcx>) {
+        let tcx = cx.tcx;
+        let deref_trait = match tcx.lang_items().deref_trait() {
+            Some(deref_trait) => deref_trait,
+            None => return,
+        };
+        // Every impl of a cycle finds it, and the first of them reports it.
+        let mut reported = FxHashSet::default();
+        for &hir_id in tcx.hir().trait_impls(deref_trait) {
+            let impl_def_id = tcx.hir().local_def_id(hir_id);
+            if reported.contains(&impl_def_id) {
+                continue;
+            }
+            let self_ty = tcx.type_of(impl_def_id);
+            let covers_self = DerefCycles::deref_impl(tcx, deref_trait, self_ty)
+                .map_or(false, |(deref_impl, _)| deref_impl == impl_def_id);
+
+            // Follow autoderef from `self_ty`, through references and the impls it would use.
+            let identity = InternalSubsts::identity_for_item(tcx, impl_def_id);
+            let mut path = vec![self_ty];
+            let mut impls = vec![impl_def_id];
+            let mut ty = match DerefCycles::target(tcx, impl_def_id, identity) {
+                Some(target) => target,
+                None => continue,
+            };
+            let is_cycle = loop {
+                path.push(ty);
+                if ty == self_ty || (covers_self && ty.ty_adt_def() == self_ty.ty_adt_def()) {
+                    break true;
+                }
+                ty = match ty.kind {
+                    ty::Ref(_, pointee, _) => pointee,
+                    _ => {
+                        let (deref_impl, substs) =
+                            match DerefCycles::deref_impl(tcx, deref_trait, ty) {
+                                Some(deref_impl) => deref_impl,
+                                None => break false,
+                            };
+                        // A cycle that doesn't come back to `self_ty` is reported from its impls.
+                        if impls.contains(&deref_impl) {
+                            break false;
+                        }
+                        impls.push(deref_impl);
+                        match DerefCycles::target(tcx, deref_impl, substs) {
+                            Some(target) => target,
+                            None => break false,
+                        }
+                    }
+                };
+            };
+            if !is_cycle {
+                continue;
+            }
+            reported.extend(impls.iter().cloned());
+
+            let msg = if path.len() == 2 {
+                format!("`Deref` implementation of `{}` targets `{}` itself", self_ty, self_ty)
+            } else {
+                format!("dereferencing `{}` leads back to `{}`", self_ty, path[path.len() - 1])
+            };
+            let span = tcx.def_span(impl_def_id);
+            let mut err = tcx.struct_span_lint_hir(DEREF_CYCLES, hir_id, span, &msg);
+            for &other in impls[1..].iter().filter(|other| other.is_local()) {
+                let identity = InternalSubsts::identity_for_item(tcx, other);
+                if let Some(target) = DerefCycles::target(tcx, other, identity) {
+                    err.span_label(
+                        tcx.def_span(other),
+                        format!("`{}` derefs to `{}` here", tcx.type_of(other), target),
+                    );
+                }
+            }
+            let path: Vec<_> = path.iter().map(|ty| format!("`{}`", ty)).collect();
+            err.note(&format!(
+                "autoderef follows {} and so on, until it reaches the recursion limit",
+                path.join(" -> ")
+            ));
+            err.emit();
+        }
+    }
+}
+
+declare_lint! {
+    /// The `empty_loops` lint detects `loop {}` and `while true {}` with nothing in the body.
+    ///
+    /// ```rust,no_run
+    /// fn main() {
+    ///     std::thread::spawn(|| { /* ... */ });
+    ///     loop {}
+    /// }
+    /// ```
+    ///
+    /// Such a loop keeps a CPU core busy doing nothing until the process ends. It is how
+    /// embedded programs usually halt, so the lint only applies to `#![no_std]` crates and to
+    /// targets without an operating system when it is enabled explicitly.
+    pub EMPTY_LOOPS,
+    Warn,
+    "detects empty loops that busy-wait forever"
+}
+
+declare_lint_pass!(
+    /// Checks for infinite loops with an empty body.
+    EmptyLoops => [EMPTY_LOOPS]
+);
+
+impl EmptyLoops {
+    fn is_empty(block: &hir::Block<'_>) -> bool {
+        block.stmts.is_empty() && block.expr.is_none()
+    }
+
+    /// Whether the body of a `while` loop is that of an empty `while true {}`, which lowers to
+    /// `loop { match true { true => {}, _ => break } }`.
+    fn is_empty_while_true(block: &hir::Block<'_>) -> bool {
+        let (scrutinee, arms) = match block.expr.map(|expr| &expr.kind) {
+            Some(hir::ExprKind::Match(scrutinee, arms, hir::MatchSource::WhileDesugar)) => {
+                (scrutinee, arms)
+            }
+            _ => return false,
+        };
+        let cond = match scrutinee.kind {
+            hir::ExprKind::DropTemps(cond) => cond,
+            _ => return false,
+        };
+        match cond.kind {
+            hir::ExprKind::Lit(ref lit) if lit.node == ast::LitKind::Bool(true) => {}
+            _ => return false,
+        }
+        match arms.first().map(|arm| &arm.body.kind) {
+            Some(hir::ExprKind::Block(body, _)) => EmptyLoops::is_empty(body),
+            _ => false,
+        }
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for EmptyLoops {
+    fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, e: &'tcx hir::Expr<'tcx>) {
+        let what = match e.kind {
+            hir::ExprKind::Loop(block, _, hir::LoopSource::Loop) if EmptyLoops::is_empty(block) => {
+                "loop {}"
+            }
+            hir::ExprKind::Loop(block, _, hir::LoopSource::While)
+                if EmptyLoops::is_empty_while_true(block) =>
+            {
+                "while true {}"
+            }
+            _ => return,
+        };
+        if e.span.from_expansion() {
+            return;
+        }
+
+        let embedded = cx.sess().target.target.target_os == "none"
+            || attr::contains_name(&cx.tcx.hir().krate().attrs, sym::no_std);
+        if embedded {
+            if let (_, lint::LintSource::Default) = cx.tcx.lint_level_at_node(EMPTY_LOOPS, e.hir_id)
+            {
+                return;
+            }
+        }
+
+        let msg = format!("empty `{}` busy-waits forever", what);
+        let mut err = cx.struct_span_lint(EMPTY_LOOPS, e.span, &msg);
+        err.note("it keeps a CPU core busy, and inhibits some optimizations of the code around it");
+        if embedded {
+            err.help(
+                "halting the CPU with an intrinsic of the target saves power, or `allow` this \
+                 lint if spinning is intended",
+            );
+        } else {
+            err.help(
+                "to wait for another thread, use `std::thread::park` or a channel; to spin on \
+                 purpose, call `std::sync::atomic::spin_loop_hint()` in the loop",
+            );
+            err.note(
+                "embedded and `#![no_std]` crates can `allow` this lint, or halt the CPU with an \
+                 intrinsic of the target",
+            );
+        }
+        err.emit();
+    }
+}
+
+declare_lint! {
+    pub EXHAUSTIVE_PUBLIC_TYPES,
+    Allow,
+    "detects public enums and structs of library crates that are not `#[non_exhaustive]`"
+}
+
+declare_lint_pass!(
+    /// Checks for exported enums and tuple or unit structs that downstream crates can match on
+    /// exhaustively, so that adding a variant or field to them later is a breaking change.
+    ExhaustivePublicTypes => [EXHAUSTIVE_PUBLIC_TYPES]
+);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for ExhaustivePublicTypes {
+    fn check_item(&mut self, cx: &LateContext<'a, 'tcx>, item: &'tcx hir::Item<'tcx>) {
+        let (descr, addition) = match item.kind {
+            hir::ItemKind::Enum(..) => ("enum", "variants"),
+            hir::ItemKind::Struct(ref data, _) => match data {
+                hir::VariantData::Unit(..) => ("struct", "fields"),
+                // A private field already keeps other crates from constructing the struct or
+                // matching on it without `..`.
+                hir::VariantData::Tuple(fields, _)
+                    if fields.iter().all(|field| field.vis.node.is_pub()) =>
+                {
+                    ("struct", "fields")
+                }
+                _ => return,
+            },
+            _ => return,
+        };
+
+        if cx.tcx.sess.crate_types.borrow().iter().all(|ty| *ty == CrateType::Executable) {
+            return;
+        }
+        if !cx.access_levels.is_exported(item.hir_id) {
+            return;
+        }
+        if attr::contains_name(&item.attrs, sym::non_exhaustive)
+            || attr::contains_name(&item.attrs, sym::exhaustive)
+        {
+            return;
+        }
+
+        let span = cx.tcx.sess.source_map().def_span(item.span);
+        let msg = format!("public {} `{}` is exhaustive", descr, item.ident);
+        cx.struct_span_lint(EXHAUSTIVE_PUBLIC_TYPES, span, &msg)
+            .note(&format!(
+                "other crates can match on it exhaustively, so adding {} to it is a breaking \
+                 change",
+                addition
+            ))
+            .help(
+                "mark it `#[non_exhaustive]` if it may be extended, or `#[exhaustive]` if it \
+                 is final",
+            )
+            .emit();
+    }
+}
+
+declare_lint! {
+    pub INLINE_ALWAYS_RECURSION,
+    Warn,
+    "detects `#[inline(always)]` on recursive functions"
+}
+
+declare_lint_pass!(
+    /// Checks for functions marked `#[inline(always)]` that call themselves, directly or
+    /// through other functions of the crate. Such calls can't be inlined.
+    InlineAlwaysRecursion => [INLINE_ALWAYS_RECURSION]
+);
+
+/// Collects the calls in a function body to other functions of the local crate, resolving
+/// trait methods to their impl when it is known.
+struct LocalCallees<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    tables: &'a ty::TypeckTables<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    callees: Vec<(DefId, Span)>,
+}
+
+impl<'tcx> LocalCallees<'_, 'tcx> {
+    fn record(&mut self, def_id: DefId, substs: SubstsRef<'tcx>, span: Span) {
+        let def_id = match Instance::resolve(self.tcx, self.param_env, def_id, substs) {
+            Some(Instance { def: ty::InstanceDef::Item(def_id), .. }) => def_id,
+            Some(_) => return,
+            None => def_id,
+        };
+        if def_id.is_local() {
+            self.callees.push((def_id, span));
+        }
+    }
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for LocalCallees<'a, 'tcx> {
+    type Map = Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> intravisit::NestedVisitorMap<'_, Self::Map> {
+        intravisit::NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        match expr.kind {
+            hir::ExprKind::Call(ref callee, _) => {
+                if let hir::ExprKind::Path(ref qpath) = callee.kind {
+                    match self.tables.qpath_res(qpath, callee.hir_id) {
+                        Res::Def(DefKind::Fn, def_id) | Res::Def(DefKind::Method, def_id) => {
+                            let substs = self.tables.node_substs(callee.hir_id);
+                            self.record(def_id, substs, expr.span);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            hir::ExprKind::MethodCall(..) => {
+                if let Some(def_id) = self.tables.type_dependent_def_id(expr.hir_id) {
+                    let substs = self.tables.node_substs(expr.hir_id);
+                    self.record(def_id, substs, expr.span);
+                }
+            }
+            _ => {}
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+impl InlineAlwaysRecursion {
+    fn local_callees<'c>(
+        tcx: TyCtxt<'_>,
+        cache: &'c mut FxHashMap<DefId, Vec<(DefId, Span)>>,
+        def_id: DefId,
+    ) -> &'c [(DefId, Span)] {
+        cache.entry(def_id).or_insert_with(|| {
+            let body_id = match tcx.hir().as_local_hir_id(def_id) {
+                Some(hir_id) => match tcx.hir().maybe_body_owned_by(hir_id) {
+                    Some(body_id) => body_id,
+                    None => return vec![],
+                },
+                None => return vec![],
+            };
+            let mut visitor = LocalCallees {
+                tcx,
+                tables: tcx.typeck_tables_of(def_id),
+                param_env: tcx.param_env(def_id),
+                callees: vec![],
+            };
+            visitor.visit_body(tcx.hir().body(body_id));
+            visitor.callees
+        })
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for InlineAlwaysRecursion {
+    fn check_crate(&mut self, cx: &LateContext<'a, 'tcx>, _: &'tcx hir::Crate<'tcx>) {
+        let tcx = cx.tcx;
+        let mut callees = FxHashMap::default();
+        for def_id in tcx.body_owners() {
+            match tcx.def_kind(def_id) {
+                Some(DefKind::Fn) | Some(DefKind::Method) => {}
+                _ => continue,
+            }
+            if tcx.codegen_fn_attrs(def_id).inline != attr::InlineAttr::Always {
+                continue;
+            }
+
+            // Search the call graph breadth-first for the shortest cycle back to `def_id`,
+            // remembering for every function reached the call it was first reached through.
+            let mut reached_through = FxHashMap::default();
+            let mut queue = VecDeque::new();
+            queue.push_back(def_id);
+            let mut back_edge = None;
+            'search: while let Some(caller) = queue.pop_front() {
+                for &(callee, span) in
+                    InlineAlwaysRecursion::local_callees(tcx, &mut callees, caller)
+                {
+                    if callee == def_id {
+                        back_edge = Some((caller, span));
+                        break 'search;
+                    }
+                    if !reached_through.contains_key(&callee) {
+                        reached_through.insert(callee, (caller, span));
+                        queue.push_back(callee);
+                    }
+                }
+            }
+            let (last_caller, mut first_call) = match back_edge {
+                Some(back_edge) => back_edge,
+                None => continue,
+            };
+            let mut cycle = vec![last_caller];
+            let mut current = last_caller;
+            while current != def_id {
+                let (caller, span) = reached_through[&current];
+                cycle.push(caller);
+                first_call = span;
+                current = caller;
+            }
+            cycle.reverse();
+
+            let attr_span = match tcx.get_attrs(def_id).iter().find(|a| a.check_name(sym::inline)) {
+                Some(attr) => attr.span,
+                None => continue,
+            };
+            let hir_id = tcx.hir().as_local_hir_id(def_id).unwrap();
+            let name = tcx.def_path_str(def_id);
+            let mut err = tcx.struct_span_lint_hir(
+                INLINE_ALWAYS_RECURSION,
+                hir_id,
+                attr_span,
+                &format!("`#[inline(always)]` on recursive function `{}`", name),
+            );
+            if cycle.len() == 1 {
+                err.span_label(first_call, "recursive call site");
+            } else {
+                err.span_label(
+                    first_call,
+                    format!(
+                        "this call to `{}` leads back to `{}`",
+                        tcx.def_path_str(cycle[1]),
+                        name
+                    ),
+                );
+                let path = cycle
+                    .iter()
+                    .chain(Some(&def_id))
+                    .map(|&def_id| format!("`{}`", tcx.def_path_str(def_id)))
+                    .collect::<Vec<_>>();
+                err.note(&format!("the function is recursive through {}", path.join(" -> ")));
+            }
+            err.note("recursive calls can't be inlined, so the attribute has no effect on them");
+            err.help("use `#[inline]` to leave the decision to the compiler");
+            err.emit();
+        }
+    }
+}
+
+declare_lint! {
+    pub CONFLICTING_FN_ATTRIBUTES,
+    Warn,
+    "detects function attributes that contradict each other"
+}
+
+declare_lint_pass!(
+    /// Checks for combinations of attributes on a function where one of them overrides or
+    /// defeats the other.
+    ConflictingFnAttributes => [CONFLICTING_FN_ATTRIBUTES]
+);
+
+/// Attribute pairs that contradict each other, with what happens today when both are used.
+const CONFLICTING_FN_ATTRS: &[(&str, &str, &str)] = &[
+    (
+        "cold",
+        "inline(always)",
+        "the function is still inlined into every caller, which mostly defeats `#[cold]`",
+    ),
+    ("inline(always)", "inline(never)", "the last `#[inline]` attribute takes effect"),
+    (
+        "no_mangle",
+        "export_name",
+        "the symbol is named after `#[export_name]`, so `#[no_mangle]` has no effect",
+    ),
+];
+
+impl ConflictingFnAttributes {
+    /// The name `attr` is referred to by in `CONFLICTING_FN_ATTRS`.
+    fn attr_name(attr: &ast::Attribute) -> Option<&'static str> {
+        if attr.check_name(sym::cold) {
+            Some("cold")
+        } else if attr.check_name(sym::no_mangle) {
+            Some("no_mangle")
+        } else if attr.check_name(sym::export_name) {
+            Some("export_name")
+        } else if attr.check_name(sym::inline) {
+            let items = attr.meta_item_list()?;
+            if attr::list_contains_name(&items, sym::always) {
+                Some("inline(always)")
+            } else if attr::list_contains_name(&items, sym::never) {
+                Some("inline(never)")
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+
+    fn check_attrs(cx: &LateContext<'_, '_>, attrs: &[ast::Attribute]) {
+        let attrs = attrs
+            .iter()
+            .filter_map(|attr| Some((ConflictingFnAttributes::attr_name(attr)?, attr.span)))
+            .collect::<Vec<_>>();
+        for &(first, second, outcome) in CONFLICTING_FN_ATTRS {
+            let first_span = attrs.iter().find(|&&(name, _)| name == first);
+            let second_span = attrs.iter().find(|&&(name, _)| name == second);
+            if let (Some(&(_, first_span)), Some(&(_, second_span))) = (first_span, second_span) {
+                cx.struct_span_lint(
+                    CONFLICTING_FN_ATTRIBUTES,
+                    MultiSpan::from_spans(vec![first_span, second_span]),
+                    &format!("`#[{}]` conflicts with `#[{}]`", first, second),
+                )
+                .note(outcome)
+                .emit();
+            }
+        }
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for ConflictingFnAttributes {
+    fn check_item(&mut self, cx: &LateContext<'_, '_>, it: &hir::Item<'_>) {
+        if let hir::ItemKind::Fn(..) = it.kind {
+            ConflictingFnAttributes::check_attrs(cx, &it.attrs);
+        }
+    }
+
+    fn check_trait_item(&mut self, cx: &LateContext<'_, '_>, item: &hir::TraitItem<'_>) {
+        if let hir::TraitItemKind::Method(..) = item.kind {
+            ConflictingFnAttributes::check_attrs(cx, &item.attrs);
+        }
+    }
+
+    fn check_impl_item(&mut self, cx: &LateContext<'_, '_>, item: &hir::ImplItem<'_>) {
+        if let hir::ImplItemKind::Method(..) = item.kind {
+            ConflictingFnAttributes::check_attrs(cx, &item.attrs);
+        }
+    }
+}
+
+declare_lint! {
+    pub REDUNDANT_GENERIC_INLINE,
+    Allow,
+    "detects `#[inline]` on public generic functions, which are inlinable without it"
+}
+
+declare_lint_pass!(
+    /// Checks for `#[inline]` on exported functions that are generic over types or consts,
+    /// including through their impl or trait. These are instantiated in the crates that call
+    /// them, so they can be inlined there without the attribute.
+    RedundantGenericInline => [REDUNDANT_GENERIC_INLINE]
+);
+
+impl RedundantGenericInline {
+    fn check_fn(cx: &LateContext<'_, '_>, hir_id: hir::HirId, attrs: &[ast::Attribute]) {
+        // `#[inline(always)]` and `#[inline(never)]` change more than whether the function can
+        // be inlined, and the `#[inline]`s of derives aren't written by the user.
+        let attr = match attrs.iter().find(|attr| attr.check_name(sym::inline)) {
+            Some(attr) if attr.is_word() && !attr.span.from_expansion() => attr,
+            _ => return,
+        };
+        if !cx.access_levels.is_exported(hir_id) {
+            return;
+        }
+        let def_id = cx.tcx.hir().local_def_id(hir_id);
+        if !cx.tcx.generics_of(def_id).requires_monomorphization(cx.tcx) {
+            return;
+        }
+
+        let msg = format!("`#[inline]` on generic function `{}`", cx.tcx.def_path_str(def_id));
+        cx.struct_span_lint(REDUNDANT_GENERIC_INLINE, attr.span, &msg)
+            .note(
+                "generic functions are instantiated in the crates that call them, where they can \
+                 be inlined without the attribute",
+            )
+            .span_suggestion_short(
+                attr.span,
+                "remove this attribute",
+                String::new(),
+                Applicability::MachineApplicable,
+            )
+            .emit();
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for RedundantGenericInline {
+    fn check_item(&mut self, cx: &LateContext<'_, '_>, it: &hir::Item<'_>) {
+        if let hir::ItemKind::Fn(..) = it.kind {
+            RedundantGenericInline::check_fn(cx, it.hir_id, &it.attrs);
+        }
+    }
+
+    fn check_trait_item(&mut self, cx: &LateContext<'_, '_>, item: &hir::TraitItem<'_>) {
+        if let hir::TraitItemKind::Method(_, hir::TraitMethod::Provided(_)) = item.kind {
+            RedundantGenericInline::check_fn(cx, item.hir_id, &item.attrs);
+        }
+    }
+
+    fn check_impl_item(&mut self, cx: &LateContext<'_, '_>, item: &hir::ImplItem<'_>) {
+        if let hir::ImplItemKind::Method(..) = item.kind {
+            RedundantGenericInline::check_fn(cx, item.hir_id, &item.attrs);
+        }
+    }
+}
+
+declare_lint! {
+    pub UNGUARDED_TARGET_FEATURE_CALLS,
+    Warn,
+    "detects calls to `#[target_feature]` functions without a check for the feature"
+}
+
+declare_lint_pass!(
+    /// Checks for calls to functions with `#[target_feature(enable = "...")]` from functions
+    /// without target features of their own, outside of an `if` that detects the feature at
+    /// runtime.
+    UnguardedTargetFeatureCalls => [UNGUARDED_TARGET_FEATURE_CALLS]
+);
+
+/// Finds a use of one of the `is_*_feature_detected!` macros.
+struct FeatureDetection {
+    found: bool,
+}
+
+impl<'v> Visitor<'v> for FeatureDetection {
+    type Map = Map<'v>;
+
+    fn nested_visit_map(&mut self) -> intravisit::NestedVisitorMap<'_, Self::Map> {
+        intravisit::NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'v hir::Expr<'v>) {
+        let mut span = expr.span;
+        while span.from_expansion() {
+            let expn_data = span.ctxt().outer_expn_data();
+            if let ExpnKind::Macro(MacroKind::Bang, name) = expn_data.kind {
+                if name.as_str().ends_with("_feature_detected") {
+                    self.found = true;
+                    return;
+                }
+            }
+            span = expn_data.call_site;
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+impl UnguardedTargetFeatureCalls {
+    /// Whether `hir_id` is in the `then` branch of an `if` whose condition detects a
+    /// target feature.
+    fn is_guarded(cx: &LateContext<'_, '_>, hir_id: hir::HirId, span: Span) -> bool {
+        let hir = cx.tcx.hir();
+        let mut current = hir_id;
+        loop {
+            let parent = hir.get_parent_node(current);
+            if parent == current {
+                return false;
+            }
+            current = parent;
+            match hir.find(current) {
+                Some(Node::Expr(hir::Expr {
+                    kind: hir::ExprKind::Match(cond, arms, hir::MatchSource::IfDesugar { .. }),
+                    ..
+                })) if arms[0].body.span.contains(span) => {
+                    let mut detection = FeatureDetection { found: false };
+                    detection.visit_expr(cond);
+                    if detection.found {
+                        return true;
+                    }
+                }
+                Some(Node::Item(_)) | None => return false,
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for UnguardedTargetFeatureCalls {
+    fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, expr: &'tcx hir::Expr<'tcx>) {
+        let callee_id = match expr.kind {
+            hir::ExprKind::Call(ref callee, _) => match callee.kind {
+                hir::ExprKind::Path(ref qpath) => match cx.tables.qpath_res(qpath, callee.hir_id) {
+                    Res::Def(DefKind::Fn, def_id) | Res::Def(DefKind::Method, def_id) => def_id,
+                    _ => return,
+                },
+                _ => return,
+            },
+            hir::ExprKind::MethodCall(..) => match cx.tables.type_dependent_def_id(expr.hir_id) {
+                Some(def_id) => def_id,
+                None => return,
+            },
+            _ => return,
+        };
+        let callee_attrs = cx.tcx.codegen_fn_attrs(callee_id);
+        if callee_attrs.target_features.is_empty() {
+            return;
+        }
+
+        // Functions with target features of their own, as well as `unsafe fn`s, are expected
+        // to only be called once the features have been checked for.
+        let caller_id = cx.tcx.hir().local_def_id(cx.tcx.hir().get_parent_item(expr.hir_id));
+        match cx.tcx.def_kind(caller_id) {
+            Some(DefKind::Fn) | Some(DefKind::Method) => {
+                if !cx.tcx.codegen_fn_attrs(caller_id).target_features.is_empty()
+                    || cx.tcx.fn_sig(caller_id).unsafety() == hir::Unsafety::Unsafe
+                {
+                    return;
+                }
+            }
+            _ => {}
+        }
+
+        let config = &cx.sess().parse_sess.config;
+        let missing = callee_attrs
+            .target_features
+            .iter()
+            .filter(|&&feature| !config.contains(&(sym::target_feature, Some(feature))))
+            .collect::<Vec<_>>();
+        if missing.is_empty() || UnguardedTargetFeatureCalls::is_guarded(cx, expr.hir_id, expr.span)
+        {
+            return;
+        }
+
+        let name = cx.tcx.def_path_str(callee_id);
+        let features = missing.iter().map(|f| format!("`{}`", f)).collect::<Vec<_>>().join(", ");
+        let msg = format!(
+            "call to `{}` requires target {} {}, which may not be available",
+            name,
+            if missing.len() == 1 { "feature" } else { "features" },
+            features
+        );
+        cx.struct_span_lint(UNGUARDED_TARGET_FEATURE_CALLS, expr.span, &msg)
+            .note("executing instructions the CPU does not support is undefined behavior")
+            .help(&format!(
+                "check for the {} at runtime, e.g. with `is_x86_feature_detected!(\"{}\")`, or \
+                 enable {} for the whole crate with `-C target-feature`",
+                if missing.len() == 1 { "feature" } else { "features" },
+                missing[0],
+                if missing.len() == 1 { "it" } else { "them" },
+            ))
+            .emit();
+    }
+}
+
+declare_lint! {
+    pub MISSING_NO_MANGLE,
+    Allow,
+    "detects exported `extern` functions of C libraries without `#[no_mangle]`"
+}
+
+declare_lint_pass!(
+    /// Checks for public `extern` functions of `cdylib` and `staticlib` crates whose symbol is
+    /// mangled, so that C code can't link to them under their name.
+    MissingNoMangle => [MISSING_NO_MANGLE]
+);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for MissingNoMangle {
+    fn check_item(&mut self, cx: &LateContext<'a, 'tcx>, item: &'tcx hir::Item<'tcx>) {
+        let generics = match item.kind {
+            hir::ItemKind::Fn(ref sig, ref generics, _) => match sig.header.abi {
+                Abi::Rust | Abi::RustCall | Abi::RustIntrinsic | Abi::PlatformIntrinsic => return,
+                _ => generics,
+            },
+            _ => return,
+        };
+
+        let crate_types = cx.tcx.sess.crate_types.borrow();
+        if !crate_types.iter().any(|ty| *ty == CrateType::Cdylib || *ty == CrateType::Staticlib) {
+            return;
+        }
+        // Functions only reachable from Rust, e.g. through a function pointer, are called
+        // through that and not by their symbol.
+        if !cx.access_levels.is_exported(item.hir_id) {
+            return;
+        }
+        if attr::contains_name(&item.attrs, sym::no_mangle)
+            || attr::contains_name(&item.attrs, sym::export_name)
+        {
+            return;
+        }
+        // Generic functions can't be `#[no_mangle]`, which `no_mangle_generic_items` is about.
+        let is_generic = generics.params.iter().any(|param| match param.kind {
+            GenericParamKind::Lifetime { .. } => false,
+            GenericParamKind::Type { .. } | GenericParamKind::Const { .. } => true,
+        });
+        if is_generic {
+            return;
+        }
+
+        let source_map = cx.tcx.sess.source_map();
+        let span = source_map.def_span(item.span);
+        let margin = source_map.span_to_margin(item.span).unwrap_or(0);
+        let msg = format!("exported `extern` function `{}` has a mangled symbol", item.ident);
+        cx.struct_span_lint(MISSING_NO_MANGLE, span, &msg)
+            .note("C code linking to this library can't refer to it by its name")
+            .span_suggestion(
+                item.span.shrink_to_lo(),
+                "export it under its name",
+                format!("#[no_mangle]\n{}", " ".repeat(margin)),
+                Applicability::MaybeIncorrect,
+            )
+            .emit();
+    }
+}
+
+declare_lint! {
+    pub FLOATS,
+    Allow,
+    "detects uses of floating-point types, literals and operations outside of `#[cfg(test)]`"
+}
+
+#[derive(Default)]
+pub struct Floats {
+    /// How many `#[cfg(test)]` items the pass is in.
+    test_items: usize,
+}
+
+impl_lint_pass!(
+    /// Checks for floating-point code, for targets where it is implemented in software and
+    /// pulls in large support routines. Tests are expected to run on the host, so the items
+    /// marked `#[cfg(test)]` are left alone.
+    Floats => [FLOATS]
+);
+
+impl Floats {
+    fn is_cfg_test(attrs: &[ast::Attribute]) -> bool {
+        attrs.iter().any(|attr| {
+            attr.check_name(sym::cfg)
+                && attr
+                    .meta_item_list()
+                    .map_or(false, |list| list.iter().any(|item| item.check_name(sym::test)))
+        })
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for Floats {
+    fn check_item(&mut self, _: &LateContext<'a, 'tcx>, item: &'tcx hir::Item<'tcx>) {
+        if Floats::is_cfg_test(&item.attrs) {
+            self.test_items += 1;
+        }
+    }
+
+    fn check_item_post(&mut self, _: &LateContext<'a, 'tcx>, item: &'tcx hir::Item<'tcx>) {
+        if Floats::is_cfg_test(&item.attrs) {
+            self.test_items -= 1;
+        }
+    }
+
+    fn check_ty(&mut self, cx: &LateContext<'a, 'tcx>, ty: &'tcx hir::Ty<'tcx>) {
+        if self.test_items > 0 {
+            return;
+        }
+        if let hir::TyKind::Path(hir::QPath::Resolved(None, ref path)) = ty.kind {
+            if let Res::PrimTy(hir::PrimTy::Float(float_ty)) = path.res {
+                let msg = format!("use of the floating-point type `{}`", float_ty.name_str());
+                cx.span_lint(FLOATS, ty.span, &msg);
+            }
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, expr: &'tcx hir::Expr<'tcx>) {
+        if self.test_items > 0 {
+            return;
+        }
+        let msg = match expr.kind {
+            hir::ExprKind::Lit(ref lit) => match lit.node {
+                ast::LitKind::Float(..) | ast::LitKind::FloatUnsuffixed(..) => {
+                    "floating-point literal"
+                }
+                _ => return,
+            },
+            // A negative literal is reported as a literal.
+            hir::ExprKind::Unary(
+                hir::UnOp::UnNeg,
+                hir::Expr { kind: hir::ExprKind::Lit(..), .. },
+            ) => {
+                return;
+            }
+            hir::ExprKind::Binary(_, ref operand, _)
+            | hir::ExprKind::AssignOp(_, ref operand, _)
+            | hir::ExprKind::Unary(hir::UnOp::UnNeg, ref operand) => {
+                if !cx.tables.expr_ty(operand).is_floating_point() {
+                    return;
+                }
+                "floating-point operation"
+            }
+            _ => return,
+        };
+        cx.span_lint(FLOATS, expr.span, msg);
+    }
+}
+
+declare_lint! {
+    pub SHADOWED_GENERIC_PARAMS,
+    Warn,
+    "detects generic parameters of nested items named like a parameter of an enclosing item"
+}
+
+#[derive(Default)]
+pub struct ShadowedGenericParams {
+    /// The generic parameters of the items the pass is in, innermost last, with whether they
+    /// are lifetimes.
+    scopes: Vec<Vec<(ast::Ident, bool)>>,
+}
+
+impl_lint_pass!(
+    /// Checks for items declared in the body of a generic function, or in an impl or trait
+    /// there, whose generic parameters reuse the names of the outer ones. The outer parameters
+    /// can't be used in nested items, so the name silently refers to the inner one.
+    ShadowedGenericParams => [SHADOWED_GENERIC_PARAMS]
+);
+
+impl ShadowedGenericParams {
+    fn item_generics(item: &ast::Item) -> Option<&ast::Generics> {
+        match item.kind {
+            ast::ItemKind::Fn(_, ref generics, _)
+            | ast::ItemKind::TyAlias(_, ref generics)
+            | ast::ItemKind::Enum(_, ref generics)
+            | ast::ItemKind::Struct(_, ref generics)
+            | ast::ItemKind::Union(_, ref generics)
+            | ast::ItemKind::Trait(_, _, ref generics, ..)
+            | ast::ItemKind::TraitAlias(ref generics, _)
+            | ast::ItemKind::Impl(_, _, _, ref generics, ..) => Some(generics),
+            _ => None,
+        }
+    }
+
+    /// Checks `generics` against the parameters of the enclosing items, leaving out the
+    /// innermost `skip` of them, and then enters their scope.
+    fn enter(&mut self, cx: &EarlyContext<'_>, generics: &ast::Generics, skip: usize) {
+        let params = generics
+            .params
+            .iter()
+            .map(|param| match param.kind {
+                ast::GenericParamKind::Lifetime => (param.ident, true),
+                ast::GenericParamKind::Type { .. } | ast::GenericParamKind::Const { .. } => {
+                    (param.ident, false)
+                }
+            })
+            .collect::<Vec<_>>();
+        let outer_scopes = &self.scopes[..self.scopes.len().saturating_sub(skip)];
+        for &(name, is_lifetime) in &params {
+            if name.span.from_expansion() {
+                continue;
+            }
+            let outer = outer_scopes.iter().rev().flat_map(|scope| scope.iter()).find(
+                |&&(outer, outer_is_lifetime)| {
+                    outer.name == name.name && outer_is_lifetime == is_lifetime
+                },
+            );
+            let outer = match outer {
+                Some(&(outer, _)) if !outer.span.from_expansion() => outer,
+                _ => continue,
+            };
+            cx.struct_span_lint(
+                SHADOWED_GENERIC_PARAMS,
+                name.span,
+                &format!("generic parameter `{}` shadows a parameter of an enclosing item", name),
+            )
+            .span_label(name.span, format!("refers to this `{}` in the nested item", name))
+            .span_label(outer.span, format!("outer `{}` declared here", name))
+            .note(&format!(
+                "nested items can't use the generic parameters of the items around them, so \
+                 every `{}` in the nested item refers to the inner parameter",
+                name
+            ))
+            .help("consider giving the inner parameter a different name")
+            .emit();
+        }
+        self.scopes.push(params);
+    }
+}
+
+impl EarlyLintPass for ShadowedGenericParams {
+    fn check_item(&mut self, cx: &EarlyContext<'_>, item: &ast::Item) {
+        if let Some(generics) = ShadowedGenericParams::item_generics(item) {
+            self.enter(cx, generics, 0);
+        }
+    }
+
+    fn check_item_post(&mut self, _: &EarlyContext<'_>, item: &ast::Item) {
+        if ShadowedGenericParams::item_generics(item).is_some() {
+            self.scopes.pop();
+        }
+    }
+
+    // Parameters of associated items that shadow those of their own impl or trait are
+    // rejected by resolution already.
+    fn check_trait_item(&mut self, cx: &EarlyContext<'_>, item: &ast::AssocItem) {
+        self.enter(cx, &item.generics, 1);
+    }
+
+    fn check_trait_item_post(&mut self, _: &EarlyContext<'_>, _: &ast::AssocItem) {
+        self.scopes.pop();
+    }
+
+    fn check_impl_item(&mut self, cx: &EarlyContext<'_>, item: &ast::AssocItem) {
+        self.enter(cx, &item.generics, 1);
+    }
+
+    fn check_impl_item_post(&mut self, _: &EarlyContext<'_>, _: &ast::AssocItem) {
+        self.scopes.pop();
+    }
+}
+
+declare_lint! {
+    pub SUSPICIOUS_ASSIGNMENT_FORMATTING,
+    Warn,
+    "detects `x =- y`, `x =! y` and `x =* y`, which look like compound operators"
+}
+
+declare_lint_pass!(
+    /// Checks for assignments whose `=` is spaced apart from the place but glued to the unary
+    /// operator of the value, so that they read like `-=`, `!=` or `*=`.
+    SuspiciousAssignmentFormatting => [SUSPICIOUS_ASSIGNMENT_FORMATTING]
+);
+
+impl EarlyLintPass for SuspiciousAssignmentFormatting {
+    fn check_expr(&mut self, cx: &EarlyContext<'_>, expr: &ast::Expr) {
+        let (place, value, eq_span) = match expr.kind {
+            ast::ExprKind::Assign(ref place, ref value, eq_span) => (place, value, eq_span),
+            _ => return,
+        };
+        let (op, operand) = match value.kind {
+            ast::ExprKind::Unary(op, ref operand) => (op, operand),
+            _ => return,
+        };
+        if expr.span.from_expansion()
+            || place.span.hi() == eq_span.lo()
+            || eq_span.hi() != value.span.lo()
+        {
+            return;
+        }
+        let source_map = cx.sess.source_map();
+        let (place, operand) = match (
+            source_map.span_to_snippet(place.span),
+            source_map.span_to_snippet(operand.span),
+        ) {
+            (Ok(place), Ok(operand)) => (place, operand),
+            _ => return,
+        };
+
+        let (op, other, meaning) = match op {
+            ast::UnOp::Neg => ("-", "-=", "subtract"),
+            ast::UnOp::Not => ("!", "!=", "compare"),
+            ast::UnOp::Deref => ("*", "*=", "multiply"),
+        };
+        let op_span = eq_span.to(value.span.with_hi(value.span.lo() + BytePos(1)));
+        cx.struct_span_lint(
+            SUSPICIOUS_ASSIGNMENT_FORMATTING,
+            op_span,
+            &format!("`={}` looks like `{}`", op, other),
+        )
+        .note(&format!("this assigns `{}{}` to `{}`", op, operand, place))
+        .help(&format!(
+            "to {}, write `{} {} {}`; to assign `{}{}`, write `{} = {}{}`",
+            meaning, place, other, operand, op, operand, place, op, operand
+        ))
+        .emit();
+    }
+}
+
+declare_lint! {
+    pub MIXED_BOOL_OPERATORS,
+    Allow,
+    "detects `&&` and `||` in the same expression without parentheses around the `&&`s"
+}
+
+#[derive(Default)]
+pub struct MixedBoolOperators {
+    /// The `||`s of the chains reported so far, which are part of the same report.
+    reported_ors: FxHashSet<ast::NodeId>,
+}
+
+impl_lint_pass!(
+    /// Checks for `a || b && c`, which is `a || (b && c)` but easily read or meant as
+    /// `(a || b) && c`.
+    MixedBoolOperators => [MIXED_BOOL_OPERATORS]
+);
+
+impl MixedBoolOperators {
+    /// The `&&` operands of the `||` chain `expr`, and the `||`s that make up the chain.
+    fn and_operands<'a>(
+        expr: &'a ast::Expr,
+        operands: &mut Vec<&'a ast::Expr>,
+        ors: &mut Vec<ast::NodeId>,
+    ) {
+        if let ast::ExprKind::Binary(op, ref lhs, ref rhs) = expr.kind {
+            match op.node {
+                ast::BinOpKind::Or => {
+                    ors.push(expr.id);
+                    MixedBoolOperators::and_operands(lhs, operands, ors);
+                    MixedBoolOperators::and_operands(rhs, operands, ors);
+                }
+                ast::BinOpKind::And => operands.push(expr),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl EarlyLintPass for MixedBoolOperators {
+    fn check_expr(&mut self, cx: &EarlyContext<'_>, expr: &ast::Expr) {
+        match expr.kind {
+            ast::ExprKind::Binary(op, ..) if op.node == ast::BinOpKind::Or => {}
+            _ => return,
+        }
+        if expr.span.from_expansion() || self.reported_ors.contains(&expr.id) {
+            return;
+        }
+        let mut operands = vec![];
+        let mut ors = vec![];
+        MixedBoolOperators::and_operands(expr, &mut operands, &mut ors);
+        if operands.is_empty() {
+            return;
+        }
+
+        self.reported_ors.extend(ors);
+        let parens = operands
+            .iter()
+            .flat_map(|operand| {
+                vec![
+                    (operand.span.shrink_to_lo(), "(".to_string()),
+                    (operand.span.shrink_to_hi(), ")".to_string()),
+                ]
+            })
+            .collect();
+        cx.struct_span_lint(
+            MIXED_BOOL_OPERATORS,
+            expr.span,
+            "`&&` and `||` are mixed without parentheses",
+        )
+        .note("`&&` binds more tightly than `||`")
+        .multipart_suggestion(
+            "add parentheses to make the grouping explicit",
+            parens,
+            Applicability::MachineApplicable,
+        )
+        .emit();
+    }
+}
@@ -15,8 +15,8 @@
 //! for all lint attributes.
 
 use rustc::lint::{EarlyContext, LintStore};
-use rustc::lint::{EarlyLintPass, EarlyLintPassObject};
-use rustc::lint::{LintContext, LintPass};
+use rustc::lint::{EarlyLintCallback, EarlyLintInterests, EarlyLintPass, EarlyLintPassObject};
+use rustc::lint::{LintArray, LintContext, LintPass};
 use rustc_session::lint::LintBuffer;
 use rustc_session::Session;
 use rustc_span::Span;
@@ -38,6 +38,10 @@ struct EarlyContextAndPass<'a, T: EarlyLintPass> {
 impl<'a, T: EarlyLintPass> EarlyContextAndPass<'a, T> {
     fn check_id(&mut self, id: ast::NodeId) {
         for early_lint in self.context.buffered.take(id) {
+            let lint = early_lint.lint_id.lint;
+            if !self.context.mark_lint_emitted(lint, Some(&early_lint.span), &early_lint.msg) {
+                continue;
+            }
             self.context.lookup_and_emit_with_diagnostics(
                 early_lint.lint_id.lint,
                 Some(early_lint.span.clone()),
@@ -84,6 +88,19 @@ impl<'a, T: EarlyLintPass> ast_visit::Visitor<'a> for EarlyContextAndPass<'a, T>
     fn visit_item(&mut self, it: &'a ast::Item) {
         self.with_lint_attrs(it.id, &it.attrs, |cx| {
             run_early_pass!(cx, check_item, it);
+            run_early_pass!(cx, check_vis, &it.vis, it.id);
+            match it.kind {
+                ast::ItemKind::Enum(ref enum_def, _) => {
+                    run_early_pass!(cx, check_enum_def, enum_def, it);
+                }
+                ast::ItemKind::Union(ref variant_data, _) => {
+                    run_early_pass!(cx, check_union_def, variant_data, it);
+                }
+                ast::ItemKind::TraitAlias(_, ref bounds) => {
+                    run_early_pass!(cx, check_trait_alias, bounds, it);
+                }
+                _ => {}
+            }
             ast_visit::walk_item(cx, it);
             run_early_pass!(cx, check_item_post, it);
         })
@@ -92,6 +109,7 @@ impl<'a, T: EarlyLintPass> ast_visit::Visitor<'a> for EarlyContextAndPass<'a, T>
     fn visit_foreign_item(&mut self, it: &'a ast::ForeignItem) {
         self.with_lint_attrs(it.id, &it.attrs, |cx| {
             run_early_pass!(cx, check_foreign_item, it);
+            run_early_pass!(cx, check_vis, &it.vis, it.id);
             ast_visit::walk_foreign_item(cx, it);
             run_early_pass!(cx, check_foreign_item_post, it);
         })
@@ -107,10 +125,26 @@ impl<'a, T: EarlyLintPass> ast_visit::Visitor<'a> for EarlyContextAndPass<'a, T>
     fn visit_expr(&mut self, e: &'a ast::Expr) {
         self.with_lint_attrs(e.id, &e.attrs, |cx| {
             run_early_pass!(cx, check_expr, e);
+            if let ast::ExprKind::Closure(capture, asyncness, _, ref decl, ref body, _) = e.kind {
+                run_early_pass!(cx, check_closure, e, capture, asyncness, decl, body);
+            }
             ast_visit::walk_expr(cx, e);
         })
     }
 
+    fn visit_field(&mut self, f: &'a ast::Field) {
+        self.with_lint_attrs(f.id, &f.attrs, |cx| {
+            run_early_pass!(cx, check_expr_field, f);
+            ast_visit::walk_field(cx, f);
+        })
+    }
+
+    fn visit_field_pattern(&mut self, f: &'a ast::FieldPat) {
+        self.with_lint_attrs(f.id, &f.attrs, |cx| {
+            ast_visit::walk_field_pattern(cx, f);
+        })
+    }
+
     fn visit_stmt(&mut self, s: &'a ast::Stmt) {
         run_early_pass!(self, check_stmt, s);
         self.check_id(s.id);
@@ -125,6 +159,9 @@ impl<'a, T: EarlyLintPass> ast_visit::Visitor<'a> for EarlyContextAndPass<'a, T>
         id: ast::NodeId,
     ) {
         run_early_pass!(self, check_fn, fk, decl, span, id);
+        if let Some(header) = fk.header() {
+            run_early_pass!(self, check_fn_header, header, span, id);
+        }
         self.check_id(id);
         ast_visit::walk_fn(self, fk, decl, span);
         run_early_pass!(self, check_fn_post, fk, decl, span, id);
@@ -142,6 +179,7 @@ impl<'a, T: EarlyLintPass> ast_visit::Visitor<'a> for EarlyContextAndPass<'a, T>
     fn visit_struct_field(&mut self, s: &'a ast::StructField) {
         self.with_lint_attrs(s.id, &s.attrs, |cx| {
             run_early_pass!(cx, check_struct_field, s);
+            run_early_pass!(cx, check_vis, &s.vis, s.id);
             ast_visit::walk_struct_field(cx, s);
         })
     }
@@ -149,6 +187,7 @@ impl<'a, T: EarlyLintPass> ast_visit::Visitor<'a> for EarlyContextAndPass<'a, T>
     fn visit_variant(&mut self, v: &'a ast::Variant) {
         self.with_lint_attrs(v.id, &v.attrs, |cx| {
             run_early_pass!(cx, check_variant, v);
+            run_early_pass!(cx, check_vis, &v.vis, v.id);
             ast_visit::walk_variant(cx, v);
             run_early_pass!(cx, check_variant_post, v);
         })
@@ -190,13 +229,23 @@ impl<'a, T: EarlyLintPass> ast_visit::Visitor<'a> for EarlyContextAndPass<'a, T>
         ast_visit::walk_arm(self, a);
     }
 
+    fn visit_anon_const(&mut self, c: &'a ast::AnonConst) {
+        run_early_pass!(self, check_anon_const, c);
+        ast_visit::walk_anon_const(self, c);
+    }
+
     fn visit_expr_post(&mut self, e: &'a ast::Expr) {
+        if let ast::ExprKind::Closure(capture, asyncness, _, ref decl, ref body, _) = e.kind {
+            run_early_pass!(self, check_closure_post, e, capture, asyncness, decl, body);
+        }
         run_early_pass!(self, check_expr_post, e);
     }
 
     fn visit_generic_param(&mut self, param: &'a ast::GenericParam) {
-        run_early_pass!(self, check_generic_param, param);
-        ast_visit::walk_generic_param(self, param);
+        self.with_lint_attrs(param.id, &param.attrs, |cx| {
+            run_early_pass!(cx, check_generic_param, param);
+            ast_visit::walk_generic_param(cx, param);
+        })
     }
 
     fn visit_generics(&mut self, g: &'a ast::Generics) {
@@ -209,6 +258,16 @@ impl<'a, T: EarlyLintPass> ast_visit::Visitor<'a> for EarlyContextAndPass<'a, T>
         ast_visit::walk_where_predicate(self, p);
     }
 
+    fn visit_generic_arg(&mut self, arg: &'a ast::GenericArg) {
+        run_early_pass!(self, check_generic_arg, arg);
+        ast_visit::walk_generic_arg(self, arg);
+    }
+
+    fn visit_assoc_ty_constraint(&mut self, constraint: &'a ast::AssocTyConstraint) {
+        run_early_pass!(self, check_assoc_ty_constraint, constraint);
+        ast_visit::walk_assoc_ty_constraint(self, constraint);
+    }
+
     fn visit_poly_trait_ref(&mut self, t: &'a ast::PolyTraitRef, m: &'a ast::TraitBoundModifier) {
         run_early_pass!(self, check_poly_trait_ref, t, m);
         ast_visit::walk_poly_trait_ref(self, t, m);
@@ -217,6 +276,10 @@ impl<'a, T: EarlyLintPass> ast_visit::Visitor<'a> for EarlyContextAndPass<'a, T>
     fn visit_trait_item(&mut self, trait_item: &'a ast::AssocItem) {
         self.with_lint_attrs(trait_item.id, &trait_item.attrs, |cx| {
             run_early_pass!(cx, check_trait_item, trait_item);
+            run_early_pass!(cx, check_vis, &trait_item.vis, trait_item.id);
+            if let ast::AssocItemKind::Fn(ref sig, None) = trait_item.kind {
+                run_early_pass!(cx, check_fn_header, &sig.header, trait_item.span, trait_item.id);
+            }
             ast_visit::walk_trait_item(cx, trait_item);
             run_early_pass!(cx, check_trait_item_post, trait_item);
         });
@@ -225,11 +288,22 @@ impl<'a, T: EarlyLintPass> ast_visit::Visitor<'a> for EarlyContextAndPass<'a, T>
     fn visit_impl_item(&mut self, impl_item: &'a ast::AssocItem) {
         self.with_lint_attrs(impl_item.id, &impl_item.attrs, |cx| {
             run_early_pass!(cx, check_impl_item, impl_item);
+            run_early_pass!(cx, check_vis, &impl_item.vis, impl_item.id);
             ast_visit::walk_impl_item(cx, impl_item);
             run_early_pass!(cx, check_impl_item_post, impl_item);
         });
     }
 
+    fn visit_use_tree(&mut self, use_tree: &'a ast::UseTree, id: ast::NodeId, _nested: bool) {
+        run_early_pass!(self, check_use_tree, use_tree, id);
+        ast_visit::walk_use_tree(self, use_tree, id);
+    }
+
+    fn visit_label(&mut self, label: &'a ast::Label) {
+        run_early_pass!(self, check_label, label);
+        ast_visit::walk_label(self, label);
+    }
+
     fn visit_lifetime(&mut self, lt: &'a ast::Lifetime) {
         run_early_pass!(self, check_lifetime, lt);
         self.check_id(lt.id);
@@ -264,6 +338,17 @@ impl<'a, T: EarlyLintPass> ast_visit::Visitor<'a> for EarlyContextAndPass<'a, T>
 
 struct EarlyLintPassObjects<'a> {
     lints: &'a mut [EarlyLintPassObject],
+    /// The callbacks any of `lints` implements.
+    interests: EarlyLintInterests,
+}
+
+impl<'a> EarlyLintPassObjects<'a> {
+    fn new(lints: &'a mut [EarlyLintPassObject]) -> Self {
+        let interests = lints
+            .iter()
+            .fold(EarlyLintInterests::NONE, |interests, lint| interests.union(lint.interests()));
+        EarlyLintPassObjects { lints, interests }
+    }
 }
 
 #[allow(rustc::lint_pass_impl_without_macro)]
@@ -271,11 +356,18 @@ impl LintPass for EarlyLintPassObjects<'_> {
     fn name(&self) -> &'static str {
         panic!()
     }
+
+    fn lints(&self) -> LintArray {
+        self.lints.iter().flat_map(|pass| pass.lints()).collect()
+    }
 }
 
 macro_rules! expand_early_lint_pass_impl_methods {
     ([$($(#[$attr:meta])* fn $name:ident($($param:ident: $arg:ty),*);)*]) => (
         $(fn $name(&mut self, context: &EarlyContext<'_>, $($param: $arg),*) {
+            if !self.interests.contains(EarlyLintCallback::$name) {
+                return;
+            }
             for obj in self.lints.iter_mut() {
                 obj.$name(context, $($param),*);
             }
@@ -287,6 +379,10 @@ macro_rules! early_lint_pass_impl {
     ([], [$($methods:tt)*]) => (
         impl EarlyLintPass for EarlyLintPassObjects<'_> {
             expand_early_lint_pass_impl_methods!([$($methods)*]);
+
+            fn interests(&self) -> EarlyLintInterests {
+                self.interests
+            }
         }
     )
 }
@@ -297,12 +393,14 @@ fn early_lint_crate<T: EarlyLintPass>(
     sess: &Session,
     lint_store: &LintStore,
     krate: &ast::Crate,
+    crate_name: &str,
     pass: T,
     buffered: LintBuffer,
-    warn_about_weird_lints: bool,
+    pre_expansion: bool,
 ) -> LintBuffer {
+    sess.record_lint_pass_run(|| pass.lints());
     let mut cx = EarlyContextAndPass {
-        context: EarlyContext::new(sess, lint_store, krate, buffered, warn_about_weird_lints),
+        context: EarlyContext::new(sess, lint_store, krate, crate_name, buffered, pre_expansion),
         pass,
     };
 
@@ -323,6 +421,7 @@ pub fn check_ast_crate<T: EarlyLintPass>(
     sess: &Session,
     lint_store: &LintStore,
     krate: &ast::Crate,
+    crate_name: &str,
     pre_expansion: bool,
     lint_buffer: Option<LintBuffer>,
     builtin_lints: T,
@@ -333,34 +432,60 @@ pub fn check_ast_crate<T: EarlyLintPass>(
         lint_store.early_passes.iter().map(|p| (p)()).collect()
     };
     let mut buffered = lint_buffer.unwrap_or_default();
+    let kind = if pre_expansion { "pre-expansion" } else { "early" };
 
     if !sess.opts.debugging_opts.no_interleave_lints {
-        buffered =
-            early_lint_crate(sess, lint_store, krate, builtin_lints, buffered, pre_expansion);
+        buffered = sess.verbose_lint_pass(
+            || format!("builtin {} lints", kind),
+            || {
+                early_lint_crate(
+                    sess,
+                    lint_store,
+                    krate,
+                    crate_name,
+                    builtin_lints,
+                    buffered,
+                    pre_expansion,
+                )
+            },
+        );
 
         if !passes.is_empty() {
-            buffered = early_lint_crate(
-                sess,
-                lint_store,
-                krate,
-                EarlyLintPassObjects { lints: &mut passes[..] },
-                buffered,
-                pre_expansion,
+            buffered = sess.verbose_lint_pass(
+                || format!("plugin {} lints", kind),
+                || {
+                    early_lint_crate(
+                        sess,
+                        lint_store,
+                        krate,
+                        crate_name,
+                        EarlyLintPassObjects::new(&mut passes[..]),
+                        buffered,
+                        pre_expansion,
+                    )
+                },
             );
         }
     } else {
         for pass in &mut passes {
+            let name = pass.name();
             buffered = sess
                 .prof
-                .extra_verbose_generic_activity(&format!("running lint: {}", pass.name()))
+                .extra_verbose_generic_activity(&format!("running lint: {}", name))
                 .run(|| {
-                    early_lint_crate(
-                        sess,
-                        lint_store,
-                        krate,
-                        EarlyLintPassObjects { lints: slice::from_mut(pass) },
-                        buffered,
-                        pre_expansion,
+                    sess.verbose_lint_pass(
+                        || format!("{} ({})", name, kind),
+                        || {
+                            early_lint_crate(
+                                sess,
+                                lint_store,
+                                krate,
+                                crate_name,
+                                EarlyLintPassObjects::new(slice::from_mut(pass)),
+                                buffered,
+                                pre_expansion,
+                            )
+                        },
                     )
                 });
         }
@@ -16,14 +16,14 @@
 
 use rustc::hir::map::Map;
 use rustc::lint::LateContext;
-use rustc::lint::{LateLintPass, LateLintPassObject};
+use rustc::lint::{LateLintCallback, LateLintInterests, LateLintPass, LateLintPassObject};
 use rustc::ty::{self, TyCtxt};
 use rustc_data_structures::sync::{join, par_iter, ParallelIterator};
 use rustc_hir as hir;
 use rustc_hir::def_id::{DefId, LOCAL_CRATE};
 use rustc_hir::intravisit as hir_visit;
 use rustc_hir::intravisit::Visitor;
-use rustc_session::lint::LintPass;
+use rustc_session::lint::{LintArray, LintPass};
 use rustc_span::Span;
 use syntax::ast;
 use syntax::walk_list;
@@ -153,6 +153,18 @@ impl<'a, 'tcx, T: LateLintPass<'a, 'tcx>> hir_visit::Visitor<'tcx>
         })
     }
 
+    fn visit_field(&mut self, f: &'tcx hir::Field<'tcx>) {
+        self.with_lint_attrs(f.expr.hir_id, &f.attrs, |cx| {
+            hir_visit::walk_field(cx, f);
+        })
+    }
+
+    fn visit_field_pattern(&mut self, f: &'tcx hir::FieldPat<'tcx>) {
+        self.with_lint_attrs(f.pat.hir_id, &f.attrs, |cx| {
+            hir_visit::walk_field_pattern(cx, f);
+        })
+    }
+
     fn visit_stmt(&mut self, s: &'tcx hir::Stmt<'tcx>) {
         // statement attributes are actually just attributes on one of
         // - item
@@ -249,8 +261,10 @@ impl<'a, 'tcx, T: LateLintPass<'a, 'tcx>> hir_visit::Visitor<'tcx>
     }
 
     fn visit_generic_param(&mut self, p: &'tcx hir::GenericParam<'tcx>) {
-        lint_callback!(self, check_generic_param, p);
-        hir_visit::walk_generic_param(self, p);
+        self.with_lint_attrs(p.hir_id, &p.attrs, |cx| {
+            lint_callback!(cx, check_generic_param, p);
+            hir_visit::walk_generic_param(cx, p);
+        })
     }
 
     fn visit_generics(&mut self, g: &'tcx hir::Generics<'tcx>) {
@@ -315,6 +329,17 @@ impl<'a, 'tcx, T: LateLintPass<'a, 'tcx>> hir_visit::Visitor<'tcx>
 
 struct LateLintPassObjects<'a> {
     lints: &'a mut [LateLintPassObject],
+    /// The callbacks any of `lints` implements.
+    interests: LateLintInterests,
+}
+
+impl<'a> LateLintPassObjects<'a> {
+    fn new(lints: &'a mut [LateLintPassObject]) -> Self {
+        let interests = lints
+            .iter()
+            .fold(LateLintInterests::NONE, |interests, lint| interests.union(lint.interests()));
+        LateLintPassObjects { lints, interests }
+    }
 }
 
 #[allow(rustc::lint_pass_impl_without_macro)]
@@ -322,11 +347,18 @@ impl LintPass for LateLintPassObjects<'_> {
     fn name(&self) -> &'static str {
         panic!()
     }
+
+    fn lints(&self) -> LintArray {
+        self.lints.iter().flat_map(|pass| pass.lints()).collect()
+    }
 }
 
 macro_rules! expand_late_lint_pass_impl_methods {
     ([$a:tt, $hir:tt], [$($(#[$attr:meta])* fn $name:ident($($param:ident: $arg:ty),*);)*]) => (
         $(fn $name(&mut self, context: &LateContext<$a, $hir>, $($param: $arg),*) {
+            if !self.interests.contains(LateLintCallback::$name) {
+                return;
+            }
             for obj in self.lints.iter_mut() {
                 obj.$name(context, $($param),*);
             }
@@ -338,6 +370,10 @@ macro_rules! late_lint_pass_impl {
     ([], [$hir:tt], $methods:tt) => (
         impl<'a, $hir> LateLintPass<'a, $hir> for LateLintPassObjects<'_> {
             expand_late_lint_pass_impl_methods!(['a, $hir], $methods);
+
+            fn interests(&self) -> LateLintInterests {
+                self.interests
+            }
         }
     )
 }
@@ -349,6 +385,7 @@ fn late_lint_mod_pass<'tcx, T: for<'a> LateLintPass<'a, 'tcx>>(
     module_def_id: DefId,
     pass: T,
 ) {
+    tcx.sess.record_lint_pass_run(|| pass.lints());
     let access_levels = &tcx.privacy_access_levels(LOCAL_CRATE);
 
     let context = LateContext {
@@ -383,17 +420,29 @@ pub fn late_lint_mod<'tcx, T: for<'a> LateLintPass<'a, 'tcx>>(
         return;
     }
 
-    late_lint_mod_pass(tcx, module_def_id, builtin_lints);
+    let module_name = || {
+        let path = tcx.def_path_str(module_def_id);
+        if path.is_empty() { "the crate root".to_string() } else { format!("`{}`", path) }
+    };
+
+    tcx.sess.verbose_lint_pass(
+        || format!("builtin late module lints in {}", module_name()),
+        || late_lint_mod_pass(tcx, module_def_id, builtin_lints),
+    );
 
     let mut passes: Vec<_> =
         tcx.lint_store.late_module_passes.iter().map(|pass| (pass)()).collect();
 
     if !passes.is_empty() {
-        late_lint_mod_pass(tcx, module_def_id, LateLintPassObjects { lints: &mut passes[..] });
+        tcx.sess.verbose_lint_pass(
+            || format!("plugin late module lints in {}", module_name()),
+            || late_lint_mod_pass(tcx, module_def_id, LateLintPassObjects::new(&mut passes[..])),
+        );
     }
 }
 
 fn late_lint_pass_crate<'tcx, T: for<'a> LateLintPass<'a, 'tcx>>(tcx: TyCtxt<'tcx>, pass: T) {
+    tcx.sess.record_lint_pass_run(|| pass.lints());
     let access_levels = &tcx.privacy_access_levels(LOCAL_CRATE);
 
     let krate = tcx.hir().krate();
@@ -428,17 +477,32 @@ fn late_lint_crate<'tcx, T: for<'a> LateLintPass<'a, 'tcx>>(tcx: TyCtxt<'tcx>, b
 
     if !tcx.sess.opts.debugging_opts.no_interleave_lints {
         if !passes.is_empty() {
-            late_lint_pass_crate(tcx, LateLintPassObjects { lints: &mut passes[..] });
+            tcx.sess.verbose_lint_pass(
+                || "plugin late lints".to_string(),
+                || late_lint_pass_crate(tcx, LateLintPassObjects::new(&mut passes[..])),
+            );
         }
 
-        late_lint_pass_crate(tcx, builtin_lints);
+        tcx.sess.verbose_lint_pass(
+            || "builtin late lints".to_string(),
+            || late_lint_pass_crate(tcx, builtin_lints),
+        );
     } else {
         for pass in &mut passes {
+            let name = pass.name();
             tcx.sess
                 .prof
-                .extra_verbose_generic_activity(&format!("running late lint: {}", pass.name()))
+                .extra_verbose_generic_activity(&format!("running late lint: {}", name))
                 .run(|| {
-                    late_lint_pass_crate(tcx, LateLintPassObjects { lints: slice::from_mut(pass) });
+                    tcx.sess.verbose_lint_pass(
+                        || format!("{} (late)", name),
+                        || {
+                            late_lint_pass_crate(
+                                tcx,
+                                LateLintPassObjects::new(slice::from_mut(pass)),
+                            )
+                        },
+                    );
                 });
         }
 
@@ -446,14 +510,20 @@ fn late_lint_crate<'tcx, T: for<'a> LateLintPass<'a, 'tcx>>(tcx: TyCtxt<'tcx>, b
             tcx.lint_store.late_module_passes.iter().map(|pass| (pass)()).collect();
 
         for pass in &mut passes {
+            let name = pass.name();
             tcx.sess
                 .prof
-                .extra_verbose_generic_activity(&format!(
-                    "running late module lint: {}",
-                    pass.name()
-                ))
+                .extra_verbose_generic_activity(&format!("running late module lint: {}", name))
                 .run(|| {
-                    late_lint_pass_crate(tcx, LateLintPassObjects { lints: slice::from_mut(pass) });
+                    tcx.sess.verbose_lint_pass(
+                        || format!("{} (late module)", name),
+                        || {
+                            late_lint_pass_crate(
+                                tcx,
+                                LateLintPassObjects::new(slice::from_mut(pass)),
+                            )
+                        },
+                    );
                 });
         }
     }
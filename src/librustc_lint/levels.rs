@@ -1,7 +1,8 @@
 use rustc::hir::map::Map;
-use rustc::lint::{LintLevelMap, LintLevelSets, LintLevelsBuilder, LintStore};
+use rustc::lint::{self, builtin, LintLevelMap, LintLevelSets, LintLevelsBuilder, LintStore};
 use rustc::ty::query::Providers;
 use rustc::ty::TyCtxt;
+use rustc_data_structures::fx::FxHashSet;
 use rustc_hir as hir;
 use rustc_hir::def_id::{CrateNum, LOCAL_CRATE};
 use rustc_hir::intravisit;
@@ -30,6 +31,38 @@ fn lint_levels(tcx: TyCtxt<'_>, cnum: CrateNum) -> &LintLevelMap {
     tcx.arena.alloc(builder.levels.build_map())
 }
 
+/// Warns about the `allow` attributes that did not suppress any lint. This has to run after
+/// every other lint has been emitted, including those of the mono item collection.
+pub fn check_unused_allows(tcx: TyCtxt<'_>) {
+    // Queries whose results are loaded from the incremental cache don't emit their lints
+    // again, so there would be no way to tell which attributes they relied on.
+    if tcx.sess.opts.incremental.is_some() {
+        return;
+    }
+
+    let used = tcx.sess.used_lint_allows.lock().clone();
+    let mut reported = FxHashSet::default();
+    for allow in &tcx.lint_levels(LOCAL_CRATE).allows {
+        if used.contains(&allow.span) || allow.span.from_expansion() {
+            continue;
+        }
+        if !reported.insert(allow.span) {
+            continue;
+        }
+        let msg = format!("`allow({})` did not suppress any lint", allow.name);
+        lint::struct_lint_level(
+            tcx.sess,
+            builtin::UNUSED_ALLOWS,
+            allow.level,
+            allow.src,
+            Some(allow.span.into()),
+            &msg,
+        )
+        .help("remove the lint from the attribute")
+        .emit();
+    }
+}
+
 struct LintLevelMapBuilder<'a, 'tcx> {
     levels: LintLevelsBuilder<'tcx>,
     tcx: TyCtxt<'tcx>,
@@ -81,6 +114,26 @@ impl<'tcx> intravisit::Visitor<'tcx> for LintLevelMapBuilder<'_, 'tcx> {
         })
     }
 
+    // Fields are not HIR map nodes, so their lint levels are registered against the
+    // expression or pattern they contain.
+    fn visit_field(&mut self, f: &'tcx hir::Field<'tcx>) {
+        self.with_lint_attrs(f.expr.hir_id, &f.attrs, |builder| {
+            intravisit::walk_field(builder, f);
+        })
+    }
+
+    fn visit_field_pattern(&mut self, f: &'tcx hir::FieldPat<'tcx>) {
+        self.with_lint_attrs(f.pat.hir_id, &f.attrs, |builder| {
+            intravisit::walk_field_pattern(builder, f);
+        })
+    }
+
+    fn visit_generic_param(&mut self, p: &'tcx hir::GenericParam<'tcx>) {
+        self.with_lint_attrs(p.hir_id, &p.attrs, |builder| {
+            intravisit::walk_generic_param(builder, p);
+        })
+    }
+
     fn visit_struct_field(&mut self, s: &'tcx hir::StructField<'tcx>) {
         self.with_lint_attrs(s.hir_id, &s.attrs, |builder| {
             intravisit::walk_struct_field(builder, s);
@@ -23,13 +23,18 @@ extern crate rustc;
 extern crate rustc_session;
 
 mod array_into_iter;
+mod async_await;
 pub mod builtin;
 mod early;
 mod late;
 mod levels;
 mod non_ascii_idents;
+mod noop_method_call;
 mod nonstandard_style;
 mod redundant_semicolon;
+mod report;
+mod source_hygiene;
+mod standalone;
 mod types;
 mod unused;
 
@@ -51,11 +56,14 @@ use syntax::ast;
 use lint::LintId;
 
 use array_into_iter::ArrayIntoIter;
+use async_await::*;
 use builtin::*;
 use non_ascii_idents::*;
 use nonstandard_style::*;
+use noop_method_call::NoopMethodCall;
 use redundant_semicolon::*;
 use rustc::lint::internal::*;
+use source_hygiene::SourceHygiene;
 use types::*;
 use unused::*;
 
@@ -63,6 +71,9 @@ use unused::*;
 pub use builtin::SoftLints;
 pub use early::check_ast_crate;
 pub use late::check_crate;
+pub use levels::check_unused_allows;
+pub use report::write_lint_reports;
+pub use standalone::{check_ast_crate_standalone, StandaloneLintOptions};
 
 pub fn provide(providers: &mut Providers<'_>) {
     levels::provide(providers);
@@ -86,7 +97,7 @@ macro_rules! early_lint_passes {
             [
                 UnusedParens: UnusedParens,
                 UnusedImportBraces: UnusedImportBraces,
-                UnsafeCode: UnsafeCode,
+                UnsafeCode: UnsafeCode::default(),
                 AnonymousParameters: AnonymousParameters,
                 EllipsisInclusiveRangePatterns: EllipsisInclusiveRangePatterns::default(),
                 NonCamelCaseTypes: NonCamelCaseTypes,
@@ -95,6 +106,10 @@ macro_rules! early_lint_passes {
                 NonAsciiIdents: NonAsciiIdents,
                 IncompleteFeatures: IncompleteFeatures,
                 RedundantSemicolon: RedundantSemicolon,
+                SourceHygiene: SourceHygiene,
+                ShadowedGenericParams: ShadowedGenericParams::default(),
+                SuspiciousAssignmentFormatting: SuspiciousAssignmentFormatting,
+                MixedBoolOperators: MixedBoolOperators::default(),
             ]
         );
     };
@@ -130,6 +145,12 @@ macro_rules! late_lint_passes {
                 // and change this to a module lint pass
                 MissingDebugImplementations: MissingDebugImplementations::default(),
                 ArrayIntoIter: ArrayIntoIter,
+                // Tracks exported symbols across modules
+                DuplicateExportedSymbols: DuplicateExportedSymbols::default(),
+                // Follows calls across modules
+                InlineAlwaysRecursion: InlineAlwaysRecursion,
+                // Follows `Deref` impls across modules
+                DerefCycles: DerefCycles,
             ]
         );
     };
@@ -157,12 +178,30 @@ macro_rules! late_lint_mod_passes {
                 TypeAliasBounds: TypeAliasBounds,
                 TrivialConstraints: TrivialConstraints,
                 TypeLimits: TypeLimits::new(),
+                FloatEquality: FloatEquality,
+                ImplicitDiscriminantCasts: ImplicitDiscriminantCasts,
+                PointerWidthCasts: PointerWidthCasts,
+                LargeCopyTypes: LargeCopyTypes,
+                LargeConstData: LargeConstData,
+                InvalidAtomicOrdering: InvalidAtomicOrdering,
+                ImplicitStaticTraitObjects: ImplicitStaticTraitObjects,
                 NonSnakeCase: NonSnakeCase,
                 InvalidNoMangleItems: InvalidNoMangleItems,
                 // Depends on access levels
                 UnreachablePub: UnreachablePub,
                 ExplicitOutlivesRequirements: ExplicitOutlivesRequirements,
                 InvalidValue: InvalidValue,
+                InconsistentTraitImpls: InconsistentTraitImpls,
+                ExhaustivePublicTypes: ExhaustivePublicTypes,
+                MissingNoMangle: MissingNoMangle,
+                Floats: Floats::default(),
+                ConflictingFnAttributes: ConflictingFnAttributes,
+                RedundantGenericInline: RedundantGenericInline,
+                UnguardedTargetFeatureCalls: UnguardedTargetFeatureCalls,
+                NonSendAcrossAwait: NonSendAcrossAwait,
+                BlockingInAsync: BlockingInAsync,
+                NoopMethodCall: NoopMethodCall,
+                EmptyLoops: EmptyLoops,
             ]
         );
     };
@@ -245,6 +284,7 @@ fn register_builtins(store: &mut lint::LintStore, no_interleave_lints: bool) {
         UNREACHABLE_PATTERNS,
         OVERLAPPING_PATTERNS,
         UNUSED_MUST_USE,
+        UNUSED_FUTURES,
         UNUSED_UNSAFE,
         PATH_STATEMENTS,
         UNUSED_ATTRIBUTES,
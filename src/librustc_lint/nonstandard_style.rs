@@ -5,12 +5,48 @@ use rustc_hir as hir;
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::intravisit::FnKind;
 use rustc_hir::{GenericParamKind, PatKind};
+use rustc_session::lint::Lint;
+use rustc_session::Session;
 use rustc_span::symbol::sym;
 use rustc_span::{symbol::Ident, BytePos, Span};
 use rustc_target::spec::abi::Abi;
 use syntax::ast;
 use syntax::attr;
 
+/// Whether `name` is in the `allowed-names` that `--lint-spec` gives `lint`.
+fn is_allowed_name(sess: &Session, lint: &Lint, name: &str) -> bool {
+    let lint_name = lint.name_lower();
+    sess.opts
+        .lint_spec
+        .iter()
+        .filter(|entry| entry.tool.is_none() && entry.name == lint_name)
+        .flat_map(|entry| &entry.allowed_names)
+        .any(|pattern| matches_name_pattern(pattern, name))
+}
+
+/// Matches `name` against `pattern`, in which a `*` stands for any run of characters.
+fn matches_name_pattern(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    if !name.starts_with(first) {
+        return false;
+    }
+    let mut rest = &name[first.len()..];
+    let parts: Vec<_> = parts.collect();
+    match parts.split_last() {
+        None => rest.is_empty(),
+        Some((last, middle)) => {
+            for part in middle {
+                match rest.find(part) {
+                    Some(i) => rest = &rest[i + part.len()..],
+                    None => return false,
+                }
+            }
+            rest.len() >= last.len() && rest.ends_with(last)
+        }
+    }
+}
+
 #[derive(PartialEq)]
 pub enum MethodLateContext {
     TraitAutoImpl,
@@ -36,7 +72,13 @@ declare_lint! {
     "types, variants, traits and type parameters should have camel case names"
 }
 
-declare_lint_pass!(NonCamelCaseTypes => [NON_CAMEL_CASE_TYPES]);
+declare_lint! {
+    pub UPPER_CASE_ACRONYMS,
+    Allow,
+    "types and variants should spell acronyms in camel case, as in `HttpServer`"
+}
+
+declare_lint_pass!(NonCamelCaseTypes => [NON_CAMEL_CASE_TYPES, UPPER_CASE_ACRONYMS]);
 
 fn char_has_case(c: char) -> bool {
     c.is_lowercase() || c.is_uppercase()
@@ -102,11 +144,53 @@ fn to_camel_case(s: &str) -> String {
         .0
 }
 
+/// `name` with its acronyms in camel case, as in `HTTPServer` to `HttpServer`, or `None` if it
+/// has none in upper case.
+fn camel_case_acronyms(name: &str) -> Option<String> {
+    let chars: Vec<_> = name.chars().collect();
+    let mut renamed = String::with_capacity(name.len());
+    let mut changed = false;
+    for (i, &c) in chars.iter().enumerate() {
+        let prev_is_upper_case = i > 0 && chars[i - 1].is_uppercase();
+        let next_is_lower_case = chars.get(i + 1).map_or(false, |next| next.is_lowercase());
+        // An upper case letter following another one is part of an acronym, unless it starts
+        // the next word.
+        if c.is_uppercase() && prev_is_upper_case && !next_is_lower_case {
+            renamed.extend(c.to_lowercase());
+            changed = true;
+        } else {
+            renamed.push(c);
+        }
+    }
+    if changed { Some(renamed) } else { None }
+}
+
 impl NonCamelCaseTypes {
+    fn check_acronyms(&self, cx: &EarlyContext<'_>, sort: &str, ident: &Ident) {
+        let name = &ident.name.as_str();
+
+        // Names that aren't camel case at all are left to `non_camel_case_types`.
+        if !is_camel_case(name) || is_allowed_name(cx.sess(), UPPER_CASE_ACRONYMS, name) {
+            return;
+        }
+        if let Some(renamed) = camel_case_acronyms(name) {
+            let msg = format!("{} `{}` has an acronym in upper case", sort, name);
+            cx.struct_span_lint(UPPER_CASE_ACRONYMS, ident.span, &msg)
+                .span_suggestion(
+                    ident.span,
+                    "spell the acronym in camel case",
+                    renamed,
+                    Applicability::MachineApplicable,
+                )
+                .note("the uses of the name have to be renamed as well")
+                .emit();
+        }
+    }
+
     fn check_case(&self, cx: &EarlyContext<'_>, sort: &str, ident: &Ident) {
         let name = &ident.name.as_str();
 
-        if !is_camel_case(name) {
+        if !is_camel_case(name) && !is_allowed_name(cx.sess(), NON_CAMEL_CASE_TYPES, name) {
             let msg = format!("{} `{}` should have an upper camel case name", sort, name);
             cx.struct_span_lint(NON_CAMEL_CASE_TYPES, ident.span, &msg)
                 .span_suggestion(
@@ -135,8 +219,14 @@ impl EarlyLintPass for NonCamelCaseTypes {
             ast::ItemKind::TyAlias(..)
             | ast::ItemKind::Enum(..)
             | ast::ItemKind::Struct(..)
-            | ast::ItemKind::Union(..) => self.check_case(cx, "type", &it.ident),
-            ast::ItemKind::Trait(..) => self.check_case(cx, "trait", &it.ident),
+            | ast::ItemKind::Union(..) => {
+                self.check_case(cx, "type", &it.ident);
+                self.check_acronyms(cx, "type", &it.ident);
+            }
+            ast::ItemKind::Trait(..) => {
+                self.check_case(cx, "trait", &it.ident);
+                self.check_acronyms(cx, "trait", &it.ident);
+            }
             _ => (),
         }
     }
@@ -144,11 +234,13 @@ impl EarlyLintPass for NonCamelCaseTypes {
     fn check_trait_item(&mut self, cx: &EarlyContext<'_>, it: &ast::AssocItem) {
         if let ast::AssocItemKind::TyAlias(..) = it.kind {
             self.check_case(cx, "associated type", &it.ident);
+            self.check_acronyms(cx, "associated type", &it.ident);
         }
     }
 
     fn check_variant(&mut self, cx: &EarlyContext<'_>, v: &ast::Variant) {
         self.check_case(cx, "variant", &v.ident);
+        self.check_acronyms(cx, "variant", &v.ident);
     }
 
     fn check_generic_param(&mut self, cx: &EarlyContext<'_>, param: &ast::GenericParam) {
@@ -222,7 +314,7 @@ impl NonSnakeCase {
 
         let name = &ident.name.as_str();
 
-        if !is_snake_case(name) {
+        if !is_snake_case(name) && !is_allowed_name(cx.sess(), NON_SNAKE_CASE, name) {
             let sc = NonSnakeCase::to_snake_case(name);
 
             let msg = format!("{} `{}` should have a snake case name", sort, name);
@@ -374,7 +466,9 @@ impl NonUpperCaseGlobals {
     fn check_upper_case(cx: &LateContext<'_, '_>, sort: &str, ident: &Ident) {
         let name = &ident.name.as_str();
 
-        if name.chars().any(|c| c.is_lowercase()) {
+        if name.chars().any(|c| c.is_lowercase())
+            && !is_allowed_name(cx.sess(), NON_UPPER_CASE_GLOBALS, name)
+        {
             let uc = NonSnakeCase::to_snake_case(&name).to_uppercase();
 
             let msg = format!("{} `{}` should have an upper case name", sort, name);
@@ -0,0 +1,97 @@
+use rustc::lint::{LateContext, LateLintPass, LintContext};
+use rustc::ty;
+use rustc_errors::Applicability;
+use rustc_hir as hir;
+use rustc_span::symbol::sym;
+
+declare_lint! {
+    pub NOOP_METHOD_CALL,
+    Allow,
+    "detects the use of well-known noop methods"
+}
+
+declare_lint_pass!(
+    /// Checks for calls to `clone`, `borrow` and `deref` that resolve to the implementations
+    /// for references, which just return the reference they are called on.
+    NoopMethodCall => [NOOP_METHOD_CALL]
+);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for NoopMethodCall {
+    fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, expr: &'tcx hir::Expr<'tcx>) {
+        let (call, receiver) = match expr.kind {
+            hir::ExprKind::MethodCall(call, _, args) => (call, &args[0]),
+            _ => return,
+        };
+        let trait_id = match cx.tables.type_dependent_def_id(expr.hir_id) {
+            Some(def_id) => match cx.tcx.trait_of_item(def_id) {
+                Some(trait_id) => trait_id,
+                None => return,
+            },
+            None => return,
+        };
+
+        // The impls of these traits for `&T` are the only ones with a reference as `Self`
+        // (apart from the blanket `Borrow<T> for T`, which is told apart by the parameter),
+        // so there is no need to resolve the impl itself, even for generic types.
+        let substs = cx.tables.node_substs(expr.hir_id);
+        let inner_ty = match substs.type_at(0).kind {
+            ty::Ref(_, inner_ty, hir::Mutability::Not) => inner_ty,
+            _ => return,
+        };
+        let lang_items = cx.tcx.lang_items();
+        let is_noop = if Some(trait_id) == lang_items.clone_trait()
+            || Some(trait_id) == lang_items.deref_trait()
+        {
+            true
+        } else if cx.tcx.is_diagnostic_item(sym::borrow_trait, trait_id) {
+            substs.type_at(1) == inner_ty
+        } else {
+            false
+        };
+        if !is_noop {
+            return;
+        }
+
+        let method = call.ident;
+        let receiver_ty = cx.tables.expr_ty(receiver);
+        let self_ty = substs.type_at(0);
+        let call_span = expr.span.with_lo(receiver.span.hi());
+        if receiver_ty == cx.tables.expr_ty(expr) {
+            let msg =
+                format!("call to `.{}()` on a reference in this situation does nothing", method);
+            cx.struct_span_lint(NOOP_METHOD_CALL, call_span, &msg)
+                .note(&format!(
+                    "the type `{}` which `{}` is being called on is the same as the type \
+                     returned from `{}`, so the method call does not do anything",
+                    receiver_ty, method, method
+                ))
+                .span_suggestion(
+                    call_span,
+                    "remove this redundant call",
+                    String::new(),
+                    Applicability::MachineApplicable,
+                )
+                .emit();
+        } else {
+            let msg = format!(
+                "call to `.{}()` on `{}` only copies the inner reference",
+                method, receiver_ty
+            );
+            let mut err = cx.struct_span_lint(NOOP_METHOD_CALL, call_span, &msg);
+            err.note(&format!(
+                "auto-deref resolved the call to the implementation for `{}`, \
+                 which returns the reference unchanged",
+                self_ty
+            ));
+            if let Ok(snippet) = cx.sess().source_map().span_to_snippet(receiver.span) {
+                err.span_suggestion(
+                    receiver.span,
+                    &format!("dereference the receiver to call `{}` on `{}`", method, inner_ty),
+                    format!("(*{})", snippet),
+                    Applicability::MaybeIncorrect,
+                );
+            }
+            err.emit();
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! The lint reports of `--emit lint-report` (Checkstyle) and `--emit lint-report-junit`, for
+//! code-quality dashboards and merge gates that only read these formats, and the JSON listing of
+//! `--emit lint-metadata`, for tools comparing the lints of two builds.
+
+use rustc_errors::{Diagnostic, DiagnosticId, Level};
+use rustc_serialize::json::as_pretty_json;
+use rustc_session::config::OutputType;
+use rustc_session::Session;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+
+/// A lint emitted during the compilation, as it appears in the reports.
+struct ReportedLint {
+    name: String,
+    severity: &'static str,
+    message: String,
+    line: usize,
+    col: usize,
+}
+
+/// Writes the lint reports requested with `--emit`, from the lints emitted so far. This runs
+/// when the session ends, after codegen and once the handler emitted the lints it held back.
+pub fn write_lint_reports(sess: &Session) {
+    let report_types =
+        [OutputType::LintReport, OutputType::LintReportJunit, OutputType::LintMetadata];
+    if !report_types.iter().any(|ty| sess.opts.output_types.contains_key(ty)) {
+        return;
+    }
+    // Compilation stopped before the outputs were known, e.g. on a parse error.
+    let (crate_name, outputs) = match sess.lint_report_outputs.try_get() {
+        Some(outputs) => outputs,
+        None => return,
+    };
+
+    let files = reported_lints(sess);
+    for &report_type in &report_types {
+        if !sess.opts.output_types.contains_key(&report_type) {
+            continue;
+        }
+        let report = match report_type {
+            OutputType::LintReport => checkstyle_report(&files),
+            OutputType::LintReportJunit => junit_report(crate_name, &files),
+            _ => lint_metadata(sess, crate_name),
+        };
+        let path = outputs.path(report_type);
+        match fs::write(&path, report) {
+            Ok(()) => {
+                if sess.opts.json_artifact_notifications {
+                    sess.parse_sess
+                        .span_diagnostic
+                        .emit_artifact_notification(&path, report_type.shorthand());
+                }
+            }
+            Err(e) => sess.err(&format!("failed to write lint report `{}`: {}", path.display(), e)),
+        }
+    }
+}
+
+/// The lints emitted so far, by file. Lints without a span are reported against the crate root.
+fn reported_lints(sess: &Session) -> BTreeMap<String, Vec<ReportedLint>> {
+    let crate_root = match sess.local_crate_source_file {
+        Some(ref path) => path.display().to_string(),
+        None => String::new(),
+    };
+
+    let mut files = BTreeMap::new();
+    for diag in sess.recorded_lints() {
+        let name = match diag.code {
+            Some(DiagnosticId::Lint { ref name, .. }) => name.clone(),
+            _ => continue,
+        };
+        let severity = match diag.level {
+            Level::Warning => "warning",
+            _ => "error",
+        };
+        let (file, line, col) = match diag.span.primary_span() {
+            Some(span) if !span.is_dummy() => {
+                let loc = sess.source_map().lookup_char_pos(span.lo());
+                (loc.file.name.to_string(), loc.line, loc.col.0 + 1)
+            }
+            _ => (crate_root.clone(), 0, 0),
+        };
+        let lint = ReportedLint { name, severity, message: message(&diag), line, col };
+        files.entry(file).or_insert_with(Vec::new).push(lint);
+    }
+    files
+}
+
+/// The message of `diag` along with its notes and help, one per line.
+fn message(diag: &Diagnostic) -> String {
+    let mut message = diag.message();
+    for child in &diag.children {
+        message.push_str(&format!("\n{}: {}", child.level, child.message()));
+    }
+    for sugg in &diag.suggestions {
+        message.push_str(&format!("\nhelp: {}", sugg.msg));
+    }
+    message
+}
+
+fn checkstyle_report(files: &BTreeMap<String, Vec<ReportedLint>>) -> String {
+    let mut report = String::new();
+    report.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    report.push_str("<checkstyle version=\"4.3\">\n");
+    for (file, lints) in files {
+        let _ = writeln!(report, "  <file name=\"{}\">", escape(file));
+        for lint in lints {
+            let _ = writeln!(
+                report,
+                "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" \
+                 source=\"rustc.{}\"/>",
+                lint.line,
+                lint.col,
+                lint.severity,
+                escape(&lint.message),
+                lint.name
+            );
+        }
+        report.push_str("  </file>\n");
+    }
+    report.push_str("</checkstyle>\n");
+    report
+}
+
+fn junit_report(crate_name: &str, files: &BTreeMap<String, Vec<ReportedLint>>) -> String {
+    let count: usize = files.values().map(|lints| lints.len()).sum();
+    let mut report = String::new();
+    report.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    report.push_str("<testsuites>\n");
+    let _ = writeln!(
+        report,
+        "  <testsuite name=\"rustc lints: {}\" tests=\"{}\" failures=\"{}\" errors=\"0\">",
+        escape(crate_name),
+        count,
+        count
+    );
+    for (file, lints) in files {
+        for lint in lints {
+            let _ = writeln!(
+                report,
+                "    <testcase name=\"{} at {}:{}:{}\" classname=\"{}\">",
+                lint.name,
+                escape(file),
+                lint.line,
+                lint.col,
+                escape(file)
+            );
+            let first_line = lint.message.lines().next().unwrap_or("");
+            let _ = writeln!(
+                report,
+                "      <failure type=\"{}\" message=\"{}\">{}</failure>",
+                lint.severity,
+                escape(first_line),
+                escape(&lint.message)
+            );
+            report.push_str("    </testcase>\n");
+        }
+    }
+    report.push_str("  </testsuite>\n");
+    report.push_str("</testsuites>\n");
+    report
+}
+
+/// The `--emit lint-metadata` listing.
+#[derive(RustcEncodable)]
+struct LintMetadata {
+    crate_name: String,
+    lints: Vec<LintEmission>,
+}
+
+/// A lint emitted during the compilation, in the order of emission.
+#[derive(RustcEncodable)]
+struct LintEmission {
+    name: String,
+    /// "error" or "warning".
+    level: &'static str,
+    message: String,
+    spans: Vec<LintEmissionSpan>,
+    /// Whether the lint suggests a change to the code, whether or not it can be applied
+    /// automatically.
+    has_suggestion: bool,
+}
+
+#[derive(RustcEncodable)]
+struct LintEmissionSpan {
+    file_name: String,
+    /// 1-based.
+    line_start: usize,
+    line_end: usize,
+    /// 1-based, character offset.
+    column_start: usize,
+    column_end: usize,
+    is_primary: bool,
+    label: Option<String>,
+}
+
+fn lint_metadata(sess: &Session, crate_name: &str) -> String {
+    let source_map = sess.source_map();
+    let mut lints = Vec::new();
+    for diag in sess.recorded_lints() {
+        let name = match diag.code {
+            Some(DiagnosticId::Lint { ref name, .. }) => name.clone(),
+            _ => continue,
+        };
+        let spans = diag
+            .span
+            .span_labels()
+            .into_iter()
+            .filter(|label| !label.span.is_dummy())
+            .map(|label| {
+                let lo = source_map.lookup_char_pos(label.span.lo());
+                let hi = source_map.lookup_char_pos(label.span.hi());
+                LintEmissionSpan {
+                    file_name: lo.file.name.to_string(),
+                    line_start: lo.line,
+                    line_end: hi.line,
+                    column_start: lo.col.0 + 1,
+                    column_end: hi.col.0 + 1,
+                    is_primary: label.is_primary,
+                    label: label.label,
+                }
+            })
+            .collect();
+        lints.push(LintEmission {
+            name,
+            level: match diag.level {
+                Level::Warning => "warning",
+                _ => "error",
+            },
+            message: diag.message(),
+            spans,
+            has_suggestion: !diag.suggestions.is_empty(),
+        });
+    }
+    let metadata = LintMetadata { crate_name: crate_name.to_string(), lints };
+    format!("{}\n", as_pretty_json(&metadata))
+}
+
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,188 @@
+//! Lints on the text of the source files, rather than on the syntax parsed from them. They see
+//! every file the crate was read from, including those of `mod` items and `include!`.
+
+use rustc::lint::{self, EarlyContext, EarlyLintPass, Level, LintContext};
+use rustc_errors::Applicability;
+use rustc_lexer::{LiteralKind, TokenKind};
+use rustc_span::source_map::SourceFile;
+use rustc_span::{BytePos, FileName, Span};
+use syntax::ast;
+
+declare_lint! {
+    pub BYTE_ORDER_MARKS,
+    Allow,
+    "detects byte-order marks at the start of source files other than the crate root"
+}
+
+declare_lint! {
+    pub MIXED_LINE_ENDINGS,
+    Allow,
+    "detects source files with both `\\n` and `\\r\\n` line endings"
+}
+
+declare_lint! {
+    pub INVISIBLE_CHARACTERS,
+    Allow,
+    "detects invisible and zero-width characters outside of string and character literals"
+}
+
+declare_lint_pass!(
+    /// Checks the source files once the crate is expanded, so that `include!`d files are loaded.
+    SourceHygiene => [BYTE_ORDER_MARKS, MIXED_LINE_ENDINGS, INVISIBLE_CHARACTERS]
+);
+
+/// The characters `INVISIBLE_CHARACTERS` looks for, with their Unicode names.
+const INVISIBLE_CHARS: &[(char, &str)] = &[
+    ('\u{00AD}', "SOFT HYPHEN"),
+    ('\u{180E}', "MONGOLIAN VOWEL SEPARATOR"),
+    ('\u{200B}', "ZERO WIDTH SPACE"),
+    ('\u{200C}', "ZERO WIDTH NON-JOINER"),
+    ('\u{200D}', "ZERO WIDTH JOINER"),
+    ('\u{200E}', "LEFT-TO-RIGHT MARK"),
+    ('\u{200F}', "RIGHT-TO-LEFT MARK"),
+    ('\u{202A}', "LEFT-TO-RIGHT EMBEDDING"),
+    ('\u{202B}', "RIGHT-TO-LEFT EMBEDDING"),
+    ('\u{202C}', "POP DIRECTIONAL FORMATTING"),
+    ('\u{202D}', "LEFT-TO-RIGHT OVERRIDE"),
+    ('\u{202E}', "RIGHT-TO-LEFT OVERRIDE"),
+    ('\u{2060}', "WORD JOINER"),
+    ('\u{2061}', "FUNCTION APPLICATION"),
+    ('\u{2062}', "INVISIBLE TIMES"),
+    ('\u{2063}', "INVISIBLE SEPARATOR"),
+    ('\u{2064}', "INVISIBLE PLUS"),
+    ('\u{2066}', "LEFT-TO-RIGHT ISOLATE"),
+    ('\u{2067}', "RIGHT-TO-LEFT ISOLATE"),
+    ('\u{2068}', "FIRST STRONG ISOLATE"),
+    ('\u{2069}', "POP DIRECTIONAL ISOLATE"),
+    ('\u{FEFF}', "ZERO WIDTH NO-BREAK SPACE"),
+];
+
+impl EarlyLintPass for SourceHygiene {
+    fn check_crate(&mut self, cx: &EarlyContext<'_>, _: &ast::Crate) {
+        // The lints are allowed by default, and reading every file is not worth it then.
+        let lints = [BYTE_ORDER_MARKS, MIXED_LINE_ENDINGS, INVISIBLE_CHARACTERS];
+        let levels: Vec<_> = lints.iter().map(|&lint| cx.builder.lint_level(lint)).collect();
+        if levels.iter().all(|&(level, _)| level == Level::Allow) {
+            for (_, src) in levels {
+                lint::mark_allow_used(cx.sess, src);
+            }
+            return;
+        }
+
+        let files = cx.sess.source_map().files().clone();
+        // Only `.rs` files are checked, which leaves out the text read by `include_str!` and
+        // `include_bytes!`. The crate root comes first.
+        let files = files.iter().filter(|file| match file.name {
+            FileName::Real(ref path) => {
+                !file.is_imported() && path.extension().map_or(false, |ext| ext == "rs")
+            }
+            _ => false,
+        });
+        for (i, file) in files.enumerate() {
+            if i > 0 {
+                check_byte_order_mark(cx, file);
+            }
+            check_line_endings(cx, file);
+            check_invisible_chars(cx, file);
+        }
+    }
+}
+
+fn check_byte_order_mark(cx: &EarlyContext<'_>, file: &SourceFile) {
+    // The byte-order mark is stripped when the file is loaded, and recorded as the
+    // normalization of its first position.
+    let has_bom =
+        file.normalized_pos.first().map_or(false, |np| np.pos == file.start_pos && np.diff == 3);
+    if has_bom {
+        let span = Span::with_root_ctxt(file.start_pos, file.start_pos);
+        cx.struct_span_lint(BYTE_ORDER_MARKS, span, "file starts with a byte-order mark")
+            .note("only the crate root is expected to start with a byte-order mark")
+            .emit();
+    }
+}
+
+fn check_line_endings(cx: &EarlyContext<'_>, file: &SourceFile) {
+    let src = match file.src {
+        Some(ref src) => src,
+        None => return,
+    };
+    // The `\r` of each `\r\n` is stripped when the file is loaded, and recorded as the
+    // normalization of the position after the `\n`.
+    let is_crlf = |newline: usize| {
+        let after = file.start_pos + BytePos::from_usize(newline + 1);
+        file.normalized_pos.binary_search_by_key(&after, |np| np.pos).is_ok()
+    };
+
+    let mut newlines = src.match_indices('\n').map(|(i, _)| i);
+    let first_is_crlf = match newlines.next() {
+        Some(first) => is_crlf(first),
+        None => return,
+    };
+    let (mut crlf, mut lf) = if first_is_crlf { (1, 0) } else { (0, 1) };
+    let mut first_mismatch = None;
+    for newline in newlines {
+        let newline_is_crlf = is_crlf(newline);
+        if newline_is_crlf {
+            crlf += 1;
+        } else {
+            lf += 1;
+        }
+        if first_mismatch.is_none() && newline_is_crlf != first_is_crlf {
+            first_mismatch = Some(newline);
+        }
+    }
+
+    if let Some(newline) = first_mismatch {
+        let (this, other) = if first_is_crlf { ("\\n", "\\r\\n") } else { ("\\r\\n", "\\n") };
+        let lo = file.start_pos + BytePos::from_usize(newline);
+        let span = Span::with_root_ctxt(lo, lo);
+        cx.struct_span_lint(MIXED_LINE_ENDINGS, span, "file mixes `\\n` and `\\r\\n` line endings")
+            .span_label(span, format!("this line ends with `{}`", this))
+            .note(&format!(
+                "the lines before it end with `{}`; {} lines of this file end with `\\n` and {} \
+                 with `\\r\\n`",
+                other, lf, crlf
+            ))
+            .emit();
+    }
+}
+
+fn check_invisible_chars(cx: &EarlyContext<'_>, file: &SourceFile) {
+    let src = match file.src {
+        Some(ref src) => src,
+        None => return,
+    };
+    let mut pos = rustc_lexer::strip_shebang(src).unwrap_or(0);
+    for token in rustc_lexer::tokenize(&src[pos..]) {
+        let start = pos;
+        pos += token.len;
+        match token.kind {
+            TokenKind::Literal { kind: LiteralKind::Int { .. }, .. }
+            | TokenKind::Literal { kind: LiteralKind::Float { .. }, .. } => {}
+            // String and character literals may contain anything, and the lexer already
+            // rejects the characters that start no token.
+            TokenKind::Literal { .. } | TokenKind::Unknown => continue,
+            _ => {}
+        }
+        for (i, c) in src[start..pos].char_indices() {
+            let name = match INVISIBLE_CHARS.iter().find(|&&(invisible, _)| invisible == c) {
+                Some(&(_, name)) => name,
+                None => continue,
+            };
+            let lo = file.start_pos + BytePos::from_usize(start + i);
+            let span = Span::with_root_ctxt(lo, lo + BytePos::from_usize(c.len_utf8()));
+            cx.struct_span_lint(
+                INVISIBLE_CHARACTERS,
+                span,
+                &format!("invisible character `{}` ({})", c.escape_unicode(), name),
+            )
+            .span_suggestion(
+                span,
+                "remove the invisible character",
+                String::new(),
+                Applicability::MaybeIncorrect,
+            )
+            .emit();
+        }
+    }
+}
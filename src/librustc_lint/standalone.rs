@@ -0,0 +1,94 @@
+//! Running the early lint passes over a parsed crate, for tools such as IDEs that have an AST
+//! but no compilation to go with it.
+
+use crate::early::check_ast_crate;
+use crate::{new_lint_store, BuiltinCombinedEarlyLintPass, BuiltinCombinedPreExpansionLintPass};
+use rustc_data_structures::sync::{Lock, Lrc};
+use rustc_errors::emitter::Emitter;
+use rustc_errors::Diagnostic;
+use rustc_feature::Features;
+use rustc_session::build_early_lint_session;
+use rustc_session::config::{CrateType, Options};
+use rustc_session::lint::Level;
+use rustc_span::edition::Edition;
+use rustc_span::source_map::SourceMap;
+use syntax::ast;
+
+/// How the crate given to `check_ast_crate_standalone` would be compiled.
+#[derive(Clone, Debug)]
+pub struct StandaloneLintOptions {
+    pub crate_name: String,
+    /// As if passed with `--crate-type`.
+    pub crate_types: Vec<CrateType>,
+    pub edition: Edition,
+    /// Lint levels, as if passed with `-A`, `-W`, `-D` and `-F`.
+    pub lint_opts: Vec<(String, Level)>,
+    /// As if passed with `--cap-lints`.
+    pub lint_cap: Option<Level>,
+}
+
+/// Collects diagnostics instead of printing them.
+struct CollectingEmitter {
+    source_map: Lrc<SourceMap>,
+    diagnostics: Lrc<Lock<Vec<Diagnostic>>>,
+}
+
+impl Emitter for CollectingEmitter {
+    fn emit_diagnostic(&mut self, diag: &Diagnostic) {
+        self.diagnostics.lock().push(diag.clone());
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        Some(&self.source_map)
+    }
+}
+
+/// Runs the builtin pre-expansion and early lint passes over `krate`, which was parsed into
+/// `source_map`, and returns the diagnostics they emitted.
+///
+/// The crate doesn't have to be expanded, or to have node ids assigned; the lints simply won't
+/// see into macro invocations then. The passes only get a bare session made up from `options`,
+/// without a sysroot or a query system, and the crate's `#![feature]` attributes are ignored.
+pub fn check_ast_crate_standalone(
+    krate: &ast::Crate,
+    source_map: Lrc<SourceMap>,
+    options: StandaloneLintOptions,
+) -> Vec<Diagnostic> {
+    let diagnostics = Lrc::new(Lock::new(Vec::new()));
+    let emitter =
+        CollectingEmitter { source_map: source_map.clone(), diagnostics: diagnostics.clone() };
+
+    let sopts = Options {
+        edition: options.edition,
+        lint_opts: options.lint_opts,
+        lint_cap: options.lint_cap,
+        ..Options::default()
+    };
+    let sess = build_early_lint_session(sopts, source_map, Box::new(emitter));
+    sess.init_features(Features::default());
+    sess.crate_types.set(options.crate_types);
+
+    let lint_store = new_lint_store(false, false);
+    check_ast_crate(
+        &sess,
+        &lint_store,
+        krate,
+        &options.crate_name,
+        true,
+        None,
+        BuiltinCombinedPreExpansionLintPass::new(),
+    );
+    check_ast_crate(
+        &sess,
+        &lint_store,
+        krate,
+        &options.crate_name,
+        false,
+        None,
+        BuiltinCombinedEarlyLintPass::new(),
+    );
+
+    drop(sess);
+    let diagnostics = diagnostics.lock().clone();
+    diagnostics
+}
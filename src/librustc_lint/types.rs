@@ -1,22 +1,30 @@
 #![allow(non_snake_case)]
 
+use rustc::hir::map::Map;
 use rustc::lint::{LateContext, LateLintPass, LintContext};
-use rustc::mir::interpret::{sign_extend, truncate};
+use rustc::middle::resolve_lifetime as rl;
+use rustc::mir::interpret::{
+    sign_extend, truncate, AllocId, Allocation, ConstValue, GlobalAlloc, Scalar,
+};
 use rustc::ty::layout::{self, IntegerExt, LayoutOf, SizeSkeleton, VariantIdx};
 use rustc::ty::subst::SubstsRef;
 use rustc::ty::{self, AdtKind, ParamEnv, Ty, TyCtxt};
 use rustc_data_structures::fx::FxHashSet;
 use rustc_errors::Applicability;
 use rustc_hir as hir;
+use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::DefId;
+use rustc_hir::intravisit::{self, Visitor};
 use rustc_hir::{is_range_literal, ExprKind, Node};
 use rustc_index::vec::Idx;
 use rustc_span::source_map;
-use rustc_span::symbol::sym;
+use rustc_span::symbol::{sym, Symbol};
 use rustc_span::Span;
 use rustc_target::spec::abi::Abi;
 use syntax::{ast, attr};
 
+use crate::nonstandard_style::{method_context, MethodLateContext};
+
 use log::debug;
 use std::cmp;
 use std::{f32, f64, i16, i32, i64, i8, u16, u32, u64, u8};
@@ -491,6 +499,16 @@ enum FfiResult<'tcx> {
     FfiUnsafe { ty: Ty<'tcx>, reason: &'static str, help: Option<&'static str> },
 }
 
+/// A field through which a type that is not FFI-safe was reached.
+struct FfiField<'tcx> {
+    /// The type the field belongs to.
+    adt_ty: Ty<'tcx>,
+    /// The variant the field belongs to, if `adt_ty` is an enum.
+    variant: Option<DefId>,
+    field: &'tcx ty::FieldDef,
+    field_ty: Ty<'tcx>,
+}
+
 fn is_zst<'tcx>(tcx: TyCtxt<'tcx>, did: DefId, ty: Ty<'tcx>) -> bool {
     tcx.layout_of(tcx.param_env(did).and(ty)).map(|layout| layout.is_zst()).unwrap_or(false)
 }
@@ -575,6 +593,7 @@ impl<'a, 'tcx> ImproperCTypesVisitor<'a, 'tcx> {
                 sp,
                 "passing raw arrays by value is not FFI-safe",
                 Some("consider passing a pointer to the array"),
+                &[],
             );
             true
         } else {
@@ -584,7 +603,15 @@ impl<'a, 'tcx> ImproperCTypesVisitor<'a, 'tcx> {
 
     /// Checks if the given type is "ffi-safe" (has a stable, well-defined
     /// representation which can be exported to C code).
-    fn check_type_for_ffi(&self, cache: &mut FxHashSet<Ty<'tcx>>, ty: Ty<'tcx>) -> FfiResult<'tcx> {
+    ///
+    /// If it isn't, `fields` is filled with the fields leading to the type that isn't, from the
+    /// innermost one out.
+    fn check_type_for_ffi(
+        &self,
+        cache: &mut FxHashSet<Ty<'tcx>>,
+        fields: &mut Vec<FfiField<'tcx>>,
+        ty: Ty<'tcx>,
+    ) -> FfiResult<'tcx> {
         use FfiResult::*;
 
         let cx = self.cx.tcx;
@@ -646,13 +673,19 @@ impl<'a, 'tcx> ImproperCTypesVisitor<'a, 'tcx> {
                             if def.repr.transparent() && is_zst(cx, field.did, field_ty) {
                                 continue;
                             }
-                            let r = self.check_type_for_ffi(cache, field_ty);
+                            let r = self.check_type_for_ffi(cache, fields, field_ty);
                             match r {
                                 FfiSafe => {
                                     all_phantom = false;
                                 }
                                 FfiPhantom(..) => {}
                                 FfiUnsafe { .. } => {
+                                    fields.push(FfiField {
+                                        adt_ty: ty,
+                                        variant: None,
+                                        field,
+                                        field_ty,
+                                    });
                                     return r;
                                 }
                             }
@@ -691,13 +724,19 @@ impl<'a, 'tcx> ImproperCTypesVisitor<'a, 'tcx> {
                             if def.repr.transparent() && is_zst(cx, field.did, field_ty) {
                                 continue;
                             }
-                            let r = self.check_type_for_ffi(cache, field_ty);
+                            let r = self.check_type_for_ffi(cache, fields, field_ty);
                             match r {
                                 FfiSafe => {
                                     all_phantom = false;
                                 }
                                 FfiPhantom(..) => {}
                                 FfiUnsafe { .. } => {
+                                    fields.push(FfiField {
+                                        adt_ty: ty,
+                                        variant: None,
+                                        field,
+                                        field_ty,
+                                    });
                                     return r;
                                 }
                             }
@@ -757,10 +796,16 @@ impl<'a, 'tcx> ImproperCTypesVisitor<'a, 'tcx> {
                                 if def.repr.transparent() && is_zst(cx, field.did, field_ty) {
                                     continue;
                                 }
-                                let r = self.check_type_for_ffi(cache, field_ty);
+                                let r = self.check_type_for_ffi(cache, fields, field_ty);
                                 match r {
                                     FfiSafe => {}
                                     FfiUnsafe { .. } => {
+                                        fields.push(FfiField {
+                                            adt_ty: ty,
+                                            variant: Some(variant.def_id),
+                                            field,
+                                            field_ty,
+                                        });
                                         return r;
                                     }
                                     FfiPhantom(..) => {
@@ -816,10 +861,10 @@ impl<'a, 'tcx> ImproperCTypesVisitor<'a, 'tcx> {
             },
 
             ty::RawPtr(ty::TypeAndMut { ty, .. }) | ty::Ref(_, ty, _) => {
-                self.check_type_for_ffi(cache, ty)
+                self.check_type_for_ffi(cache, fields, ty)
             }
 
-            ty::Array(inner_ty, _) => self.check_type_for_ffi(cache, inner_ty),
+            ty::Array(inner_ty, _) => self.check_type_for_ffi(cache, fields, inner_ty),
 
             ty::FnPtr(sig) => {
                 match sig.abi() {
@@ -838,7 +883,7 @@ impl<'a, 'tcx> ImproperCTypesVisitor<'a, 'tcx> {
 
                 let sig = cx.erase_late_bound_regions(&sig);
                 if !sig.output().is_unit() {
-                    let r = self.check_type_for_ffi(cache, sig.output());
+                    let r = self.check_type_for_ffi(cache, fields, sig.output());
                     match r {
                         FfiSafe => {}
                         _ => {
@@ -847,7 +892,7 @@ impl<'a, 'tcx> ImproperCTypesVisitor<'a, 'tcx> {
                     }
                 }
                 for arg in sig.inputs() {
-                    let r = self.check_type_for_ffi(cache, arg);
+                    let r = self.check_type_for_ffi(cache, fields, arg);
                     match r {
                         FfiSafe => {}
                         _ => {
@@ -881,6 +926,7 @@ impl<'a, 'tcx> ImproperCTypesVisitor<'a, 'tcx> {
         sp: Span,
         note: &str,
         help: Option<&str>,
+        fields: &[FfiField<'tcx>],
     ) {
         let mut diag = self.cx.struct_span_lint(
             IMPROPER_CTYPES,
@@ -892,6 +938,19 @@ impl<'a, 'tcx> ImproperCTypesVisitor<'a, 'tcx> {
             diag.help(help);
         }
         diag.note(note);
+        for field in fields.iter().rev() {
+            let owner = match field.variant {
+                Some(variant) => format!("variant `{}`", self.cx.tcx.def_path_str(variant)),
+                None => format!("`{}`", field.adt_ty),
+            };
+            diag.span_note(
+                self.cx.tcx.def_span(field.field.did),
+                &format!(
+                    "the field `{}` of {} has type `{}`",
+                    field.field.ident, owner, field.field_ty
+                ),
+            );
+        }
         if let ty::Adt(def, _) = ty.kind {
             if let Some(sp) = self.cx.tcx.hir().span_if_local(def.did) {
                 diag.span_note(sp, "type defined here");
@@ -921,7 +980,7 @@ impl<'a, 'tcx> ImproperCTypesVisitor<'a, 'tcx> {
         let mut visitor = ProhibitOpaqueTypes { ty: None };
         ty.visit_with(&mut visitor);
         if let Some(ty) = visitor.ty {
-            self.emit_ffi_unsafe_type_lint(ty, sp, "opaque types have no C equivalent", None);
+            self.emit_ffi_unsafe_type_lint(ty, sp, "opaque types have no C equivalent", None, &[]);
             true
         } else {
             false
@@ -947,13 +1006,15 @@ impl<'a, 'tcx> ImproperCTypesVisitor<'a, 'tcx> {
             return;
         }
 
-        match self.check_type_for_ffi(&mut FxHashSet::default(), ty) {
+        let mut fields = Vec::new();
+        match self.check_type_for_ffi(&mut FxHashSet::default(), &mut fields, ty) {
             FfiResult::FfiSafe => {}
             FfiResult::FfiPhantom(ty) => {
-                self.emit_ffi_unsafe_type_lint(ty, sp, "composed only of `PhantomData`", None);
+                let note = "composed only of `PhantomData`";
+                self.emit_ffi_unsafe_type_lint(ty, sp, note, None, &[]);
             }
             FfiResult::FfiUnsafe { ty, reason, help } => {
-                self.emit_ffi_unsafe_type_lint(ty, sp, reason, help);
+                self.emit_ffi_unsafe_type_lint(ty, sp, reason, help, &fields);
             }
         }
     }
@@ -1074,3 +1135,695 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for VariantSizeDifferences {
         }
     }
 }
+
+declare_lint! {
+    LARGE_COPY_TYPES,
+    Allow,
+    "`Copy` types larger than `-Z large-copy-threshold` bytes"
+}
+
+declare_lint_pass!(
+    /// Checks for `Copy` impls, derived or manual, on types that are expensive to copy
+    /// implicitly.
+    LargeCopyTypes => [LARGE_COPY_TYPES]
+);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for LargeCopyTypes {
+    fn check_item(&mut self, cx: &LateContext<'a, 'tcx>, it: &'tcx hir::Item<'tcx>) {
+        if let hir::ItemKind::Impl(..) = it.kind {
+        } else {
+            return;
+        }
+        let impl_def_id = cx.tcx.hir().local_def_id(it.hir_id);
+        let trait_ref = match cx.tcx.impl_trait_ref(impl_def_id) {
+            Some(trait_ref) => trait_ref,
+            None => return,
+        };
+        if Some(trait_ref.def_id) != cx.tcx.lang_items().copy_trait() {
+            return;
+        }
+        let ty = cx.tcx.erase_regions(&trait_ref.self_ty());
+        // The size of a generic type is only known once it is instantiated.
+        let size = match cx.layout_of(ty) {
+            Ok(layout) => layout.size.bytes(),
+            Err(_) => return,
+        };
+        let threshold = cx.sess().opts.debugging_opts.large_copy_threshold as u64;
+        if size <= threshold {
+            return;
+        }
+
+        // Lints inside the expansion of `#[derive]` are not emitted, so report derived impls on
+        // the type definition instead.
+        let span = match ty.ty_adt_def() {
+            Some(adt_def) if attr::contains_name(&it.attrs, sym::automatically_derived) => {
+                cx.tcx.def_span(adt_def.did)
+            }
+            _ => cx.sess().source_map().def_span(it.span),
+        };
+        let mut err = cx.struct_span_lint(
+            LARGE_COPY_TYPES,
+            span,
+            &format!("`{}` is `Copy` and {} bytes in size", ty, size),
+        );
+        err.note(&format!(
+            "every implicit copy moves {} bytes, more than the limit of {} bytes",
+            size, threshold
+        ));
+        err.help("consider removing the `Copy` implementation and cloning explicitly instead");
+        err.emit();
+    }
+}
+
+declare_lint! {
+    LARGE_CONST_DATA,
+    Allow,
+    "constants and statics whose value is larger than `-Z large-const-data-threshold` bytes"
+}
+
+declare_lint_pass!(
+    /// Checks the evaluated values of constants and statics, along with the memory they point
+    /// to, for data that bloats the binary, like large `include_bytes!`.
+    LargeConstData => [LARGE_CONST_DATA]
+);
+
+impl LargeConstData {
+    /// The size of the memory `alloc_id` points to and of the memory it points to in turn,
+    /// counting each allocation once. Other statics are left out, as they aren't copied.
+    fn pointee_size(tcx: TyCtxt<'_>, alloc_id: AllocId, seen: &mut FxHashSet<AllocId>) -> u64 {
+        if !seen.insert(alloc_id) {
+            return 0;
+        }
+        let alloc = match tcx.alloc_map.lock().get(alloc_id) {
+            Some(GlobalAlloc::Memory(alloc)) => alloc,
+            _ => return 0,
+        };
+        alloc.size.bytes() + LargeConstData::relocations_size(tcx, alloc, seen)
+    }
+
+    fn relocations_size(tcx: TyCtxt<'_>, alloc: &Allocation, seen: &mut FxHashSet<AllocId>) -> u64 {
+        alloc
+            .relocations()
+            .values()
+            .map(|&((), inner)| LargeConstData::pointee_size(tcx, inner, seen))
+            .sum()
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for LargeConstData {
+    fn check_item(&mut self, cx: &LateContext<'a, 'tcx>, it: &'tcx hir::Item<'tcx>) {
+        let descr = match it.kind {
+            hir::ItemKind::Const(..) => "constant",
+            hir::ItemKind::Static(..) => "static",
+            _ => return,
+        };
+        let def_id = cx.tcx.hir().local_def_id(it.hir_id);
+        // Errors are reported by `UnusedBrokenConst`.
+        let value = match cx.tcx.const_eval_poly(def_id) {
+            Ok(value) => value,
+            Err(_) => return,
+        };
+        let layout = match cx.layout_of(value.ty) {
+            Ok(layout) => layout,
+            Err(_) => return,
+        };
+        let mut seen = FxHashSet::default();
+        let pointees = match value.val {
+            ty::ConstKind::Value(ConstValue::Scalar(Scalar::Ptr(ptr))) => {
+                LargeConstData::pointee_size(cx.tcx, ptr.alloc_id, &mut seen)
+            }
+            ty::ConstKind::Value(ConstValue::Slice { data, start, end }) => {
+                (end - start) as u64 + LargeConstData::relocations_size(cx.tcx, data, &mut seen)
+            }
+            ty::ConstKind::Value(ConstValue::ByRef { alloc, .. }) => {
+                LargeConstData::relocations_size(cx.tcx, alloc, &mut seen)
+            }
+            _ => 0,
+        };
+        let size = layout.size.bytes() + pointees;
+        let threshold = cx.sess().opts.debugging_opts.large_const_data_threshold as u64;
+        if size <= threshold {
+            return;
+        }
+
+        let mut err = cx.struct_span_lint(
+            LARGE_CONST_DATA,
+            cx.sess().source_map().def_span(it.span),
+            &format!(
+                "the value of {} `{}` takes up {} bytes, more than the limit of {} bytes",
+                descr, it.ident, size, threshold
+            ),
+        );
+        if let hir::ItemKind::Const(..) = it.kind {
+            err.note("each use of a constant can get its own copy of the value in the binary");
+            err.help(
+                "consider making it a `static`, which is stored once, or loading the data at \
+                 run time",
+            );
+        } else {
+            err.help("consider loading the data at run time");
+        }
+        err.emit();
+    }
+}
+
+declare_lint! {
+    FLOAT_EQUALITY,
+    Allow,
+    "exact equality comparisons between floating-point values"
+}
+
+declare_lint_pass!(
+    /// Checks for `==` and `!=` between floats, which rarely do what is meant once rounding
+    /// errors are involved.
+    FloatEquality => [FLOAT_EQUALITY]
+);
+
+impl FloatEquality {
+    /// Comparisons against these are exact by nature and left alone: `0.0`, `-0.0` and the
+    /// infinities.
+    fn is_exact_operand(cx: &LateContext<'_, '_>, expr: &hir::Expr<'_>) -> bool {
+        match expr.kind {
+            hir::ExprKind::Unary(hir::UnOp::UnNeg, ref inner) => {
+                FloatEquality::is_exact_operand(cx, inner)
+            }
+            hir::ExprKind::Lit(ref lit) => match lit.node {
+                ast::LitKind::Float(value, _) | ast::LitKind::FloatUnsuffixed(value) => {
+                    value.as_str().replace('_', "").parse::<f64>() == Ok(0.0)
+                }
+                _ => false,
+            },
+            hir::ExprKind::Path(ref qpath) => match cx.tables.qpath_res(qpath, expr.hir_id) {
+                Res::Def(DefKind::Const, def_id) => {
+                    let path = cx.get_def_path(def_id);
+                    match &path[..] {
+                        [krate, module, name] => {
+                            (krate.as_str() == "core" || krate.as_str() == "std")
+                                && (module.as_str() == "f32" || module.as_str() == "f64")
+                                && (name.as_str() == "INFINITY" || name.as_str() == "NEG_INFINITY")
+                        }
+                        _ => false,
+                    }
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Whether both operands name the same local, as in the usual `x != x` NaN check.
+    fn is_self_comparison(cx: &LateContext<'_, '_>, l: &hir::Expr<'_>, r: &hir::Expr<'_>) -> bool {
+        match (&l.kind, &r.kind) {
+            (hir::ExprKind::Path(ref l_path), hir::ExprKind::Path(ref r_path)) => {
+                match (cx.tables.qpath_res(l_path, l.hir_id), cx.tables.qpath_res(r_path, r.hir_id))
+                {
+                    (Res::Local(l_id), Res::Local(r_id)) => l_id == r_id,
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for FloatEquality {
+    fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, e: &'tcx hir::Expr<'tcx>) {
+        let (binop, l, r) = match e.kind {
+            hir::ExprKind::Binary(binop, ref l, ref r)
+                if binop.node == hir::BinOpKind::Eq || binop.node == hir::BinOpKind::Ne =>
+            {
+                (binop, l, r)
+            }
+            _ => return,
+        };
+        let float_ty = match cx.tables.expr_ty(l).kind {
+            ty::Float(float_ty) => float_ty,
+            _ => return,
+        };
+        if FloatEquality::is_exact_operand(cx, l)
+            || FloatEquality::is_exact_operand(cx, r)
+            || FloatEquality::is_self_comparison(cx, l, r)
+        {
+            return;
+        }
+
+        let msg = format!(
+            "strict comparison of `{}` values with `{}`",
+            float_ty.name_str(),
+            binop.node.as_str()
+        );
+        let mut err = cx.struct_span_lint(FLOAT_EQUALITY, e.span, &msg);
+        let source_map = cx.sess().source_map();
+        if let (Ok(l_snippet), Ok(mut r_snippet)) =
+            (source_map.span_to_snippet(l.span), source_map.span_to_snippet(r.span))
+        {
+            if let hir::ExprKind::Binary(..) = r.kind {
+                r_snippet = format!("({})", r_snippet);
+            }
+            let diff = format!("({} - {})", l_snippet, r_snippet);
+            let ty = float_ty.name_str();
+            let eq = binop.node == hir::BinOpKind::Eq;
+            // `abs` is only in `std`, so `no_std` crates compare against both bounds, with the
+            // constants from `core`.
+            let suggestion = if attr::contains_name(&cx.tcx.hir().krate().attrs, sym::no_std) {
+                if eq {
+                    format!("{0} > -core::{1}::EPSILON && {0} < core::{1}::EPSILON", diff, ty)
+                } else {
+                    format!("{0} <= -core::{1}::EPSILON || {0} >= core::{1}::EPSILON", diff, ty)
+                }
+            } else {
+                let cmp = if eq { "<" } else { ">=" };
+                format!("{}.abs() {} std::{}::EPSILON", diff, cmp, ty)
+            };
+            err.span_suggestion(
+                e.span,
+                "compare the difference against an epsilon instead",
+                suggestion,
+                Applicability::MaybeIncorrect,
+            );
+        }
+        err.note(
+            "rounding errors make exact comparisons of computed floating-point values unreliable",
+        );
+        err.emit();
+    }
+}
+
+declare_lint! {
+    IMPLICIT_DISCRIMINANT_CASTS,
+    Allow,
+    "`as` casts to integers of enums without a `#[repr]` or explicit discriminants"
+}
+
+declare_lint_pass!(
+    /// Checks for enums cast to integers whose discriminants are implicit, so that the result
+    /// depends on the order of the variants rather than on anything written down.
+    ImplicitDiscriminantCasts => [IMPLICIT_DISCRIMINANT_CASTS]
+);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for ImplicitDiscriminantCasts {
+    fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, e: &'tcx hir::Expr<'tcx>) {
+        let operand = match e.kind {
+            hir::ExprKind::Cast(ref operand, _) => operand,
+            _ => return,
+        };
+        let (adt, cast_ty) = match (&cx.tables.expr_ty(operand).kind, cx.tables.expr_ty(e)) {
+            (ty::Adt(adt, _), cast_ty) if adt.is_enum() && cast_ty.is_integral() => (adt, cast_ty),
+            _ => return,
+        };
+        if adt.repr.c() || adt.repr.int.is_some() {
+            return;
+        }
+        let has_explicit_discr = adt.variants.iter().any(|variant| match variant.discr {
+            ty::VariantDiscr::Explicit(_) => true,
+            ty::VariantDiscr::Relative(_) => false,
+        });
+        if has_explicit_discr {
+            return;
+        }
+
+        let name = cx.tcx.def_path_str(adt.did);
+        let msg = format!("cast of `{}` to `{}` uses its implicit discriminants", name, cast_ty);
+        let mut err = cx.struct_span_lint(IMPLICIT_DISCRIMINANT_CASTS, e.span, &msg);
+        if adt.did.is_local() {
+            err.span_label(cx.tcx.def_span(adt.did), format!("`{}` declared here", name));
+        }
+        err.note(&format!(
+            "`{}` has no `#[repr]` or explicit discriminants, so the values it is cast to \
+             change when its variants are reordered or new ones are inserted",
+            name
+        ));
+        err.help(&format!(
+            "give `{}` a representation like `#[repr(u8)]` or explicit discriminants, or \
+             convert it with a `match`",
+            name
+        ));
+        err.emit();
+    }
+}
+
+declare_lint! {
+    POINTER_WIDTH_CASTS,
+    Allow,
+    "`as` casts between pointer-sized and fixed-size integers that only truncate on some targets"
+}
+
+declare_lint_pass!(
+    /// Checks for integer casts that are lossless for some of the pointer widths of
+    /// `-Z portable-pointer-widths` and truncate for others, like `usize as u32`, which only
+    /// show up once the crate is built for the other width.
+    PointerWidthCasts => [POINTER_WIDTH_CASTS]
+);
+
+impl PointerWidthCasts {
+    /// The width in bits and the signedness of an integer type on a target with `ptr_width`.
+    fn int_repr(ty: Ty<'_>, ptr_width: u64) -> Option<(u64, bool)> {
+        match ty.kind {
+            ty::Int(int_ty) => Some((int_ty.bit_width().map_or(ptr_width, |w| w as u64), true)),
+            ty::Uint(uint_ty) => Some((uint_ty.bit_width().map_or(ptr_width, |w| w as u64), false)),
+            _ => None,
+        }
+    }
+
+    fn is_lossless(from: (u64, bool), to: (u64, bool)) -> bool {
+        match (from.1, to.1) {
+            (false, false) | (true, true) => from.0 <= to.0,
+            (false, true) => from.0 < to.0,
+            (true, false) => false,
+        }
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for PointerWidthCasts {
+    fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, e: &'tcx hir::Expr<'tcx>) {
+        let operand = match e.kind {
+            hir::ExprKind::Cast(ref operand, _) => operand,
+            _ => return,
+        };
+        let (from, to) = (cx.tables.expr_ty(operand), cx.tables.expr_ty(e));
+        let is_pointer_sized = |ty: Ty<'_>| match ty.kind {
+            ty::Int(ast::IntTy::Isize) | ty::Uint(ast::UintTy::Usize) => true,
+            _ => false,
+        };
+        if !is_pointer_sized(from) && !is_pointer_sized(to) {
+            return;
+        }
+
+        let widths = &cx.sess().opts.debugging_opts.portable_pointer_widths;
+        let mut lossy_widths = vec![];
+        let mut lossless_widths = vec![];
+        for &width in widths {
+            let from_repr = PointerWidthCasts::int_repr(from, width);
+            let to_repr = PointerWidthCasts::int_repr(to, width);
+            let (from_repr, to_repr) = match (from_repr, to_repr) {
+                (Some(from_repr), Some(to_repr)) => (from_repr, to_repr),
+                _ => return,
+            };
+            if PointerWidthCasts::is_lossless(from_repr, to_repr) {
+                lossless_widths.push(width);
+            } else {
+                lossy_widths.push(width);
+            }
+        }
+        // Casts that truncate everywhere are no different from other narrowing casts.
+        if lossy_widths.is_empty() || lossless_widths.is_empty() {
+            return;
+        }
+
+        let describe = |widths: &[u64]| {
+            widths.iter().map(|width| format!("{}-bit", width)).collect::<Vec<_>>().join(", ")
+        };
+        let msg = format!(
+            "cast from `{}` to `{}` truncates on targets with {} pointers",
+            from,
+            to,
+            describe(&lossy_widths)
+        );
+        cx.struct_span_lint(POINTER_WIDTH_CASTS, e.span, &msg)
+            .note(&format!(
+                "the cast is lossless with {} pointers, so the truncation goes unnoticed until \
+                 the crate is built for the other widths",
+                describe(&lossless_widths)
+            ))
+            .help(&format!(
+                "use `{}::try_from` to handle the values that don't fit, or handle each width \
+                 with `#[cfg(target_pointer_width = \"{}\")]`",
+                to, lossy_widths[0]
+            ))
+            .emit();
+    }
+}
+
+declare_lint! {
+    pub INVALID_ATOMIC_ORDERING,
+    Warn,
+    "detects atomic operations and fences with an ordering they panic on"
+}
+
+declare_lint_pass!(
+    /// Checks for atomic loads, stores, compare-exchanges and fences whose `Ordering` argument
+    /// makes them panic at runtime.
+    InvalidAtomicOrdering => [INVALID_ATOMIC_ORDERING]
+);
+
+impl InvalidAtomicOrdering {
+    /// The name of the `core::sync::atomic::Ordering` variant that `expr` refers to, if any.
+    fn ordering(cx: &LateContext<'_, '_>, expr: &hir::Expr<'_>) -> Option<Symbol> {
+        let qpath = match expr.kind {
+            ExprKind::Path(ref qpath) => qpath,
+            _ => return None,
+        };
+        let ctor_id = match cx.tables.qpath_res(qpath, expr.hir_id) {
+            Res::Def(DefKind::Ctor(..), ctor_id) => ctor_id,
+            _ => return None,
+        };
+        let variant_id = cx.tcx.parent(ctor_id)?;
+        let enum_id = cx.tcx.parent(variant_id)?;
+        if cx.match_def_path(enum_id, &[sym::core, sym::sync, sym::atomic, sym::Ordering]) {
+            Some(cx.tcx.item_name(variant_id))
+        } else {
+            None
+        }
+    }
+
+    /// The name of the method if `expr` calls a method of one of the atomic types.
+    fn atomic_method(cx: &LateContext<'_, '_>, expr: &hir::Expr<'_>) -> Option<Symbol> {
+        let method_id = cx.tables.type_dependent_def_id(expr.hir_id)?;
+        let impl_id = cx.tcx.impl_of_method(method_id)?;
+        let adt_def = cx.tcx.type_of(impl_id).ty_adt_def()?;
+        match &cx.get_def_path(adt_def.did)[..] {
+            [krate, sync, atomic, _]
+                if *krate == sym::core && *sync == sym::sync && *atomic == sym::atomic =>
+            {
+                Some(cx.tcx.item_name(method_id))
+            }
+            _ => None,
+        }
+    }
+
+    fn lint(cx: &LateContext<'_, '_>, span: Span, msg: &str, help: &str) {
+        cx.struct_span_lint(INVALID_ATOMIC_ORDERING, span, msg).help(help).emit();
+    }
+
+    fn check_load_store(cx: &LateContext<'_, '_>, method: Symbol, args: &[hir::Expr<'_>]) {
+        let (order_arg, invalid, msg, help) = match method {
+            sym::load if args.len() == 2 => (
+                &args[1],
+                [sym::Release, sym::AcqRel],
+                "atomic loads cannot have `Release` or `AcqRel` ordering",
+                "consider using ordering modes `Acquire`, `SeqCst` or `Relaxed`",
+            ),
+            sym::store if args.len() == 3 => (
+                &args[2],
+                [sym::Acquire, sym::AcqRel],
+                "atomic stores cannot have `Acquire` or `AcqRel` ordering",
+                "consider using ordering modes `Release`, `SeqCst` or `Relaxed`",
+            ),
+            _ => return,
+        };
+        if let Some(ordering) = InvalidAtomicOrdering::ordering(cx, order_arg) {
+            if invalid.contains(&ordering) {
+                InvalidAtomicOrdering::lint(cx, order_arg.span, msg, help);
+            }
+        }
+    }
+
+    fn check_compare_exchange(cx: &LateContext<'_, '_>, method: Symbol, args: &[hir::Expr<'_>]) {
+        let (success_arg, failure_arg) = match method {
+            sym::compare_exchange | sym::compare_exchange_weak if args.len() == 5 => {
+                (&args[3], &args[4])
+            }
+            sym::fetch_update if args.len() == 4 => (&args[3], &args[2]),
+            _ => return,
+        };
+        let failure = match InvalidAtomicOrdering::ordering(cx, failure_arg) {
+            Some(failure) => failure,
+            None => return,
+        };
+
+        if failure == sym::Release || failure == sym::AcqRel {
+            let msg = format!("`{}`'s failure ordering may not be `Release` or `AcqRel`", method);
+            let help = "consider using `Acquire` or `Relaxed` failure ordering instead";
+            InvalidAtomicOrdering::lint(cx, failure_arg.span, &msg, help);
+            return;
+        }
+
+        let success = match InvalidAtomicOrdering::ordering(cx, success_arg) {
+            Some(success) => success,
+            None => return,
+        };
+        let (allowed, help): (&[Symbol], _) = match success {
+            sym::Relaxed | sym::Release => (&[sym::Relaxed], "`Relaxed`"),
+            sym::Acquire | sym::AcqRel => (&[sym::Relaxed, sym::Acquire], "`Acquire` or `Relaxed`"),
+            _ => return,
+        };
+        if !allowed.contains(&failure) {
+            let msg = format!(
+                "`{}`'s failure ordering may not be stronger than the success ordering of `{}`",
+                method, success
+            );
+            let help = format!("consider using {} failure ordering instead", help);
+            InvalidAtomicOrdering::lint(cx, failure_arg.span, &msg, &help);
+        }
+    }
+
+    fn check_fence(cx: &LateContext<'_, '_>, callee: &hir::Expr<'_>, args: &[hir::Expr<'_>]) {
+        let def_id = match callee.kind {
+            ExprKind::Path(ref qpath) => match cx.tables.qpath_res(qpath, callee.hir_id) {
+                Res::Def(DefKind::Fn, def_id) => def_id,
+                _ => return,
+            },
+            _ => return,
+        };
+        let is_fence = cx.match_def_path(def_id, &[sym::core, sym::sync, sym::atomic, sym::fence])
+            || cx.match_def_path(def_id, &[sym::core, sym::sync, sym::atomic, sym::compiler_fence]);
+        if !is_fence || args.len() != 1 {
+            return;
+        }
+        if InvalidAtomicOrdering::ordering(cx, &args[0]) == Some(sym::Relaxed) {
+            InvalidAtomicOrdering::lint(
+                cx,
+                args[0].span,
+                "memory fences cannot have `Relaxed` ordering",
+                "consider using ordering modes `Acquire`, `Release`, `AcqRel` or `SeqCst`",
+            );
+        }
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for InvalidAtomicOrdering {
+    fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, expr: &'tcx hir::Expr<'tcx>) {
+        match expr.kind {
+            ExprKind::MethodCall(_, _, args) => {
+                if let Some(method) = InvalidAtomicOrdering::atomic_method(cx, expr) {
+                    InvalidAtomicOrdering::check_load_store(cx, method, args);
+                    InvalidAtomicOrdering::check_compare_exchange(cx, method, args);
+                }
+            }
+            ExprKind::Call(callee, args) => InvalidAtomicOrdering::check_fence(cx, callee, args),
+            _ => {}
+        }
+    }
+}
+
+declare_lint! {
+    pub IMPLICIT_STATIC_TRAIT_OBJECTS,
+    Allow,
+    "detects trait objects in public signatures whose lifetime bound defaults to `'static`"
+}
+
+declare_lint_pass!(
+    /// Checks public fields and return types for trait objects like `Box<dyn Trait>` without
+    /// a lifetime bound, which exclude implementors that borrow data without saying so.
+    ImplicitStaticTraitObjects => [IMPLICIT_STATIC_TRAIT_OBJECTS]
+);
+
+/// Collects the trait objects of a type whose omitted lifetime bound resolved to `'static`,
+/// and whether each of them is directly behind a reference.
+struct StaticTraitObjects<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    objects: Vec<(Span, bool)>,
+}
+
+impl StaticTraitObjects<'_> {
+    fn is_implicit_static(&self, ty: &hir::Ty<'_>) -> bool {
+        match ty.kind {
+            hir::TyKind::TraitObject(_, ref lifetime) => {
+                lifetime.name == hir::LifetimeName::ImplicitObjectLifetimeDefault
+                    && self.tcx.named_region(lifetime.hir_id) == Some(rl::Region::Static)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for StaticTraitObjects<'tcx> {
+    type Map = Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> intravisit::NestedVisitorMap<'_, Self::Map> {
+        intravisit::NestedVisitorMap::None
+    }
+
+    fn visit_ty(&mut self, ty: &'tcx hir::Ty<'tcx>) {
+        match ty.kind {
+            hir::TyKind::Rptr(_, ref mut_ty) if self.is_implicit_static(&mut_ty.ty) => {
+                self.objects.push((mut_ty.ty.span, true));
+                // Skip the object itself, but not the types in its bounds.
+                intravisit::walk_ty(self, &mut_ty.ty);
+                return;
+            }
+            _ if self.is_implicit_static(ty) => self.objects.push((ty.span, false)),
+            _ => {}
+        }
+        intravisit::walk_ty(self, ty);
+    }
+}
+
+impl ImplicitStaticTraitObjects {
+    fn check_ty<'tcx>(cx: &LateContext<'_, 'tcx>, ty: &'tcx hir::Ty<'tcx>, descr: &str) {
+        let mut visitor = StaticTraitObjects { tcx: cx.tcx, objects: vec![] };
+        visitor.visit_ty(ty);
+        for (span, behind_ref) in visitor.objects {
+            let snippet = match cx.sess().source_map().span_to_snippet(span) {
+                Ok(snippet) => snippet,
+                Err(_) => continue,
+            };
+            let explicit = if behind_ref {
+                format!("({} + 'static)", snippet)
+            } else {
+                format!("{} + 'static", snippet)
+            };
+            let msg = format!("trait object in {} has an implicit `'static` bound", descr);
+            cx.struct_span_lint(IMPLICIT_STATIC_TRAIT_OBJECTS, span, &msg)
+                .note("implementors of the trait that borrow data cannot be used here")
+                .span_suggestion(
+                    span,
+                    "if this is intended, make the bound explicit",
+                    explicit,
+                    Applicability::MachineApplicable,
+                )
+                .emit();
+        }
+    }
+
+    fn check_fn_decl<'tcx>(cx: &LateContext<'_, 'tcx>, decl: &'tcx hir::FnDecl<'tcx>) {
+        if let hir::FunctionRetTy::Return(ref ty) = decl.output {
+            ImplicitStaticTraitObjects::check_ty(cx, ty, "a public return type");
+        }
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for ImplicitStaticTraitObjects {
+    fn check_item(&mut self, cx: &LateContext<'a, 'tcx>, it: &'tcx hir::Item<'tcx>) {
+        if let hir::ItemKind::Fn(ref sig, ..) = it.kind {
+            if cx.access_levels.is_exported(it.hir_id) {
+                ImplicitStaticTraitObjects::check_fn_decl(cx, &sig.decl);
+            }
+        }
+    }
+
+    fn check_trait_item(&mut self, cx: &LateContext<'a, 'tcx>, it: &'tcx hir::TraitItem<'tcx>) {
+        if let hir::TraitItemKind::Method(ref sig, _) = it.kind {
+            if cx.access_levels.is_exported(it.hir_id) {
+                ImplicitStaticTraitObjects::check_fn_decl(cx, &sig.decl);
+            }
+        }
+    }
+
+    fn check_impl_item(&mut self, cx: &LateContext<'a, 'tcx>, it: &'tcx hir::ImplItem<'tcx>) {
+        // Trait impls have to follow the signature of the trait.
+        if let hir::ImplItemKind::Method(ref sig, _) = it.kind {
+            if cx.access_levels.is_exported(it.hir_id)
+                && method_context(cx, it.hir_id) != MethodLateContext::TraitImpl
+            {
+                ImplicitStaticTraitObjects::check_fn_decl(cx, &sig.decl);
+            }
+        }
+    }
+
+    fn check_struct_field(&mut self, cx: &LateContext<'a, 'tcx>, sf: &'tcx hir::StructField<'tcx>) {
+        if sf.vis.node.is_pub() && cx.access_levels.is_exported(sf.hir_id) {
+            ImplicitStaticTraitObjects::check_ty(cx, &sf.ty, "a public field");
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use rustc::lint::builtin::UNUSED_ATTRIBUTES;
-use rustc::lint::{EarlyContext, EarlyLintPass, LateContext, LateLintPass, LintContext};
+use rustc::lint::{in_external_macro, LintContext};
+use rustc::lint::{EarlyContext, EarlyLintPass, LateContext, LateLintPass, Lint, LintId};
+use rustc::traits::{self, supertrait_def_ids};
 use rustc::ty::adjustment;
 use rustc::ty::{self, Ty};
 use rustc_data_structures::fx::FxHashMap;
@@ -10,12 +12,14 @@ use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::DefId;
 use rustc_span::symbol::Symbol;
 use rustc_span::symbol::{kw, sym};
-use rustc_span::{BytePos, Span};
+use rustc_span::{BytePos, Span, DUMMY_SP};
 use syntax::ast;
 use syntax::attr;
 use syntax::print::pprust;
 use syntax::util::parser;
 
+use crate::async_await::in_async_body;
+
 use log::debug;
 
 declare_lint! {
@@ -31,12 +35,38 @@ declare_lint! {
     "unused result of an expression in a statement"
 }
 
-declare_lint_pass!(UnusedResults => [UNUSED_MUST_USE, UNUSED_RESULTS]);
+declare_lint! {
+    pub UNUSED_FUTURES,
+    Warn,
+    "futures that are created but never awaited or polled"
+}
+
+declare_lint! {
+    pub LET_UNDERSCORE_MUST_USE,
+    Allow,
+    "`#[must_use]` values discarded with `let _ =`"
+}
+
+declare_lint_pass!(
+    UnusedResults => [UNUSED_MUST_USE, UNUSED_RESULTS, UNUSED_FUTURES, LET_UNDERSCORE_MUST_USE]
+);
 
 impl<'a, 'tcx> LateLintPass<'a, 'tcx> for UnusedResults {
     fn check_stmt(&mut self, cx: &LateContext<'_, '_>, s: &hir::Stmt<'_>) {
         let expr = match s.kind {
             hir::StmtKind::Semi(ref expr) => &**expr,
+            hir::StmtKind::Local(ref local) => {
+                // Unlike the result of a call, reading a constant or static has no side effect
+                // that `let _ =` could be keeping.
+                if let (hir::PatKind::Wild, Some(init)) = (&local.pat.kind, &local.init) {
+                    if let Some(def_id) = value_def_id(cx, init) {
+                        check_must_use_def(cx, UNUSED_MUST_USE, def_id, s.span, "value of ", "");
+                    } else if !in_external_macro(cx.sess(), s.span) {
+                        check_let_underscore(cx, init, s.span);
+                    }
+                }
+                return;
+            }
             _ => return,
         };
 
@@ -44,32 +74,35 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for UnusedResults {
             return;
         }
 
+        // For statements produced by a macro, point at the user's expression
+        // when it was passed in from outside (e.g. `$e;`), and skip values
+        // that an external macro creates and discards on its own.
+        let span = if !s.span.from_expansion() {
+            s.span
+        } else if !expr.span.from_expansion() {
+            expr.span
+        } else if in_external_macro(cx.sess(), s.span) {
+            return;
+        } else {
+            s.span
+        };
+
         let ty = cx.tables.expr_ty(&expr);
-        let type_permits_lack_of_use = check_must_use_ty(cx, ty, &expr, s.span, "", "", 1);
+        if is_future(cx, ty) {
+            check_unawaited_future(cx, expr, span);
+            return;
+        }
+
+        let type_permits_lack_of_use =
+            check_must_use_ty(cx, UNUSED_MUST_USE, ty, &expr, span, "", "", 1);
 
         let mut fn_warned = false;
         let mut op_warned = false;
-        let maybe_def_id = match expr.kind {
-            hir::ExprKind::Call(ref callee, _) => {
-                match callee.kind {
-                    hir::ExprKind::Path(ref qpath) => {
-                        match cx.tables.qpath_res(qpath, callee.hir_id) {
-                            Res::Def(DefKind::Fn, def_id) | Res::Def(DefKind::Method, def_id) => {
-                                Some(def_id)
-                            }
-                            // `Res::Local` if it was a closure, for which we
-                            // do not currently support must-use linting
-                            _ => None,
-                        }
-                    }
-                    _ => None,
-                }
-            }
-            hir::ExprKind::MethodCall(..) => cx.tables.type_dependent_def_id(expr.hir_id),
-            _ => None,
-        };
-        if let Some(def_id) = maybe_def_id {
-            fn_warned = check_must_use_def(cx, def_id, s.span, "return value of ", "");
+        if let Some(def_id) = callee_def_id(cx, expr) {
+            fn_warned =
+                check_must_use_def(cx, UNUSED_MUST_USE, def_id, span, "return value of ", "");
+        } else if let Some(def_id) = value_def_id(cx, expr) {
+            fn_warned = check_must_use_def(cx, UNUSED_MUST_USE, def_id, span, "value of ", "");
         } else if type_permits_lack_of_use {
             // We don't warn about unused unit or uninhabited types.
             // (See https://github.com/rust-lang/rust/issues/43806 for details.)
@@ -113,12 +146,64 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for UnusedResults {
         }
 
         if !(type_permits_lack_of_use || fn_warned || op_warned) {
-            cx.span_lint(UNUSED_RESULTS, s.span, "unused result");
+            cx.span_lint(UNUSED_RESULTS, span, "unused result");
+        }
+
+        // The function or method `expr` calls, if it is a call.
+        fn callee_def_id(cx: &LateContext<'_, '_>, expr: &hir::Expr<'_>) -> Option<DefId> {
+            match expr.kind {
+                hir::ExprKind::Call(ref callee, _) => {
+                    match callee.kind {
+                        hir::ExprKind::Path(ref qpath) => {
+                            match cx.tables.qpath_res(qpath, callee.hir_id) {
+                                Res::Def(DefKind::Fn, def_id)
+                                | Res::Def(DefKind::Method, def_id) => Some(def_id),
+                                // `Res::Local` if it was a closure, for which we
+                                // do not currently support must-use linting
+                                _ => None,
+                            }
+                        }
+                        _ => None,
+                    }
+                }
+                hir::ExprKind::MethodCall(..) => cx.tables.type_dependent_def_id(expr.hir_id),
+                _ => None,
+            }
+        }
+
+        // `let _ = init;` drops `init` right away, which `unused_must_use` accepts as a way to
+        // ignore it on purpose and `let_underscore_must_use` doesn't. A place expression isn't
+        // moved out of, let alone dropped, so it is left alone.
+        fn check_let_underscore(cx: &LateContext<'_, '_>, init: &hir::Expr<'_>, span: Span) {
+            if init.is_syntactic_place_expr() {
+                return;
+            }
+            let lint = LET_UNDERSCORE_MUST_USE;
+            let ty = cx.tables.expr_ty(init);
+            if !check_must_use_ty(cx, lint, ty, init, span, "", "", 1) {
+                if let Some(def_id) = callee_def_id(cx, init) {
+                    check_must_use_def(cx, lint, def_id, span, "return value of ", "");
+                }
+            }
+        }
+
+        // The constant or static, possibly associated, that `expr` is a path to.
+        fn value_def_id(cx: &LateContext<'_, '_>, expr: &hir::Expr<'_>) -> Option<DefId> {
+            match expr.kind {
+                hir::ExprKind::Path(ref qpath) => match cx.tables.qpath_res(qpath, expr.hir_id) {
+                    Res::Def(DefKind::Const, def_id)
+                    | Res::Def(DefKind::AssocConst, def_id)
+                    | Res::Def(DefKind::Static, def_id) => Some(def_id),
+                    _ => None,
+                },
+                _ => None,
+            }
         }
 
         // Returns whether an error has been emitted (and thus another does not need to be later).
         fn check_must_use_ty<'tcx>(
             cx: &LateContext<'_, 'tcx>,
+            lint: &'static Lint,
             ty: Ty<'tcx>,
             expr: &hir::Expr<'_>,
             span: Span,
@@ -138,9 +223,13 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for UnusedResults {
                 ty::Adt(..) if ty.is_box() => {
                     let boxed_ty = ty.boxed_ty();
                     let descr_pre = &format!("{}boxed ", descr_pre);
-                    check_must_use_ty(cx, boxed_ty, expr, span, descr_pre, descr_post, plural_len)
+                    check_must_use_ty(
+                        cx, lint, boxed_ty, expr, span, descr_pre, descr_post, plural_len,
+                    )
+                }
+                ty::Adt(def, _) => {
+                    check_must_use_def(cx, lint, def.did, span, descr_pre, descr_post)
                 }
-                ty::Adt(def, _) => check_must_use_def(cx, def.did, span, descr_pre, descr_post),
                 ty::Opaque(def, _) => {
                     let mut has_emitted = false;
                     for (predicate, _) in cx.tcx.predicates_of(def).predicates {
@@ -149,7 +238,7 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for UnusedResults {
                             let def_id = trait_ref.def_id;
                             let descr_pre =
                                 &format!("{}implementer{} of ", descr_pre, plural_suffix,);
-                            if check_must_use_def(cx, def_id, span, descr_pre, descr_post) {
+                            if check_must_use_trait(cx, lint, def_id, span, descr_pre, descr_post) {
                                 has_emitted = true;
                                 break;
                             }
@@ -164,7 +253,7 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for UnusedResults {
                             let def_id = trait_ref.def_id;
                             let descr_post =
                                 &format!(" trait object{}{}", plural_suffix, descr_post,);
-                            if check_must_use_def(cx, def_id, span, descr_pre, descr_post) {
+                            if check_must_use_trait(cx, lint, def_id, span, descr_pre, descr_post) {
                                 has_emitted = true;
                                 break;
                             }
@@ -183,8 +272,9 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for UnusedResults {
                     for (i, ty) in tys.iter().map(|k| k.expect_ty()).enumerate() {
                         let descr_post = &format!(" in tuple element {}", i);
                         let span = *spans.get(i).unwrap_or(&span);
-                        if check_must_use_ty(cx, ty, expr, span, descr_pre, descr_post, plural_len)
-                        {
+                        if check_must_use_ty(
+                            cx, lint, ty, expr, span, descr_pre, descr_post, plural_len,
+                        ) {
                             has_emitted = true;
                         }
                     }
@@ -194,7 +284,10 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for UnusedResults {
                     // If the array is definitely non-empty, we can do `#[must_use]` checking.
                     Some(n) if n != 0 => {
                         let descr_pre = &format!("{}array{} of ", descr_pre, plural_suffix,);
-                        check_must_use_ty(cx, ty, expr, span, descr_pre, descr_post, n as usize + 1)
+                        let plural_len = n as usize + 1;
+                        check_must_use_ty(
+                            cx, lint, ty, expr, span, descr_pre, descr_post, plural_len,
+                        )
                     }
                     // Otherwise, we don't lint, to avoid false positives.
                     _ => false,
@@ -203,9 +296,68 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for UnusedResults {
             }
         }
 
+        fn is_future<'tcx>(cx: &LateContext<'_, 'tcx>, ty: Ty<'tcx>) -> bool {
+            let future_trait = match cx.tcx.lang_items().future_trait() {
+                Some(def_id) => def_id,
+                None => return false,
+            };
+            cx.tcx.infer_ctxt().enter(|infcx| {
+                traits::type_known_to_meet_bound_modulo_regions(
+                    &infcx,
+                    cx.param_env,
+                    ty,
+                    future_trait,
+                    DUMMY_SP,
+                )
+            })
+        }
+
+        fn check_unawaited_future(cx: &LateContext<'_, '_>, expr: &hir::Expr<'_>, span: Span) {
+            let span = if expr.span.from_expansion() { span } else { expr.span };
+            let mut err =
+                cx.struct_span_lint(UNUSED_FUTURES, span, "future is created but never awaited");
+            if !in_async_body(cx, expr.hir_id) {
+                err.note(
+                    "futures do nothing unless you `.await` or poll them; \
+                     to run this one in the background, pass it to an executor's `spawn` function",
+                );
+            } else if !expr.span.from_expansion() {
+                if let Ok(snippet) = cx.sess().source_map().span_to_snippet(expr.span) {
+                    let sugg = if expr.precedence().order() < parser::PREC_POSTFIX {
+                        format!("({}).await", snippet)
+                    } else {
+                        format!("{}.await", snippet)
+                    };
+                    err.span_suggestion(
+                        expr.span,
+                        "consider `.await`ing the future",
+                        sugg,
+                        Applicability::MachineApplicable,
+                    );
+                }
+            }
+            err.emit();
+        }
+
+        // Like `check_must_use_def`, but also considers the supertraits of `trait_def_id`,
+        // so that e.g. `impl DoubleEndedIterator` is as must-use as `impl Iterator`.
+        fn check_must_use_trait(
+            cx: &LateContext<'_, '_>,
+            lint: &'static Lint,
+            trait_def_id: DefId,
+            span: Span,
+            descr_pre_path: &str,
+            descr_post_path: &str,
+        ) -> bool {
+            supertrait_def_ids(cx.tcx, trait_def_id).any(|def_id| {
+                check_must_use_def(cx, lint, def_id, span, descr_pre_path, descr_post_path)
+            })
+        }
+
         // Returns whether an error has been emitted (and thus another does not need to be later).
         fn check_must_use_def(
             cx: &LateContext<'_, '_>,
+            lint: &'static Lint,
             def_id: DefId,
             span: Span,
             descr_pre_path: &str,
@@ -219,11 +371,18 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for UnusedResults {
                         cx.tcx.def_path_str(def_id),
                         descr_post_path
                     );
-                    let mut err = cx.struct_span_lint(UNUSED_MUST_USE, span, &msg);
+                    let mut err = cx.struct_span_lint(lint, span, &msg);
                     // check for #[must_use = "..."]
                     if let Some(note) = attr.value_str() {
                         err.note(&note.as_str());
                     }
+                    if LintId::of(lint) == LintId::of(LET_UNDERSCORE_MUST_USE) {
+                        err.note("`let _ =` drops the value right away without using it");
+                        err.help(
+                            "bind it to a named variable, or pass it to `drop` with a comment \
+                             saying why it can be ignored",
+                        );
+                    }
                     err.emit();
                     return true;
                 }
@@ -590,11 +749,11 @@ declare_lint! {
 declare_lint_pass!(UnusedImportBraces => [UNUSED_IMPORT_BRACES]);
 
 impl UnusedImportBraces {
-    fn check_use_tree(&self, cx: &EarlyContext<'_>, use_tree: &ast::UseTree, item: &ast::Item) {
+    fn check_braces(&self, cx: &EarlyContext<'_>, use_tree: &ast::UseTree, item: &ast::Item) {
         if let ast::UseTreeKind::Nested(ref items) = use_tree.kind {
             // Recursively check nested UseTrees
             for &(ref tree, _) in items {
-                self.check_use_tree(cx, tree, item);
+                self.check_braces(cx, tree, item);
             }
 
             // Trigger the lint only if there is one nested item
@@ -624,7 +783,7 @@ impl UnusedImportBraces {
 impl EarlyLintPass for UnusedImportBraces {
     fn check_item(&mut self, cx: &EarlyContext<'_>, item: &ast::Item) {
         if let ast::ItemKind::Use(ref use_tree) = item.kind {
-            self.check_use_tree(cx, use_tree, item);
+            self.check_braces(cx, use_tree, item);
         }
     }
 }
@@ -637,14 +796,87 @@ declare_lint! {
 
 declare_lint_pass!(UnusedAllocation => [UNUSED_ALLOCATION]);
 
+impl UnusedAllocation {
+    /// The boxed value if `e` is a `Box::new(value)` call.
+    fn box_new_arg<'hir>(
+        cx: &LateContext<'_, '_>,
+        e: &'hir hir::Expr<'hir>,
+    ) -> Option<&'hir hir::Expr<'hir>> {
+        if let hir::ExprKind::Call(ref callee, ref args) = e.kind {
+            if let hir::ExprKind::Path(ref qpath) = callee.kind {
+                if let Res::Def(DefKind::Method, def_id) = cx.tables.qpath_res(qpath, callee.hir_id)
+                {
+                    if cx.tcx.is_diagnostic_item(sym::box_new, def_id) && args.len() == 1 {
+                        return Some(&args[0]);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Suggests replacing `Box::new(value)` with `value`, which itself gets borrowed with the
+    /// given prefix.
+    fn lint_box_new(
+        cx: &LateContext<'_, '_>,
+        span: Span,
+        arg: &hir::Expr<'_>,
+        prefix: &str,
+        min_precedence: i8,
+        msg: &str,
+    ) {
+        let mut err = cx.struct_span_lint(UNUSED_ALLOCATION, span, msg);
+        if let Ok(snippet) = cx.sess().source_map().span_to_snippet(arg.span) {
+            let snippet = if arg.precedence().order() < min_precedence {
+                format!("({})", snippet)
+            } else {
+                snippet
+            };
+            err.span_suggestion(
+                span,
+                "remove the `Box::new`",
+                format!("{}{}", prefix, snippet),
+                Applicability::MachineApplicable,
+            );
+        }
+        err.emit();
+    }
+}
+
 impl<'a, 'tcx> LateLintPass<'a, 'tcx> for UnusedAllocation {
     fn check_expr(&mut self, cx: &LateContext<'_, '_>, e: &hir::Expr<'_>) {
+        // `&*Box::new(value)` and `&mut *Box::new(value)`.
+        if let hir::ExprKind::AddrOf(_, m, ref inner) = e.kind {
+            if let hir::ExprKind::Unary(hir::UnOp::UnDeref, ref boxed) = inner.kind {
+                if let Some(arg) = UnusedAllocation::box_new_arg(cx, boxed) {
+                    let (prefix, msg) = match m {
+                        hir::Mutability::Not => ("&", "unnecessary allocation, use `&` instead"),
+                        hir::Mutability::Mut => {
+                            ("&mut ", "unnecessary allocation, use `&mut` instead")
+                        }
+                    };
+                    UnusedAllocation::lint_box_new(
+                        cx,
+                        e.span,
+                        arg,
+                        prefix,
+                        parser::PREC_PREFIX,
+                        msg,
+                    );
+                }
+            }
+            return;
+        }
+
+        let box_new_arg = UnusedAllocation::box_new_arg(cx, e);
         match e.kind {
             hir::ExprKind::Box(_) => {}
+            _ if box_new_arg.is_some() => {}
             _ => return,
         }
 
-        for adj in cx.tables.expr_adjustments(e) {
+        let adjustments = cx.tables.expr_adjustments(e);
+        for (i, adj) in adjustments.iter().enumerate() {
             if let adjustment::Adjust::Borrow(adjustment::AutoBorrow::Ref(_, m)) = adj.kind {
                 let msg = match m {
                     adjustment::AutoBorrowMutability::Not => {
@@ -654,7 +886,24 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for UnusedAllocation {
                         "unnecessary allocation, use `&mut` instead"
                     }
                 };
-                cx.span_lint(UNUSED_ALLOCATION, e.span, msg);
+                match box_new_arg {
+                    // Only the box contents are borrowed, e.g. as the receiver of one of their
+                    // methods, so the value can be used directly.
+                    Some(arg) if i > 0 => {
+                        if let adjustment::Adjust::Deref(None) = adjustments[i - 1].kind {
+                            UnusedAllocation::lint_box_new(
+                                cx,
+                                e.span,
+                                arg,
+                                "",
+                                parser::PREC_POSTFIX,
+                                msg,
+                            );
+                        }
+                    }
+                    Some(_) => {}
+                    None => cx.span_lint(UNUSED_ALLOCATION, e.span, msg),
+                }
             }
         }
     }
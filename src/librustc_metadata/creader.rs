@@ -308,14 +308,24 @@ impl<'a> CrateLoader<'a> {
         let host_hash = host_lib.as_ref().map(|lib| lib.metadata.get_root().hash());
         self.verify_no_symbol_conflicts(span, &crate_root);
 
+        let private_deps = &self.sess.opts.debugging_opts.private_dependencies;
         let private_dep =
-            self.sess.opts.externs.get(&name.as_str()).map(|e| e.is_private_dep).unwrap_or(false);
+            self.sess.opts.externs.get(&name.as_str()).map(|e| e.is_private_dep).unwrap_or(false)
+                || private_deps.iter().any(|dep| *dep == *name.as_str());
 
         info!("register crate `{}` (private_dep = {})", crate_root.name(), private_dep);
 
         // Claim this crate number and cache it
         let cnum = self.cstore.alloc_new_crate_num();
 
+        let linted_macro_crates = &self.sess.opts.debugging_opts.lint_macros_from;
+        if linted_macro_crates.iter().any(|krate| *krate == *name.as_str()) {
+            self.sess.linted_macro_crates.lock().insert(cnum.as_u32());
+        }
+        if self.sess.opts.extern_lint_levels.iter().any(|(krate, ..)| *krate == *name.as_str()) {
+            self.sess.extern_lint_level_crates.lock().insert(cnum.as_u32(), name);
+        }
+
         // Maintain a reference to the top most crate.
         // Stash paths for top-most crate locally if necessary.
         let crate_paths;
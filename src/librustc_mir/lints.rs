@@ -1,20 +1,127 @@
 use rustc::hir::map::blocks::FnLikeNode;
-use rustc::lint::builtin::UNCONDITIONAL_RECURSION;
+use rustc::lint::builtin::{FFI_UNWIND, UNCONDITIONAL_RECURSION};
+use rustc::mir::interpret::PanicInfo;
 use rustc::mir::{self, Body, TerminatorKind};
 use rustc::ty::subst::InternalSubsts;
 use rustc::ty::{self, AssocItem, AssocItemContainer, Instance, TyCtxt};
 use rustc_hir::def_id::DefId;
 use rustc_hir::intravisit::FnKind;
 use rustc_index::bit_set::BitSet;
+use rustc_span::symbol::sym;
+use rustc_target::spec::abi::Abi;
+use rustc_target::spec::PanicStrategy;
+use syntax::attr;
 
 pub fn check(tcx: TyCtxt<'tcx>, body: &Body<'tcx>, def_id: DefId) {
     let hir_id = tcx.hir().as_local_hir_id(def_id).unwrap();
 
     if let Some(fn_like_node) = FnLikeNode::from_node(tcx.hir().get(hir_id)) {
         check_fn_for_unconditional_recursion(tcx, fn_like_node.kind(), body, def_id);
+        check_fn_for_ffi_unwind(tcx, fn_like_node.kind(), body, def_id);
     }
 }
 
+fn check_fn_for_ffi_unwind(
+    tcx: TyCtxt<'tcx>,
+    fn_kind: FnKind<'_>,
+    body: &Body<'tcx>,
+    def_id: DefId,
+) {
+    let abi = match fn_kind {
+        FnKind::ItemFn(_, _, header, _, _) => header.abi,
+        FnKind::Method(_, sig, _, _) => sig.header.abi,
+        // closures always use the Rust ABI.
+        FnKind::Closure(_) => return,
+    };
+
+    match abi {
+        Abi::Rust | Abi::RustCall | Abi::RustIntrinsic | Abi::PlatformIntrinsic => return,
+        _ => {}
+    }
+
+    // Nothing can unwind out of the function if panics abort.
+    if tcx.sess.panic_strategy() != PanicStrategy::Unwind {
+        return;
+    }
+
+    // `#[unwind(allowed)]` opts into unwinding, `#[unwind(aborts)]` already
+    // guards the boundary with an abort.
+    if attr::find_unwind_attr(None, fn_kind.attrs()).is_some() {
+        return;
+    }
+
+    // Look for the places where a panic starts in this body: calls of the
+    // functions that `panic!` and `unreachable!` expand to, and the assertions
+    // MIR building inserts for bounds checks and division. Overflow assertions
+    // are skipped since they only exist when overflow checks are enabled.
+    // Other diverging calls, like `loop {}` in a helper or `process::exit`,
+    // don't unwind.
+    //
+    // FIXME: a converging call into a Rust function can also unwind, but
+    // flagging every call would make the lint useless in practice.
+    let mut panic_locations = vec![];
+    for block in body.basic_blocks() {
+        if block.is_cleanup {
+            continue;
+        }
+
+        if let Some(ref terminator) = block.terminator {
+            match terminator.kind {
+                TerminatorKind::Call { ref func, destination: None, cleanup: Some(_), .. } => {
+                    if let ty::FnDef(callee, _) = func.ty(body, tcx).kind {
+                        if is_panic_fn(tcx, callee) {
+                            // Point at the `panic!` rather than into the std macro.
+                            panic_locations.push(terminator.source_info.span.source_callsite());
+                        }
+                    }
+                }
+                TerminatorKind::Assert { ref msg, cleanup: Some(_), .. } => match msg {
+                    PanicInfo::Overflow(_) | PanicInfo::OverflowNeg => {}
+                    _ => panic_locations.push(terminator.source_info.span),
+                },
+                _ => {}
+            }
+        }
+    }
+
+    if panic_locations.is_empty() {
+        return;
+    }
+
+    let hir_id = tcx.hir().as_local_hir_id(def_id).unwrap();
+    let sp = tcx.sess.source_map().def_span(tcx.hir().span(hir_id));
+    let mut db = tcx.struct_span_lint_hir(
+        FFI_UNWIND,
+        hir_id,
+        sp,
+        &format!("function with the {} ABI may unwind", abi),
+    );
+    for span in panic_locations {
+        db.span_label(span, "this may panic");
+    }
+    db.note("unwinding out of a function with a non-Rust ABI is undefined behavior");
+    db.help(
+        "catch the panic with `std::panic::catch_unwind`, or mark the function \
+         `#[unwind(allowed)]` if unwinding into the caller is intended",
+    );
+    db.emit();
+}
+
+/// Whether `def_id` is one of the functions starting a panic: the panic lang items, and the
+/// `panic_fmt` functions that `panic!` calls with a formatted message.
+fn is_panic_fn(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    let lang_items = tcx.lang_items();
+    if [lang_items.panic_fn(), lang_items.panic_bounds_check_fn(), lang_items.begin_panic_fn()]
+        .contains(&Some(def_id))
+    {
+        return true;
+    }
+    let krate = tcx.crate_name(def_id.krate);
+    let name = tcx.item_name(def_id);
+    (krate == sym::core && name == sym::panic_fmt)
+        || (krate == sym::std && name == sym::begin_panic_fmt)
+}
+
 fn check_fn_for_unconditional_recursion(
     tcx: TyCtxt<'tcx>,
     fn_kind: FnKind<'_>,
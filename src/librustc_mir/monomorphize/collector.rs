@@ -176,10 +176,15 @@
 
 use crate::monomorphize;
 
+use rustc::lint::builtin::{
+    EXCESSIVE_MONOMORPHIZATION, GENERIC_DIVISION_BY_ZERO, LARGE_FUTURES, LARGE_STACK_FRAMES,
+    REACHABLE_ALLOCATIONS, REACHABLE_PANICS,
+};
+use rustc::lint::{self, Level, Lint};
 use rustc::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc::middle::lang_items::{ExchangeMallocFnLangItem, StartFnLangItem};
 use rustc::mir::interpret::{AllocId, ConstValue};
-use rustc::mir::interpret::{ErrorHandled, GlobalAlloc, Scalar};
+use rustc::mir::interpret::{ErrorHandled, GlobalAlloc, PanicInfo, Scalar};
 use rustc::mir::mono::{InstantiationMode, MonoItem};
 use rustc::mir::visit::Visitor as MirVisitor;
 use rustc::mir::{self, Location, PlaceBase, Static, StaticKind};
@@ -195,7 +200,10 @@ use rustc_hir::def_id::{DefId, DefIdMap, LOCAL_CRATE};
 use rustc_hir::itemlikevisit::ItemLikeVisitor;
 use rustc_index::bit_set::GrowableBitSet;
 use smallvec::SmallVec;
+use std::cmp;
+use std::collections::VecDeque;
 use std::iter;
+use syntax::expand::allocator::ALLOCATOR_METHODS;
 
 #[derive(PartialEq)]
 pub enum MonoItemCollectionMode {
@@ -261,6 +269,18 @@ impl<'tcx> InliningMap<'tcx> {
         }
     }
 
+    // Internally iterate over all items referenced by `source`.
+    fn with_accesses<F>(&self, source: MonoItem<'tcx>, mut f: F)
+    where
+        F: FnMut(MonoItem<'tcx>),
+    {
+        if let Some(&(start_index, end_index)) = self.index.get(&source) {
+            for &target in &self.targets[start_index..end_index] {
+                f(target);
+            }
+        }
+    }
+
     // Internally iterate over all items and the things each accesses.
     pub fn iter_accesses<F>(&self, mut f: F)
     where
@@ -293,12 +313,33 @@ pub fn collect_crate_mono_items(
         tcx.sess.time("monomorphization_collector_graph_walk", || {
             par_iter(roots).for_each(|root| {
                 let mut recursion_depths = DefIdMap::default();
-                collect_items_rec(tcx, root, visited, &mut recursion_depths, inlining_map);
+                let mut instantiation_stack = Vec::new();
+                collect_items_rec(
+                    tcx,
+                    root,
+                    visited,
+                    &mut recursion_depths,
+                    &mut instantiation_stack,
+                    inlining_map,
+                );
             });
         });
     }
 
-    (visited.into_inner(), inlining_map.into_inner())
+    let (visited, inlining_map) = (visited.into_inner(), inlining_map.into_inner());
+    tcx.sess.time("monomorphization_collector_generator_sizes", || {
+        check_generator_sizes(tcx, &visited)
+    });
+    tcx.sess.time("monomorphization_collector_reachable_panics", || {
+        check_reachable_panics(tcx, &visited, &inlining_map)
+    });
+    tcx.sess.time("monomorphization_collector_reachable_allocations", || {
+        check_reachable_allocations(tcx, &visited, &inlining_map)
+    });
+    tcx.sess.time("monomorphization_collector_instantiation_counts", || {
+        check_instantiation_counts(tcx, &visited)
+    });
+    (visited, inlining_map)
 }
 
 // Find all non-generic items by walking the HIR. These items serve as roots to
@@ -333,6 +374,7 @@ fn collect_items_rec<'tcx>(
     starting_point: MonoItem<'tcx>,
     visited: MTRef<'_, MTLock<FxHashSet<MonoItem<'tcx>>>>,
     recursion_depths: &mut DefIdMap<usize>,
+    instantiation_stack: &mut Vec<Instance<'tcx>>,
     inlining_map: MTRef<'_, MTLock<InliningMap<'tcx>>>,
 ) {
     if !visited.lock_mut().insert(starting_point.clone()) {
@@ -367,6 +409,8 @@ fn collect_items_rec<'tcx>(
             // Keep track of the monomorphization recursion depth
             recursion_depth_reset = Some(check_recursion_limit(tcx, instance, recursion_depths));
             check_type_length_limit(tcx, instance);
+            check_stack_frame_size(tcx, instance, instantiation_stack);
+            check_division_by_zero(tcx, instance, instantiation_stack);
 
             collect_neighbours(tcx, instance, &mut neighbors);
         }
@@ -377,8 +421,21 @@ fn collect_items_rec<'tcx>(
 
     record_accesses(tcx, starting_point, &neighbors[..], inlining_map);
 
+    if let MonoItem::Fn(instance) = starting_point {
+        instantiation_stack.push(instance);
+    }
     for neighbour in neighbors {
-        collect_items_rec(tcx, neighbour, visited, recursion_depths, inlining_map);
+        collect_items_rec(
+            tcx,
+            neighbour,
+            visited,
+            recursion_depths,
+            instantiation_stack,
+            inlining_map,
+        );
+    }
+    if let MonoItem::Fn(_) = starting_point {
+        instantiation_stack.pop();
     }
 
     if let Some((def_id, depth)) = recursion_depth_reset {
@@ -443,6 +500,707 @@ fn check_recursion_limit<'tcx>(
     (def_id, recursion_depth)
 }
 
+/// Warns about generators (including the futures of async fns and blocks) whose
+/// layout is larger than `-Z large-future-threshold`, pointing at the values
+/// that take up the most space in it.
+///
+/// A generic generator is reported once, for its largest instance, rather than once for
+/// every set of type arguments it is instantiated with.
+fn check_generator_sizes<'tcx>(tcx: TyCtxt<'tcx>, items: &FxHashSet<MonoItem<'tcx>>) {
+    let threshold = tcx.sess.opts.debugging_opts.large_future_threshold as u64;
+    let param_env = ty::ParamEnv::reveal_all();
+    let mut largest: FxHashMap<DefId, (hir::HirId, u64, Instance<'tcx>)> = FxHashMap::default();
+    for &item in items {
+        let instance = match item {
+            MonoItem::Fn(instance) => instance,
+            _ => continue,
+        };
+        let def_id = match instance.def {
+            ty::InstanceDef::Item(def_id) => def_id,
+            _ => continue,
+        };
+        // Generators from other crates are their authors' business.
+        let hir_id = match tcx.hir().as_local_hir_id(def_id) {
+            Some(hir_id) => hir_id,
+            None => continue,
+        };
+        let ty = instance.monomorphic_ty(tcx);
+        if let ty::Generator(..) = ty.kind {
+        } else {
+            continue;
+        }
+        // Avoid computing the layouts of every interior type if nobody will see the lint.
+        let (level, src) = tcx.lint_level_at_node(LARGE_FUTURES, hir_id);
+        if level == Level::Allow {
+            lint::mark_allow_used(tcx.sess, src);
+            continue;
+        }
+        let size = match tcx.layout_of(param_env.and(ty)) {
+            Ok(layout) => layout.size.bytes(),
+            Err(_) => continue,
+        };
+        if size <= threshold {
+            continue;
+        }
+        let entry = largest.entry(def_id).or_insert((hir_id, size, instance));
+        if size > entry.1 {
+            *entry = (hir_id, size, instance);
+        }
+    }
+
+    let mut reported: Vec<_> = largest.into_iter().collect();
+    reported.sort_by_key(|&(_, (hir_id, ..))| hir_id);
+    for (def_id, (hir_id, size, instance)) in reported {
+        report_large_generator(tcx, hir_id, def_id, size, threshold, instance);
+    }
+}
+
+fn report_large_generator<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    hir_id: hir::HirId,
+    def_id: DefId,
+    size: u64,
+    threshold: u64,
+    instance: Instance<'tcx>,
+) {
+    let param_env = ty::ParamEnv::reveal_all();
+    let body = tcx.hir().body(tcx.hir().body_owned_by(hir_id));
+    let descr = match body.generator_kind {
+        Some(hir::GeneratorKind::Async(_)) => "future",
+        _ => "generator",
+    };
+
+    let tables = tcx.typeck_tables_of(def_id);
+    let mut interior: Vec<_> = tables
+        .generator_interior_types
+        .iter()
+        .filter(|cause| body.value.span.contains(cause.span))
+        .filter_map(|cause| {
+            let ty = tcx.erase_late_bound_regions(&ty::Binder::bind(cause.ty));
+            let ty = tcx.subst_and_normalize_erasing_regions(instance.substs, param_env, &ty);
+            let layout = tcx.layout_of(param_env.and(ty)).ok()?;
+            Some((layout.size.bytes(), cause))
+        })
+        .collect();
+    interior.sort_by_key(|&(size, cause)| (cmp::Reverse(size), cause.span));
+
+    // The body of an async fn is best reported as the fn itself.
+    let span_id = match body.generator_kind {
+        Some(hir::GeneratorKind::Async(hir::AsyncGeneratorKind::Fn)) => {
+            tcx.hir().get_parent_item(hir_id)
+        }
+        _ => hir_id,
+    };
+    let span = tcx.sess.source_map().def_span(tcx.hir().span(span_id));
+    let msg = format!(
+        "this {} is {} bytes, which is larger than the limit of {} bytes",
+        descr, size, threshold
+    );
+    let mut err = tcx.struct_span_lint_hir(LARGE_FUTURES, hir_id, span, &msg);
+    // Only point at the values that account for a noticeable part of the size.
+    let largest = interior.iter().take(3).filter(|&&(value_size, _)| value_size * 10 >= size);
+    for &(value_size, cause) in largest {
+        err.span_label(cause.span, format!("this value takes up {} bytes", value_size));
+        if let Some(yield_span) = cause.yield_span {
+            err.span_label(yield_span, "and is kept alive across this await point");
+        }
+    }
+    err.help(&format!(
+        "consider boxing the largest values, or the {} itself, to reduce its size",
+        descr
+    ));
+    err.emit();
+}
+
+/// Warns about functions whose stack frame is estimated to be larger than
+/// `-Z stack-frame-threshold`, pointing at the locals that take up the most space in it.
+///
+/// The estimate is the sum of the sizes of the MIR locals, which overestimates frames where
+/// LLVM reuses the slots of locals that are not alive at the same time, but gets the frames
+/// that are large because of a few large values right.
+fn check_stack_frame_size<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    instantiation_stack: &[Instance<'tcx>],
+) {
+    let threshold = match tcx.sess.opts.debugging_opts.stack_frame_threshold {
+        Some(threshold) => threshold as u64,
+        None => return,
+    };
+    let def_id = match instance.def {
+        ty::InstanceDef::Item(def_id) => def_id,
+        _ => return,
+    };
+    // The instantiations of generic functions from other crates are reported at the local
+    // function instantiating them.
+    let local_hir_id = tcx.hir().as_local_hir_id(def_id);
+    let hir_id = match local_hir_id {
+        Some(hir_id) => hir_id,
+        None => match instantiation_stack
+            .iter()
+            .rev()
+            .find_map(|caller| tcx.hir().as_local_hir_id(caller.def_id()))
+        {
+            Some(hir_id) => hir_id,
+            None => return,
+        },
+    };
+    let (level, src) = tcx.lint_level_at_node(LARGE_STACK_FRAMES, hir_id);
+    if level == Level::Allow {
+        lint::mark_allow_used(tcx.sess, src);
+        return;
+    }
+
+    let param_env = ty::ParamEnv::reveal_all();
+    let body = tcx.instance_mir(instance.def);
+    let mut locals = Vec::new();
+    for (local, decl) in body.local_decls.iter_enumerated() {
+        let ty = tcx.subst_and_normalize_erasing_regions(instance.substs, param_env, &decl.ty);
+        if let Ok(layout) = tcx.layout_of(param_env.and(ty)) {
+            locals.push((layout.size.bytes(), local, decl.source_info.span));
+        }
+    }
+    let size: u64 = locals.iter().map(|&(size, ..)| size).sum();
+    if size <= threshold {
+        return;
+    }
+    locals.sort_by_key(|&(size, local, _)| (cmp::Reverse(size), local));
+
+    let span = tcx.sess.source_map().def_span(tcx.hir().span(hir_id));
+    let msg = format!(
+        "the stack frame of `{}` is estimated at {} bytes, which is larger than the limit of {} \
+         bytes",
+        instance, size, threshold
+    );
+    let mut err = tcx.struct_span_lint_hir(LARGE_STACK_FRAMES, hir_id, span, &msg);
+    // Only point at the locals that account for a noticeable part of the size, and only in
+    // the local crate.
+    if local_hir_id.is_some() {
+        let largest = locals.iter().take(3).filter(|&&(local_size, ..)| local_size * 10 >= size);
+        for &(local_size, local, local_span) in largest {
+            let what = match body.local_kind(local) {
+                mir::LocalKind::ReturnPointer => "the return value",
+                mir::LocalKind::Arg => "this argument",
+                mir::LocalKind::Var => "this variable",
+                mir::LocalKind::Temp => "this temporary",
+            };
+            err.span_label(local_span, format!("{} takes up {} bytes", what, local_size));
+        }
+    }
+    if !instance.substs.is_empty() {
+        let chain: Vec<_> = instantiation_stack
+            .iter()
+            .chain(iter::once(&instance))
+            .map(|instance| format!("`{}`", instance))
+            .collect();
+        err.note(&format!("instantiated as {}", chain.join(" -> ")));
+    }
+    err.help("consider moving the largest values to the heap, for example with `Box`");
+    err.emit();
+}
+
+/// Warns about divisions and remainders by a constant that depends on generic parameters (an
+/// associated or generic const), and that turns out to be zero for `instance`. Such divisions
+/// always panic, but const propagation cannot see it before monomorphization.
+fn check_division_by_zero<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    instantiation_stack: &[Instance<'tcx>],
+) {
+    let def_id = match instance.def {
+        ty::InstanceDef::Item(def_id) => def_id,
+        _ => return,
+    };
+    if !def_id.is_local() || instance.substs.is_empty() {
+        return;
+    }
+
+    let param_env = ty::ParamEnv::reveal_all();
+    let body = tcx.instance_mir(instance.def);
+    // The divisor is usually moved into a temporary for the zero check, so keep track of the
+    // temporaries holding generic constants.
+    let mut generic_consts = FxHashMap::default();
+    for data in body.basic_blocks() {
+        for statement in &data.statements {
+            let (place, rvalue) = match statement.kind {
+                mir::StatementKind::Assign(box (ref place, ref rvalue)) => (place, rvalue),
+                _ => continue,
+            };
+            let (op, divisor) = match *rvalue {
+                mir::Rvalue::Use(mir::Operand::Constant(ref constant)) => {
+                    if let Some(local) = place.as_local() {
+                        if constant.literal.needs_subst() {
+                            generic_consts.insert(local, constant.literal);
+                        }
+                    }
+                    continue;
+                }
+                mir::Rvalue::BinaryOp(op @ mir::BinOp::Div, _, ref divisor)
+                | mir::Rvalue::BinaryOp(op @ mir::BinOp::Rem, _, ref divisor) => (op, divisor),
+                _ => continue,
+            };
+            let divisor = match *divisor {
+                mir::Operand::Constant(ref constant) => constant.literal,
+                mir::Operand::Copy(ref place) | mir::Operand::Move(ref place) => {
+                    match place.as_local().and_then(|local| generic_consts.get(&local)) {
+                        Some(&divisor) => divisor,
+                        None => continue,
+                    }
+                }
+            };
+            if !divisor.needs_subst() {
+                continue;
+            }
+            let divisor =
+                tcx.subst_and_normalize_erasing_regions(instance.substs, param_env, &divisor);
+            if divisor.try_eval_bits(tcx, param_env, divisor.ty) != Some(0) {
+                continue;
+            }
+
+            let lint_root = match body.source_scopes[statement.source_info.scope].local_data {
+                mir::ClearCrossCrate::Set(ref data) => data.lint_root,
+                mir::ClearCrossCrate::Clear => continue,
+            };
+            let msg = match op {
+                mir::BinOp::Div => format!("attempt to divide by zero in `{}`", instance),
+                _ => format!(
+                    "attempt to calculate the remainder with a divisor of zero in `{}`",
+                    instance
+                ),
+            };
+            let chain: Vec<_> = instantiation_stack
+                .iter()
+                .chain(iter::once(&instance))
+                .map(|instance| format!("`{}`", instance))
+                .collect();
+            tcx.struct_span_lint_hir(
+                GENERIC_DIVISION_BY_ZERO,
+                lint_root,
+                statement.source_info.span,
+                &msg,
+            )
+            .span_label(statement.source_info.span, "the divisor is zero for this instantiation")
+            .note(&format!("instantiated as {}", chain.join(" -> ")))
+            .emit();
+        }
+    }
+}
+
+/// The next step from a mono item on a shortest way to a call of interest, like a panic.
+enum PathStep<'tcx> {
+    /// The item makes the call itself, to the function with this name.
+    End(String),
+    /// The item gets there through this other item.
+    Through(MonoItem<'tcx>),
+}
+
+/// Whether `def_id` starts a panic: a panic lang item, or a function of another crate that is
+/// `#[cold]` and never returns, like the `unwrap_failed` of `Result::unwrap`.
+fn is_panic_entry(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    let lang_items = tcx.lang_items();
+    if [
+        lang_items.panic_fn(),
+        lang_items.panic_bounds_check_fn(),
+        lang_items.begin_panic_fn(),
+        lang_items.panic_impl(),
+    ]
+    .contains(&Some(def_id))
+    {
+        return true;
+    }
+    !def_id.is_local()
+        && tcx.codegen_fn_attrs(def_id).flags.contains(CodegenFnAttrFlags::COLD)
+        && tcx.fn_sig(def_id).output().skip_binder().is_never()
+}
+
+/// Whether `def_id` is one of the `__rust_alloc` functions, through which all calls to the
+/// global allocator go.
+fn is_allocator_fn(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    if !tcx.is_foreign_item(def_id) {
+        return false;
+    }
+    let name = tcx.item_name(def_id).as_str();
+    ALLOCATOR_METHODS.iter().any(|method| format!("__rust_{}", method.name) == *name)
+}
+
+/// The function that `func`, the callee of a call in the body of `instance`, resolves to, if
+/// it is known statically.
+fn callee_def_id<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    body: &mir::Body<'tcx>,
+    func: &mir::Operand<'tcx>,
+) -> Option<DefId> {
+    let callee_ty = tcx.subst_and_normalize_erasing_regions(
+        instance.substs,
+        ty::ParamEnv::reveal_all(),
+        &func.ty(body, tcx),
+    );
+    match callee_ty.kind {
+        ty::FnDef(def_id, _) => Some(def_id),
+        _ => None,
+    }
+}
+
+/// The panic entry point that the body of `instance` calls itself, if any.
+fn find_panic_entry<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> Option<String> {
+    let body = tcx.instance_mir(instance.def);
+    for data in body.basic_blocks() {
+        let def_id = match data.terminator().kind {
+            // Failing assertions, like bounds and overflow checks, call the panic lang items
+            // from codegen.
+            mir::TerminatorKind::Assert { ref msg, .. } => match msg {
+                PanicInfo::BoundsCheck { .. } => tcx.lang_items().panic_bounds_check_fn(),
+                _ => tcx.lang_items().panic_fn(),
+            },
+            mir::TerminatorKind::Call { ref func, .. } => callee_def_id(tcx, instance, &body, func)
+                .filter(|&def_id| is_panic_entry(tcx, def_id)),
+            _ => None,
+        };
+        if let Some(def_id) = def_id {
+            return Some(tcx.def_path_str(def_id));
+        }
+    }
+    None
+}
+
+/// The `__rust_alloc` function that the body of `instance` calls itself, if any.
+fn find_allocator_call<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> Option<String> {
+    let body = tcx.instance_mir(instance.def);
+    body.basic_blocks().iter().find_map(|data| match data.terminator().kind {
+        mir::TerminatorKind::Call { ref func, .. } => callee_def_id(tcx, instance, &body, func)
+            .filter(|&def_id| is_allocator_fn(tcx, def_id))
+            .map(|def_id| tcx.def_path_str(def_id)),
+        _ => None,
+    })
+}
+
+/// The functions that the body of `instance` calls statically, including the `exchange_malloc`
+/// of its `box` expressions.
+fn static_callees<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) -> Vec<Instance<'tcx>> {
+    let body = tcx.instance_mir(instance.def);
+    let mut callees = Vec::new();
+    for data in body.basic_blocks() {
+        for statement in &data.statements {
+            if let mir::StatementKind::Assign(box (_, ref rvalue)) = statement.kind {
+                if let mir::Rvalue::NullaryOp(mir::NullOp::Box, _) = *rvalue {
+                    let exchange_malloc = tcx.lang_items().exchange_malloc_fn();
+                    callees.extend(exchange_malloc.map(|def_id| Instance::mono(tcx, def_id)));
+                }
+            }
+        }
+        if let mir::TerminatorKind::Call { ref func, .. } = data.terminator().kind {
+            let callee_ty = tcx.subst_and_normalize_erasing_regions(
+                instance.substs,
+                ty::ParamEnv::reveal_all(),
+                &func.ty(&*body, tcx),
+            );
+            if let ty::FnDef(def_id, substs) = callee_ty.kind {
+                callees.extend(ty::Instance::resolve(
+                    tcx,
+                    ty::ParamEnv::reveal_all(),
+                    def_id,
+                    substs,
+                ));
+            }
+        }
+    }
+    callees
+}
+
+/// Whether the body of `instance`, a function of another crate that isn't among the collected
+/// mono items, can be walked: it has MIR, like the generic functions that are linked to under
+/// `-Z share-generics` and the `#[inline]` ones.
+///
+/// Functions of other crates without MIR, which are most of their non-generic functions, are
+/// opaque: the calls made from them can't be followed.
+fn is_walkable_upstream(tcx: TyCtxt<'_>, instance: Instance<'_>) -> bool {
+    match instance.def {
+        ty::InstanceDef::Item(def_id) => {
+            !def_id.is_local() && !tcx.is_foreign_item(def_id) && tcx.is_mir_available(def_id)
+        }
+        _ => false,
+    }
+}
+
+/// For each mono item from which a call found by `find_end` can be reached, the next step on a
+/// shortest way to such a call.
+///
+/// Besides the collected items, this follows the calls into other crates through their MIR,
+/// where it is available; see `is_walkable_upstream`.
+fn shortest_paths<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    items: &FxHashSet<MonoItem<'tcx>>,
+    inlining_map: &InliningMap<'tcx>,
+    find_end: impl Fn(Instance<'tcx>) -> Option<String>,
+) -> FxHashMap<MonoItem<'tcx>, PathStep<'tcx>> {
+    // The graph is walked in a fixed order, so that the reported paths don't change from one
+    // compilation to the next.
+    let mut sorted_items: Vec<_> =
+        items.iter().map(|&item| (item.to_string(tcx, true), item)).collect();
+    sorted_items.sort_by(|a, b| a.0.cmp(&b.0));
+    let mut callers: FxHashMap<MonoItem<'tcx>, Vec<MonoItem<'tcx>>> = FxHashMap::default();
+    for &(_, item) in &sorted_items {
+        inlining_map.with_accesses(item, |callee| callers.entry(callee).or_default().push(item));
+    }
+    // The functions of other crates that are linked to rather than instantiated here aren't
+    // among the items, so their bodies are walked too, and so are those of their callees.
+    let mut nodes: Vec<_> = sorted_items.into_iter().map(|(_, item)| item).collect();
+    let mut upstream = FxHashSet::default();
+    let mut next = 0;
+    while next < nodes.len() {
+        let item = nodes[next];
+        next += 1;
+        let instance = match item {
+            MonoItem::Fn(instance) => instance,
+            _ => continue,
+        };
+        for callee in static_callees(tcx, instance) {
+            let collected = items.contains(&MonoItem::Fn(callee));
+            if !collected && !is_walkable_upstream(tcx, callee) {
+                continue;
+            }
+            let callee = MonoItem::Fn(callee);
+            callers.entry(callee).or_default().push(item);
+            if !collected && upstream.insert(callee) {
+                nodes.push(callee);
+            }
+        }
+    }
+
+    // Walk back from the functions that make the call themselves, so that each item knows its
+    // next step on a shortest way to it.
+    let mut steps = FxHashMap::default();
+    let mut queue = VecDeque::new();
+    for &item in &nodes {
+        if let MonoItem::Fn(instance) = item {
+            if let Some(end) = find_end(instance) {
+                steps.insert(item, PathStep::End(end));
+                queue.push_back(item);
+            }
+        }
+    }
+    while let Some(item) = queue.pop_front() {
+        for &caller in callers.get(&item).into_iter().flatten() {
+            if !steps.contains_key(&caller) {
+                steps.insert(caller, PathStep::Through(item));
+                queue.push_back(caller);
+            }
+        }
+    }
+    steps
+}
+
+/// The shortest way from `instance` to a call found by `shortest_paths`, as `a` -> `b` -> `c`,
+/// if there is one.
+fn shortest_path<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    steps: &FxHashMap<MonoItem<'tcx>, PathStep<'tcx>>,
+    instance: Instance<'tcx>,
+) -> Option<String> {
+    let mut step = steps.get(&MonoItem::Fn(instance))?;
+    let mut path = vec![format!("`{}`", instance)];
+    loop {
+        match step {
+            PathStep::End(end) => {
+                path.push(format!("`{}`", end));
+                return Some(path.join(" -> "));
+            }
+            PathStep::Through(item) => {
+                path.push(match item {
+                    MonoItem::Fn(instance) => format!("`{}`", instance),
+                    _ => format!("`{}`", item.to_string(tcx, false)),
+                });
+                step = &steps[item];
+            }
+        }
+    }
+}
+
+/// The function instances of the local crate among `items` for which `lint` isn't allowed,
+/// sorted, along with their `HirId`s.
+fn linted_instances<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    items: &FxHashSet<MonoItem<'tcx>>,
+    lint: &'static Lint,
+) -> Vec<(hir::HirId, Instance<'tcx>)> {
+    let mut instances: Vec<_> = items
+        .iter()
+        .filter_map(|&item| {
+            let instance = match item {
+                MonoItem::Fn(instance) => instance,
+                _ => return None,
+            };
+            let def_id = match instance.def {
+                ty::InstanceDef::Item(def_id) => def_id,
+                _ => return None,
+            };
+            let hir_id = tcx.hir().as_local_hir_id(def_id)?;
+            let (level, src) = tcx.lint_level_at_node(lint, hir_id);
+            if level == Level::Allow {
+                lint::mark_allow_used(tcx.sess, src);
+                return None;
+            }
+            Some((hir_id, item.to_string(tcx, true), instance))
+        })
+        .collect();
+    instances.sort_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
+    instances.into_iter().map(|(hir_id, _, instance)| (hir_id, instance)).collect()
+}
+
+/// Reports the functions of the local crate from which a panic can be reached through the mono
+/// item graph, with the shortest chain of calls leading to it.
+fn check_reachable_panics<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    items: &FxHashSet<MonoItem<'tcx>>,
+    inlining_map: &InliningMap<'tcx>,
+) {
+    let reported = linted_instances(tcx, items, REACHABLE_PANICS);
+    // Avoid walking the whole graph if nobody will see the lint.
+    if reported.is_empty() {
+        return;
+    }
+    let steps =
+        shortest_paths(tcx, items, inlining_map, |instance| find_panic_entry(tcx, instance));
+
+    for (hir_id, instance) in reported {
+        let path = match shortest_path(tcx, &steps, instance) {
+            Some(path) => path,
+            None => continue,
+        };
+        let span = tcx.sess.source_map().def_span(tcx.hir().span(hir_id));
+        let msg = format!("a panic is reachable from `{}`", instance);
+        tcx.struct_span_lint_hir(REACHABLE_PANICS, hir_id, span, &msg)
+            .note(&format!("for example through {}", path))
+            .emit();
+    }
+}
+
+/// Reports the entry points of the local crate, its exported functions and `main`, from which
+/// the global allocator can be reached through the mono item graph. Each is reported once, with
+/// the shortest chain of calls from one of its instances.
+fn check_reachable_allocations<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    items: &FxHashSet<MonoItem<'tcx>>,
+    inlining_map: &InliningMap<'tcx>,
+) {
+    let access_levels = tcx.privacy_access_levels(LOCAL_CRATE);
+    let entry_fn = tcx.entry_fn(LOCAL_CRATE).map(|(def_id, _)| def_id);
+    let mut reported = linted_instances(tcx, items, REACHABLE_ALLOCATIONS);
+    reported.retain(|&(hir_id, instance)| {
+        access_levels.is_exported(hir_id) || Some(instance.def_id()) == entry_fn
+    });
+    if reported.is_empty() {
+        return;
+    }
+    let steps =
+        shortest_paths(tcx, items, inlining_map, |instance| find_allocator_call(tcx, instance));
+
+    let mut seen = FxHashSet::default();
+    for (hir_id, instance) in reported {
+        if seen.contains(&hir_id) {
+            continue;
+        }
+        let path = match shortest_path(tcx, &steps, instance) {
+            Some(path) => path,
+            None => continue,
+        };
+        seen.insert(hir_id);
+        let span = tcx.sess.source_map().def_span(tcx.hir().span(hir_id));
+        let msg = format!(
+            "the global allocator is reachable from `{}`",
+            tcx.def_path_str(instance.def_id())
+        );
+        tcx.struct_span_lint_hir(REACHABLE_ALLOCATIONS, hir_id, span, &msg)
+            .note(&format!("for example through {}", path))
+            .emit();
+    }
+}
+
+/// Reports the generic functions of the local crate with more instances than
+/// `-Z instantiation-threshold`, along with the type arguments they are most often instantiated
+/// with.
+fn check_instantiation_counts<'tcx>(tcx: TyCtxt<'tcx>, items: &FxHashSet<MonoItem<'tcx>>) {
+    let threshold = tcx.sess.opts.debugging_opts.instantiation_threshold;
+    let mut instances: FxHashMap<DefId, Vec<Instance<'tcx>>> = FxHashMap::default();
+    for &item in items {
+        let instance = match item {
+            MonoItem::Fn(instance) => instance,
+            _ => continue,
+        };
+        if let ty::InstanceDef::Item(def_id) = instance.def {
+            if def_id.is_local()
+                && !tcx.is_closure(def_id)
+                && instance.substs.types().next().is_some()
+            {
+                instances.entry(def_id).or_default().push(instance);
+            }
+        }
+    }
+
+    let mut reported: Vec<_> = instances
+        .into_iter()
+        .filter(|(_, instances)| instances.len() > threshold)
+        .filter_map(|(def_id, instances)| {
+            let hir_id = tcx.hir().as_local_hir_id(def_id)?;
+            let (level, src) = tcx.lint_level_at_node(EXCESSIVE_MONOMORPHIZATION, hir_id);
+            if level == Level::Allow {
+                lint::mark_allow_used(tcx.sess, src);
+                return None;
+            }
+            Some((hir_id, def_id, instances))
+        })
+        .collect();
+    reported.sort_by_key(|&(hir_id, ..)| hir_id);
+
+    for (hir_id, def_id, instances) in reported {
+        let span = tcx.sess.source_map().def_span(tcx.hir().span(hir_id));
+        let msg = format!(
+            "`{}` is instantiated {} times, more than the limit of {}",
+            tcx.def_path_str(def_id),
+            instances.len(),
+            threshold
+        );
+        let mut err = tcx.struct_span_lint_hir(EXCESSIVE_MONOMORPHIZATION, hir_id, span, &msg);
+
+        // The type parameters of the function come after those of its parents, like the impl
+        // of a method.
+        let mut params = Vec::new();
+        let mut generics = tcx.generics_of(def_id);
+        loop {
+            params.splice(0..0, generics.params.iter());
+            match generics.parent {
+                Some(parent) => generics = tcx.generics_of(parent),
+                None => break,
+            }
+        }
+        for param in params {
+            if let GenericParamDefKind::Type { .. } = param.kind {
+                let mut counts: FxHashMap<Ty<'tcx>, usize> = FxHashMap::default();
+                for instance in &instances {
+                    *counts.entry(instance.substs.type_at(param.index as usize)).or_default() += 1;
+                }
+                let mut counts: Vec<_> =
+                    counts.into_iter().map(|(ty, count)| (count, ty.to_string())).collect();
+                counts.sort_by(|a, b| (cmp::Reverse(a.0), &a.1).cmp(&(cmp::Reverse(b.0), &b.1)));
+                let common: Vec<_> = counts
+                    .iter()
+                    .take(3)
+                    .map(|(count, ty)| {
+                        format!("`{}` ({} time{})", ty, count, if *count == 1 { "" } else { "s" })
+                    })
+                    .collect();
+                err.note(&format!("`{}` is most often {}", param.name, common.join(", ")));
+            }
+        }
+        err.help(
+            "consider moving the code that doesn't depend on the type arguments into a \
+             non-generic function, or taking a `fn` pointer or `dyn` trait object instead",
+        );
+        err.emit();
+    }
+}
+
 fn check_type_length_limit<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>) {
     let type_length = instance.substs.types().flat_map(|ty| ty.walk()).count();
     let const_length = instance.substs.consts().flat_map(|ct| ct.ty.walk()).count();
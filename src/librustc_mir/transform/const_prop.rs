@@ -23,9 +23,10 @@ use rustc_data_structures::fx::FxHashMap;
 use rustc_hir::def::DefKind;
 use rustc_hir::def_id::DefId;
 use rustc_hir::HirId;
+use rustc_index::bit_set::BitSet;
 use rustc_index::vec::IndexVec;
 use rustc_span::{Span, DUMMY_SP};
-use syntax::ast::Mutability;
+use syntax::ast::{Mutability, UintTy};
 
 use crate::const_eval::error_to_const_error;
 use crate::interpret::{
@@ -265,6 +266,12 @@ struct ConstPropagator<'mir, 'tcx> {
     // Because we have `MutVisitor` we can't obtain the `SourceInfo` from a `Location`. So we store
     // the last known `SourceInfo` here and just keep revisiting it.
     source_info: Option<SourceInfo>,
+    // The operands of checked operations that are known to overflow, keyed by the local holding
+    // the result, so that the failing `Assert` can show what was being computed.
+    overflowing_ops: FxHashMap<Local, (BinOp, ImmTy<'tcx>, ImmTy<'tcx>)>,
+    // The `LintOnly` locals, and the locals whose current value was computed from one of them.
+    // Their values are only there for the lints, and are never written back into the MIR.
+    lint_only: BitSet<Local>,
 }
 
 impl<'mir, 'tcx> LayoutOf for ConstPropagator<'mir, 'tcx> {
@@ -311,6 +318,12 @@ impl<'mir, 'tcx> ConstPropagator<'mir, 'tcx> {
         let span = tcx.def_span(def_id);
         let mut ecx = InterpCx::new(tcx.at(span), param_env, ConstPropMachine, ());
         let can_const_prop = CanConstProp::check(body);
+        let mut lint_only = BitSet::new_empty(can_const_prop.len());
+        for (local, &mode) in can_const_prop.iter_enumerated() {
+            if mode == ConstPropMode::LintOnly {
+                lint_only.insert(local);
+            }
+        }
 
         let ret = ecx
             .layout_of(body.return_ty().subst(tcx, substs))
@@ -344,9 +357,25 @@ impl<'mir, 'tcx> ConstPropagator<'mir, 'tcx> {
             local_decls: body.local_decls.clone(),
             ret: ret.map(Into::into),
             source_info: None,
+            overflowing_ops: Default::default(),
+            lint_only,
         }
     }
 
+    /// Whether `rvalue` reads a local whose value must not be propagated into the MIR.
+    fn reads_lint_only(&self, rvalue: &Rvalue<'tcx>, location: Location) -> bool {
+        let mut finder = FindLocals { locals: &self.lint_only, found: false };
+        finder.visit_rvalue(rvalue, location);
+        finder.found
+    }
+
+    /// Whether `operand` reads a local whose value must not be propagated into the MIR.
+    fn operand_reads_lint_only(&self, operand: &Operand<'tcx>, location: Location) -> bool {
+        let mut finder = FindLocals { locals: &self.lint_only, found: false };
+        finder.visit_operand(operand, location);
+        finder.found
+    }
+
     fn get_const(&self, local: Local) -> Option<Const<'tcx>> {
         if local == RETURN_PLACE {
             // Try to read the return place as an immediate so that if it is representable as a
@@ -540,6 +569,30 @@ impl<'mir, 'tcx> ConstPropagator<'mir, 'tcx> {
         Some(())
     }
 
+    fn record_overflowing_op(
+        &mut self,
+        op: BinOp,
+        left: &Operand<'tcx>,
+        right: &Operand<'tcx>,
+        local: Local,
+        source_info: SourceInfo,
+    ) {
+        let operands = self.use_ecx(source_info, |this| {
+            let l = this.ecx.read_immediate(this.ecx.eval_operand(left, None)?)?;
+            let r = this.ecx.read_immediate(this.ecx.eval_operand(right, None)?)?;
+            let (_, overflow, _ty) = this.ecx.overflowing_binary_op(op, l, r)?;
+            Ok((l, r, overflow))
+        });
+        match operands {
+            Some((l, r, true)) => {
+                self.overflowing_ops.insert(local, (op, l, r));
+            }
+            _ => {
+                self.overflowing_ops.remove(&local);
+            }
+        }
+    }
+
     fn const_prop(
         &mut self,
         rvalue: &Rvalue<'tcx>,
@@ -577,6 +630,14 @@ impl<'mir, 'tcx> ConstPropagator<'mir, 'tcx> {
                 self.check_binary_op(*op, left, right, source_info, place_layout, overflow_check)?;
             }
 
+            // Additional checking: remember the operands of checked operations that overflow, so
+            // that the lint for the `Assert` on the overflow flag can include them.
+            Rvalue::CheckedBinaryOp(op, left, right) => {
+                if let Some(local) = place.as_local() {
+                    self.record_overflowing_op(*op, left, right, local, source_info);
+                }
+            }
+
             // Work around: avoid ICE in miri. FIXME(wesleywiser)
             // The Miri engine ICEs when taking a reference to an uninitialized unsized
             // local. There's nothing it can do here: taking a reference needs an allocation
@@ -601,6 +662,24 @@ impl<'mir, 'tcx> ConstPropagator<'mir, 'tcx> {
                 }
             }
 
+            // Additional checking: the length of an array is known from its type, even when the
+            // array itself is not (e.g. when it is only reachable through a reference). Knowing it
+            // lets us report out-of-bounds indexing on such arrays.
+            Rvalue::Len(len_place) => {
+                let len_ty = len_place.ty(&self.local_decls, self.tcx).ty;
+                if let ty::Array(_, len) = len_ty.kind {
+                    if let Some(len) = len.try_eval_usize(self.tcx, self.param_env) {
+                        trace!("evaluating Len({:?}) from its type", len_place);
+                        return self.use_ecx(source_info, |this| {
+                            let len = Scalar::from_uint(len, this.ecx.data_layout().pointer_size);
+                            let dest = this.ecx.eval_place(place)?;
+                            this.ecx.write_scalar(len, dest)?;
+                            Ok(())
+                        });
+                    }
+                }
+            }
+
             _ => {}
         }
 
@@ -717,6 +796,28 @@ impl<'mir, 'tcx> ConstPropagator<'mir, 'tcx> {
     }
 }
 
+fn bin_op_symbol(op: BinOp) -> &'static str {
+    match op {
+        BinOp::Add => "+",
+        BinOp::Sub => "-",
+        BinOp::Mul => "*",
+        BinOp::Div => "/",
+        BinOp::Rem => "%",
+        BinOp::BitXor => "^",
+        BinOp::BitAnd => "&",
+        BinOp::BitOr => "|",
+        BinOp::Shl => "<<",
+        BinOp::Shr => ">>",
+        BinOp::Eq => "==",
+        BinOp::Lt => "<",
+        BinOp::Le => "<=",
+        BinOp::Ne => "!=",
+        BinOp::Ge => ">=",
+        BinOp::Gt => ">",
+        BinOp::Offset => "offset",
+    }
+}
+
 /// The mode that `ConstProp` is allowed to run in for a given `Local`.
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum ConstPropMode {
@@ -724,6 +825,9 @@ enum ConstPropMode {
     FullConstProp,
     /// The `Local` can be propagated into but reads cannot be propagated.
     OnlyPropagateInto,
+    /// Like `FullConstProp`, except that the values read from the `Local` are only used by the
+    /// lints, and never replace anything in the MIR.
+    LintOnly,
     /// No propagation is allowed at all.
     NoPropagation,
 }
@@ -755,10 +859,89 @@ impl CanConstProp {
             }
         }
         cpv.visit_body(body);
+
+        // Indices and enum values bound to variables are allowed to propagate for the lints, as
+        // long as they never influence a branch: `let i = 5; a[i]` is guaranteed to panic, while
+        // `if i < a.len() { a[i] }` is not.
+        let in_condition = condition_locals(&body);
+        for (local, val) in cpv.can_const_prop.iter_enumerated_mut() {
+            if *val != ConstPropMode::OnlyPropagateInto
+                || body.local_kind(local) != LocalKind::Var
+                || in_condition.contains(local)
+            {
+                continue;
+            }
+            let is_index_like = match body.local_decls[local].ty.kind {
+                ty::Uint(UintTy::Usize) => true,
+                ty::Adt(adt_def, _) => adt_def.is_enum(),
+                _ => false,
+            };
+            if is_index_like {
+                trace!("local {:?} can be linted on as it is not used in a branch", local);
+                *val = ConstPropMode::LintOnly;
+            }
+        }
         cpv.can_const_prop
     }
 }
 
+/// Returns all locals whose value flows into the discriminant of a `SwitchInt`.
+fn condition_locals(body: &Body<'_>) -> BitSet<Local> {
+    let mut locals = BitSet::new_empty(body.local_decls.len());
+    for block in body.basic_blocks() {
+        if let TerminatorKind::SwitchInt { discr, .. } = &block.terminator().kind {
+            if let Operand::Copy(place) | Operand::Move(place) = discr {
+                if let PlaceBase::Local(local) = place.base {
+                    locals.insert(local);
+                }
+            }
+        }
+    }
+
+    // Follow assignments backwards until no new locals are found.
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (block, data) in body.basic_blocks().iter_enumerated() {
+            for (statement_index, statement) in data.statements.iter().enumerate() {
+                if let StatementKind::Assign(box (ref place, ref rvalue)) = statement.kind {
+                    let dest = match place.as_local() {
+                        Some(dest) if locals.contains(dest) => dest,
+                        _ => continue,
+                    };
+                    trace!("{:?} is used in a condition, and so is {:?}", dest, rvalue);
+                    let mut collector = CollectLocals { locals: &mut locals, changed: false };
+                    collector.visit_rvalue(rvalue, Location { block, statement_index });
+                    changed |= collector.changed;
+                }
+            }
+        }
+    }
+    locals
+}
+
+struct CollectLocals<'a> {
+    locals: &'a mut BitSet<Local>,
+    changed: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for CollectLocals<'_> {
+    fn visit_local(&mut self, &local: &Local, _: PlaceContext, _: Location) {
+        self.changed |= self.locals.insert(local);
+    }
+}
+
+struct FindLocals<'a> {
+    locals: &'a BitSet<Local>,
+    found: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for FindLocals<'_> {
+    fn visit_local(&mut self, &local: &Local, _: PlaceContext, _: Location) {
+        self.found |= self.locals.contains(local);
+    }
+}
+
 impl<'tcx> Visitor<'tcx> for CanConstProp {
     fn visit_local(&mut self, &local: &Local, context: PlaceContext, _: Location) {
         use rustc::mir::visit::PlaceContext::*;
@@ -809,9 +992,19 @@ impl<'mir, 'tcx> MutVisitor<'tcx> for ConstPropagator<'mir, 'tcx> {
             if let Ok(place_layout) = self.tcx.layout_of(self.param_env.and(place_ty)) {
                 if let Some(local) = place.as_local() {
                     let can_const_prop = self.can_const_prop[local];
+                    let reads_lint_only = self.reads_lint_only(rval, location);
+                    if can_const_prop != ConstPropMode::LintOnly {
+                        if reads_lint_only {
+                            self.lint_only.insert(local);
+                        } else {
+                            self.lint_only.remove(local);
+                        }
+                    }
                     if let Some(()) = self.const_prop(rval, place_layout, source_info, place) {
-                        if can_const_prop == ConstPropMode::FullConstProp
+                        if (can_const_prop == ConstPropMode::FullConstProp
                             || can_const_prop == ConstPropMode::OnlyPropagateInto
+                            || can_const_prop == ConstPropMode::LintOnly)
+                            && !reads_lint_only
                         {
                             if let Some(value) = self.get_const(local) {
                                 if self.should_const_prop(value) {
@@ -825,7 +1018,9 @@ impl<'mir, 'tcx> MutVisitor<'tcx> for ConstPropagator<'mir, 'tcx> {
                             }
                         }
                     }
-                    if self.can_const_prop[local] != ConstPropMode::FullConstProp {
+                    if self.can_const_prop[local] != ConstPropMode::FullConstProp
+                        && self.can_const_prop[local] != ConstPropMode::LintOnly
+                    {
                         trace!("can't propagate into {:?}", local);
                         if local != RETURN_PLACE {
                             self.remove_const(local);
@@ -878,9 +1073,33 @@ impl<'mir, 'tcx> MutVisitor<'tcx> for ConstPropagator<'mir, 'tcx> {
                             .hir()
                             .as_local_hir_id(self.source.def_id())
                             .expect("some part of a failing const eval must be local");
+                        let mut label = None;
                         let msg = match msg {
-                            PanicInfo::Overflow(_)
-                            | PanicInfo::OverflowNeg
+                            PanicInfo::Overflow(_) => {
+                                let overflowing_op = match cond {
+                                    Operand::Move(ref place) | Operand::Copy(ref place) => {
+                                        match place.base {
+                                            PlaceBase::Local(local) => {
+                                                self.overflowing_ops.get(&local)
+                                            }
+                                            _ => None,
+                                        }
+                                    }
+                                    Operand::Constant(_) => None,
+                                };
+                                if let Some((op, l, r)) = overflowing_op {
+                                    label = Some(format!(
+                                        "attempt to compute `{}_{} {} {}_{}`, which would overflow",
+                                        l,
+                                        l.layout.ty,
+                                        bin_op_symbol(*op),
+                                        r,
+                                        r.layout.ty,
+                                    ));
+                                }
+                                msg.description().to_owned()
+                            }
+                            PanicInfo::OverflowNeg
                             | PanicInfo::DivisionByZero
                             | PanicInfo::RemainderByZero => msg.description().to_owned(),
                             PanicInfo::BoundsCheck { ref len, ref index } => {
@@ -906,9 +1125,20 @@ impl<'mir, 'tcx> MutVisitor<'tcx> for ConstPropagator<'mir, 'tcx> {
                             // Need proper const propagator for these
                             _ => return,
                         };
-                        self.tcx.lint_hir(::rustc::lint::builtin::CONST_ERR, hir_id, span, &msg);
+                        let mut lint = self.tcx.struct_span_lint_hir(
+                            ::rustc::lint::builtin::CONST_ERR,
+                            hir_id,
+                            span,
+                            &msg,
+                        );
+                        if let Some(label) = label {
+                            lint.span_label(span, label);
+                        }
+                        lint.emit();
                     } else {
-                        if self.should_const_prop(value) {
+                        if self.should_const_prop(value)
+                            && !self.operand_reads_lint_only(cond, location)
+                        {
                             if let ScalarMaybeUndef::Scalar(scalar) = value_const {
                                 *cond = self.operand_from_scalar(
                                     scalar,
@@ -922,7 +1152,9 @@ impl<'mir, 'tcx> MutVisitor<'tcx> for ConstPropagator<'mir, 'tcx> {
             }
             TerminatorKind::SwitchInt { ref mut discr, switch_ty, .. } => {
                 if let Some(value) = self.eval_operand(&discr, source_info) {
-                    if self.should_const_prop(value) {
+                    if self.should_const_prop(value)
+                        && !self.operand_reads_lint_only(discr, location)
+                    {
                         if let ScalarMaybeUndef::Scalar(scalar) =
                             self.ecx.read_scalar(value).unwrap()
                         {
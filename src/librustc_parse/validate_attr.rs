@@ -2,13 +2,14 @@
 
 use crate::parse_in;
 
-use rustc_errors::{Applicability, PResult};
+use rustc_errors::{Applicability, DiagnosticBuilder, PResult};
 use rustc_feature::{AttributeTemplate, BUILTIN_ATTRIBUTE_MAP};
 use rustc_session::lint::builtin::ILL_FORMED_ATTRIBUTE_INPUT;
 use rustc_session::parse::ParseSess;
 use rustc_span::{sym, Symbol};
 use syntax::ast::{self, Attribute, MacArgs, MacDelimiter, MetaItem, MetaItemKind};
-use syntax::tokenstream::DelimSpan;
+use syntax::token;
+use syntax::tokenstream::{DelimSpan, TokenTree};
 
 pub fn check_meta(sess: &ParseSess, attr: &Attribute) {
     if attr.is_doc_comment() {
@@ -152,6 +153,71 @@ pub fn check_builtin_attribute(
                 }
             }
         }
-        Err(mut err) => err.emit(),
+        Err(mut err) => {
+            if [sym::allow, sym::warn, sym::deny, sym::forbid].contains(&name) {
+                suggest_lint_names_without_dashes(attr, &mut err);
+            }
+            err.emit()
+        }
+    }
+}
+
+/// Lint names are often written with dashes, as on the command line, which makes the lint
+/// attribute fail to parse. Points out the lint names spelled with underscores instead.
+fn suggest_lint_names_without_dashes(attr: &Attribute, err: &mut DiagnosticBuilder<'_>) {
+    let tokens = match &attr.get_normal_item().args {
+        MacArgs::Delimited(_, _, tokens) => tokens,
+        _ => return,
+    };
+    let tokens = tokens
+        .trees()
+        .map(|tree| match tree {
+            TokenTree::Token(token) => Some(token),
+            TokenTree::Delimited(..) => None,
+        })
+        .collect::<Vec<_>>();
+    let ident = |i: usize| match tokens.get(i) {
+        Some(Some(token)) => token.ident().map(|(ident, _)| ident),
+        _ => None,
+    };
+    let is_dash = |i: usize| match tokens.get(i) {
+        Some(Some(tok)) => tok.kind == token::BinOp(token::Minus),
+        _ => false,
+    };
+
+    let mut suggestions = vec![];
+    let mut i = 0;
+    while i < tokens.len() {
+        let first = match ident(i) {
+            Some(ident) => ident,
+            None => {
+                i += 1;
+                continue;
+            }
+        };
+        let mut name = first.as_str().to_string();
+        let mut span = first.span;
+        i += 1;
+        while is_dash(i) {
+            let next = match ident(i + 1) {
+                Some(next) => next,
+                None => break,
+            };
+            name.push('_');
+            name.push_str(&next.as_str());
+            span = span.to(next.span);
+            i += 2;
+        }
+        if span != first.span {
+            suggestions.push((span, name));
+        }
+    }
+
+    if !suggestions.is_empty() {
+        err.multipart_suggestion(
+            "lint names use underscores instead of dashes",
+            suggestions,
+            Applicability::MachineApplicable,
+        );
     }
 }
@@ -345,7 +345,12 @@ fn has_allow_dead_code_or_lang_attr(
         return true;
     }
 
-    tcx.lint_level_at_node(lint::builtin::DEAD_CODE, id).0 == lint::Allow
+    let (level, src) = tcx.lint_level_at_node(lint::builtin::DEAD_CODE, id);
+    if level == lint::Allow {
+        lint::mark_allow_used(tcx.sess, src);
+        return true;
+    }
+    false
 }
 
 // This visitor seeds items that
@@ -24,12 +24,13 @@ fn call_malformed_plugin_attribute(sess: &Session, span: Span) {
         .emit();
 }
 
-/// Read plugin metadata and dynamically load registrar functions.
+/// Read plugin metadata and dynamically load registrar functions, along with the path of the
+/// plugin they are loaded from.
 pub fn load_plugins(
     sess: &Session,
     metadata_loader: &dyn MetadataLoader,
     krate: &Crate,
-) -> Vec<PluginRegistrarFn> {
+) -> Vec<(PluginRegistrarFn, PathBuf)> {
     let mut plugins = Vec::new();
 
     for attr in &krate.attrs {
@@ -51,7 +52,7 @@ pub fn load_plugins(
 }
 
 fn load_plugin(
-    plugins: &mut Vec<PluginRegistrarFn>,
+    plugins: &mut Vec<(PluginRegistrarFn, PathBuf)>,
     sess: &Session,
     metadata_loader: &dyn MetadataLoader,
     ident: Ident,
@@ -60,8 +61,8 @@ fn load_plugin(
 
     if let Some((lib, disambiguator)) = registrar {
         let symbol = sess.generate_plugin_registrar_symbol(disambiguator);
-        let fun = dylink_registrar(sess, ident.span, lib, symbol);
-        plugins.push(fun);
+        let fun = dylink_registrar(sess, ident.span, lib.clone(), symbol);
+        plugins.push((fun, lib));
     }
 }
 
@@ -7,12 +7,13 @@ use rustc::bug;
 use rustc::hir::map::Map;
 use rustc::lint;
 use rustc::middle::privacy::{AccessLevel, AccessLevels};
+use rustc::traits;
 use rustc::ty::fold::TypeVisitor;
 use rustc::ty::query::Providers;
 use rustc::ty::subst::InternalSubsts;
 use rustc::ty::{self, GenericParamDefKind, TraitRef, Ty, TyCtxt, TypeFoldable};
 use rustc_data_structures::fx::FxHashSet;
-use rustc_errors::struct_span_err;
+use rustc_errors::{struct_span_err, Applicability};
 use rustc_hir as hir;
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::{CrateNum, DefId, CRATE_DEF_INDEX, LOCAL_CRATE};
@@ -20,7 +21,7 @@ use rustc_hir::intravisit::{self, DeepVisitor, NestedVisitorMap, Visitor};
 use rustc_hir::{AssocItemKind, HirIdSet, Node, PatKind};
 use rustc_span::hygiene::Transparency;
 use rustc_span::symbol::{kw, sym};
-use rustc_span::Span;
+use rustc_span::{Span, DUMMY_SP};
 use syntax::ast::Ident;
 use syntax::attr;
 
@@ -1852,6 +1853,7 @@ impl DefIdVisitor<'tcx> for SearchInterfaceForPrivateItemsVisitor<'tcx> {
 
 struct PrivateItemsInPublicInterfacesVisitor<'a, 'tcx> {
     tcx: TyCtxt<'tcx>,
+    access_levels: &'a AccessLevels,
     has_pub_restricted: bool,
     old_error_set: &'a HirIdSet,
 }
@@ -1897,6 +1899,99 @@ impl<'a, 'tcx> PrivateItemsInPublicInterfacesVisitor<'a, 'tcx> {
         }
     }
 
+    /// Lints the `impl Trait` return type `item` of the exported function `fn_def_id` if its
+    /// hidden type contains a private type, and is `Send` or `Sync` without the bounds saying
+    /// so. Callers can rely on those auto traits all the same, and break when the private type
+    /// changes.
+    fn check_leaked_auto_traits(&self, item: &hir::Item<'_>, fn_def_id: DefId) {
+        let tcx = self.tcx;
+        let lint = lint::builtin::PRIVATE_IN_PUBLIC_IMPL_TRAIT;
+        let fn_hir_id = match tcx.hir().as_local_hir_id(fn_def_id) {
+            Some(hir_id) => hir_id,
+            None => return,
+        };
+        if !self.access_levels.is_exported(fn_hir_id) {
+            return;
+        }
+        let (level, src) = tcx.lint_level_at_node(lint, fn_hir_id);
+        if level == lint::Level::Allow {
+            lint::mark_allow_used(tcx.sess, src);
+            return;
+        }
+
+        let opaque_def_id = tcx.hir().local_def_id(item.hir_id);
+        let hidden_ty = tcx.type_of(opaque_def_id);
+        if hidden_ty.references_error() {
+            return;
+        }
+        // Callers can't name the type, so they only know it through the `impl Trait`.
+        let private_ty = hidden_ty.walk().find_map(|ty| match ty.kind {
+            ty::Adt(adt, _) if adt.did.is_local() => {
+                let (vis, vis_span, vis_descr) = def_id_visibility(tcx, adt.did);
+                if vis == ty::Visibility::Public { None } else { Some((ty, vis_span, vis_descr)) }
+            }
+            _ => None,
+        });
+        let (private_ty, vis_span, vis_descr) = match private_ty {
+            Some(private_ty) => private_ty,
+            None => return,
+        };
+
+        let predicates = tcx.predicates_of(opaque_def_id).predicates.iter().map(|&(p, _)| p);
+        let bounds: FxHashSet<_> = traits::elaborate_predicates(tcx, predicates.collect())
+            .filter_map(|predicate| match predicate {
+                ty::Predicate::Trait(trait_predicate) => Some(trait_predicate.def_id()),
+                _ => None,
+            })
+            .collect();
+        let param_env = tcx.param_env(opaque_def_id);
+        let leaked: Vec<_> = [(sym::send_trait, "Send"), (sym::sync_trait, "Sync")]
+            .iter()
+            .filter_map(|&(diagnostic_item, name)| {
+                let trait_def_id = tcx.get_diagnostic_item(diagnostic_item)?;
+                if bounds.contains(&trait_def_id) {
+                    return None;
+                }
+                let implemented = tcx.infer_ctxt().enter(|infcx| {
+                    traits::type_known_to_meet_bound_modulo_regions(
+                        &infcx,
+                        param_env,
+                        hidden_ty,
+                        trait_def_id,
+                        DUMMY_SP,
+                    )
+                });
+                if implemented { Some(name) } else { None }
+            })
+            .collect();
+        if leaked.is_empty() {
+            return;
+        }
+
+        let span = item.span;
+        let msg = format!(
+            "the `impl Trait` return type of `{}` leaks the auto traits of {} type `{}`",
+            tcx.def_path_str(fn_def_id),
+            vis_descr,
+            private_ty
+        );
+        let leaked_list = leaked.iter().map(|name| format!("`{}`", name)).collect::<Vec<_>>();
+        tcx.struct_span_lint_hir(lint, fn_hir_id, span, &msg)
+            .span_label(vis_span, format!("`{}` declared as {}", private_ty, vis_descr))
+            .note(&format!(
+                "callers can rely on the returned value being {}, and break when `{}` changes",
+                leaked_list.join(" and "),
+                private_ty
+            ))
+            .span_suggestion(
+                span.shrink_to_hi(),
+                "if this is part of the API, add the auto traits to the bounds",
+                format!(" + {}", leaked.join(" + ")),
+                Applicability::MaybeIncorrect,
+            )
+            .emit();
+    }
+
     fn check_assoc_item(
         &self,
         hir_id: hir::HirId,
@@ -1948,10 +2043,15 @@ impl<'a, 'tcx> Visitor<'tcx> for PrivateItemsInPublicInterfacesVisitor<'a, 'tcx>
             | hir::ItemKind::TyAlias(..) => {
                 self.check(item.hir_id, item_visibility).generics().predicates().ty();
             }
-            hir::ItemKind::OpaqueTy(..) => {
+            hir::ItemKind::OpaqueTy(ref opaque) => {
                 // `ty()` for opaque types is the underlying type,
                 // it's not a part of interface, so we skip it.
                 self.check(item.hir_id, item_visibility).generics().predicates();
+                if let (hir::OpaqueTyOrigin::FnReturn, Some(fn_def_id)) =
+                    (opaque.origin, opaque.impl_trait_fn)
+                {
+                    self.check_leaked_auto_traits(item, fn_def_id);
+                }
             }
             hir::ItemKind::Trait(.., trait_item_refs) => {
                 self.check(item.hir_id, item_visibility).generics().predicates();
@@ -2108,6 +2208,7 @@ fn check_private_in_public(tcx: TyCtxt<'_>, krate: CrateNum) {
     // Check for private types and traits in public interfaces.
     let mut visitor = PrivateItemsInPublicInterfacesVisitor {
         tcx,
+        access_levels: &access_levels,
         has_pub_restricted,
         old_error_set: &visitor.old_error_set,
     };
@@ -31,6 +31,8 @@ use rustc_data_structures::fx::FxHashSet;
 use rustc_errors::pluralize;
 use rustc_session::lint::BuiltinLintDiagnostics;
 use rustc_session::node_id::NodeMap;
+use rustc_span::source_map::SourceMap;
+use rustc_span::symbol::kw;
 use rustc_span::{MultiSpan, Span, DUMMY_SP};
 use syntax::ast;
 use syntax::visit::{self, Visitor};
@@ -105,6 +107,10 @@ impl<'a, 'b> Visitor<'a> for UnusedImportCheckVisitor<'a, 'b> {
             if item.vis.node.is_pub() || item.span.is_dummy() {
                 return;
             }
+
+            // Removing the whole item has to remove its attributes too, or they would end up
+            // applying to whatever comes next.
+            self.item_span = item.attrs.iter().fold(item.span, |acc, attr| acc.to(attr.span));
         }
 
         visit::walk_item(self, item);
@@ -134,10 +140,11 @@ enum UnusedSpanResult {
     Used,
     FlatUnused(Span, Span),
     NestedFullUnused(Vec<Span>, Span),
-    NestedPartialUnused(Vec<Span>, Vec<Span>),
+    NestedPartialUnused(Vec<Span>, Vec<(Span, String)>),
 }
 
 fn calc_unused_spans(
+    source_map: &SourceMap,
     unused_import: &UnusedImport<'_>,
     use_tree: &ast::UseTree,
     use_tree_id: ast::NodeId,
@@ -166,10 +173,14 @@ fn calc_unused_spans(
             let mut to_remove = Vec::new();
             let mut all_nested_unused = true;
             let mut previous_unused = false;
+            let mut fully_used = Vec::new();
+            let mut partially_used = false;
             for (pos, (use_tree, use_tree_id)) in nested.iter().enumerate() {
-                let remove = match calc_unused_spans(unused_import, use_tree, *use_tree_id) {
+                let result = calc_unused_spans(source_map, unused_import, use_tree, *use_tree_id);
+                let remove = match result {
                     UnusedSpanResult::Used => {
                         all_nested_unused = false;
+                        fully_used.push(use_tree);
                         None
                     }
                     UnusedSpanResult::FlatUnused(span, remove) => {
@@ -182,6 +193,7 @@ fn calc_unused_spans(
                     }
                     UnusedSpanResult::NestedPartialUnused(mut spans, mut to_remove_extra) => {
                         all_nested_unused = false;
+                        partially_used = true;
                         unused_spans.append(&mut spans);
                         to_remove.append(&mut to_remove_extra);
                         None
@@ -202,10 +214,10 @@ fn calc_unused_spans(
                     // Try to collapse adjacent spans into a single one. This prevents all cases of
                     // overlapping removals, which are not supported by rustfix
                     if previous_unused && !to_remove.is_empty() {
-                        let previous = to_remove.pop().unwrap();
-                        to_remove.push(previous.to(remove_span));
+                        let (previous, _) = to_remove.pop().unwrap();
+                        to_remove.push((previous.to(remove_span), String::new()));
                     } else {
-                        to_remove.push(remove_span);
+                        to_remove.push((remove_span, String::new()));
                     }
                 }
                 previous_unused = remove.is_some();
@@ -215,12 +227,48 @@ fn calc_unused_spans(
             } else if all_nested_unused {
                 UnusedSpanResult::NestedFullUnused(unused_spans, full_span)
             } else {
+                // If a single import is left in the braces, get rid of them too, rather than
+                // leaving `use foo::{bar};` behind.
+                if let ([remaining], false) = (&fully_used[..], partially_used) {
+                    if let Some(collapsed) = collapse_nested(source_map, use_tree, remaining) {
+                        to_remove = vec![(use_tree.span, collapsed)];
+                    }
+                }
                 UnusedSpanResult::NestedPartialUnused(unused_spans, to_remove)
             }
         }
     }
 }
 
+/// Returns what the nested `use_tree` can be replaced with once `remaining` is the only import
+/// left in its braces, e.g. `foo::bar` for `foo::{bar, baz}`.
+fn collapse_nested(
+    source_map: &SourceMap,
+    use_tree: &ast::UseTree,
+    remaining: &ast::UseTree,
+) -> Option<String> {
+    // `foo::{self}` can't be written as `foo::self`.
+    if let ast::UseTreeKind::Simple(..) = remaining.kind {
+        if let [segment] = &remaining.prefix.segments[..] {
+            if segment.ident.name == kw::SelfLower {
+                return None;
+            }
+        }
+    }
+    if use_tree.span.from_expansion() {
+        return None;
+    }
+
+    let snippet = source_map.span_to_snippet(use_tree.span).ok()?;
+    let remaining = source_map.span_to_snippet(remaining.span).ok()?;
+    let brace = snippet.find('{')?;
+    // Keep the braces rather than throwing away comments in them.
+    if snippet[brace..].contains("//") || snippet[brace..].contains("/*") {
+        return None;
+    }
+    Some(format!("{}{}", &snippet[..brace], remaining))
+}
+
 impl Resolver<'_> {
     crate fn check_unused(&mut self, krate: &ast::Crate) {
         for directive in self.potentially_unused_imports.iter() {
@@ -264,25 +312,33 @@ impl Resolver<'_> {
         };
         visit::walk_crate(&mut visitor, krate);
 
+        let source_map = visitor.r.session.source_map();
         for unused in visitor.unused_imports.values() {
-            let mut fixes = Vec::new();
-            let mut spans = match calc_unused_spans(unused, unused.use_tree, unused.use_tree_id) {
-                UnusedSpanResult::Used => continue,
-                UnusedSpanResult::FlatUnused(span, remove) => {
-                    fixes.push((remove, String::new()));
-                    vec![span]
-                }
-                UnusedSpanResult::NestedFullUnused(spans, remove) => {
-                    fixes.push((remove, String::new()));
-                    spans
-                }
-                UnusedSpanResult::NestedPartialUnused(spans, remove) => {
-                    for fix in &remove {
-                        fixes.push((*fix, String::new()));
-                    }
-                    spans
+            // Removing a whole item also removes the line it was on.
+            let remove_item = |remove: Span| {
+                if remove == unused.item_span {
+                    source_map.span_extend_to_whole_lines(remove)
+                } else {
+                    remove
                 }
             };
+            let mut fixes = Vec::new();
+            let mut spans =
+                match calc_unused_spans(source_map, unused, unused.use_tree, unused.use_tree_id) {
+                    UnusedSpanResult::Used => continue,
+                    UnusedSpanResult::FlatUnused(span, remove) => {
+                        fixes.push((remove_item(remove), String::new()));
+                        vec![span]
+                    }
+                    UnusedSpanResult::NestedFullUnused(spans, remove) => {
+                        fixes.push((remove_item(remove), String::new()));
+                        spans
+                    }
+                    UnusedSpanResult::NestedPartialUnused(spans, mut remove) => {
+                        fixes.append(&mut remove);
+                        spans
+                    }
+                };
 
             let len = spans.len();
             spans.sort();
@@ -305,7 +361,7 @@ impl Resolver<'_> {
                 }
             );
 
-            let fix_msg = if fixes.len() == 1 && fixes[0].0 == unused.item_span {
+            let fix_msg = if fixes.len() == 1 && fixes[0].0.contains(unused.item_span) {
                 "remove the whole `use` item"
             } else if spans.len() > 1 {
                 "remove the unused imports"
@@ -21,6 +21,7 @@ use rustc_span::symbol::{sym, Symbol};
 
 use rustc_errors::emitter::HumanReadableErrorType;
 use rustc_errors::{ColorConfig, FatalError, Handler, HandlerFlags};
+use rustc_serialize::json::Json;
 
 use getopts;
 
@@ -28,7 +29,9 @@ use std::collections::btree_map::{
     Iter as BTreeMapIter, Keys as BTreeMapKeysIter, Values as BTreeMapValuesIter,
 };
 use std::collections::{BTreeMap, BTreeSet};
+use std::env;
 use std::fmt;
+use std::fs;
 use std::iter::{self, FromIterator};
 use std::path::{Path, PathBuf};
 use std::str::{self, FromStr};
@@ -156,6 +159,17 @@ pub enum SymbolManglingVersion {
 
 impl_stable_hash_via_hash!(SymbolManglingVersion);
 
+/// How a lint warning tells where to put an `#[allow(..)]` that allows it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AllowSuggestions {
+    /// A one-line help naming the enclosing node to put it on.
+    Help,
+    /// A rendered suggestion inserting it, for editors to offer as a quick fix.
+    Rendered,
+    /// Nothing, as in the UI tests that aren't about it.
+    Off,
+}
+
 #[derive(Clone, Copy, PartialEq, Hash)]
 pub enum DebugInfo {
     None,
@@ -173,6 +187,12 @@ pub enum OutputType {
     Object,
     Exe,
     DepInfo,
+    /// A Checkstyle XML report of the lints emitted.
+    LintReport,
+    /// A JUnit XML report of the lints emitted, each lint being a failed test case.
+    LintReportJunit,
+    /// A JSON listing of every lint emitted, for tools comparing the lints of two builds.
+    LintMetadata,
 }
 
 impl_stable_hash_via_hash!(OutputType);
@@ -180,7 +200,12 @@ impl_stable_hash_via_hash!(OutputType);
 impl OutputType {
     fn is_compatible_with_codegen_units_and_single_output_file(&self) -> bool {
         match *self {
-            OutputType::Exe | OutputType::DepInfo | OutputType::Metadata => true,
+            OutputType::Exe
+            | OutputType::DepInfo
+            | OutputType::Metadata
+            | OutputType::LintReport
+            | OutputType::LintReportJunit
+            | OutputType::LintMetadata => true,
             OutputType::Bitcode
             | OutputType::Assembly
             | OutputType::LlvmAssembly
@@ -189,7 +214,7 @@ impl OutputType {
         }
     }
 
-    fn shorthand(&self) -> &'static str {
+    pub fn shorthand(&self) -> &'static str {
         match *self {
             OutputType::Bitcode => "llvm-bc",
             OutputType::Assembly => "asm",
@@ -199,6 +224,9 @@ impl OutputType {
             OutputType::Metadata => "metadata",
             OutputType::Exe => "link",
             OutputType::DepInfo => "dep-info",
+            OutputType::LintReport => "lint-report",
+            OutputType::LintReportJunit => "lint-report-junit",
+            OutputType::LintMetadata => "lint-metadata",
         }
     }
 
@@ -212,13 +240,16 @@ impl OutputType {
             "metadata" => OutputType::Metadata,
             "link" => OutputType::Exe,
             "dep-info" => OutputType::DepInfo,
+            "lint-report" => OutputType::LintReport,
+            "lint-report-junit" => OutputType::LintReportJunit,
+            "lint-metadata" => OutputType::LintMetadata,
             _ => return None,
         })
     }
 
     fn shorthands_display() -> String {
         format!(
-            "`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`",
+            "`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`",
             OutputType::Bitcode.shorthand(),
             OutputType::Assembly.shorthand(),
             OutputType::LlvmAssembly.shorthand(),
@@ -227,6 +258,9 @@ impl OutputType {
             OutputType::Metadata.shorthand(),
             OutputType::Exe.shorthand(),
             OutputType::DepInfo.shorthand(),
+            OutputType::LintReport.shorthand(),
+            OutputType::LintReportJunit.shorthand(),
+            OutputType::LintMetadata.shorthand(),
         )
     }
 
@@ -239,6 +273,9 @@ impl OutputType {
             OutputType::Object => "o",
             OutputType::Metadata => "rmeta",
             OutputType::DepInfo => "d",
+            OutputType::LintReport => "checkstyle.xml",
+            OutputType::LintReportJunit => "junit.xml",
+            OutputType::LintMetadata => "lints.json",
             OutputType::Exe => "",
         }
     }
@@ -257,6 +294,8 @@ pub enum ErrorOutputType {
         /// human output.
         json_rendered: HumanReadableErrorType,
     },
+    /// GitHub Actions workflow commands, which show up as annotations on pull requests.
+    Github,
 }
 
 impl Default for ErrorOutputType {
@@ -307,7 +346,11 @@ impl OutputTypes {
             | OutputType::Mir
             | OutputType::Object
             | OutputType::Exe => true,
-            OutputType::Metadata | OutputType::DepInfo => false,
+            OutputType::Metadata
+            | OutputType::DepInfo
+            | OutputType::LintReport
+            | OutputType::LintReportJunit
+            | OutputType::LintMetadata => false,
         })
     }
 }
@@ -390,6 +433,8 @@ pub enum PrintRequest {
     TlsModels,
     TargetSpec,
     NativeStaticLibs,
+    LintsJson,
+    LintGroupsJson,
 }
 
 #[derive(Copy, Clone)]
@@ -528,7 +573,13 @@ impl Default for Options {
             debuginfo: DebugInfo::None,
             lint_opts: Vec::new(),
             lint_cap: None,
+            deny_lints_from_env: None,
+            lint_levels_from_env: Vec::new(),
+            lint_changed: None,
+            lint_spec: Vec::new(),
+            extern_lint_levels: Vec::new(),
             describe_lints: false,
+            lint_help_filter: LintHelpFilter::default(),
             output_types: OutputTypes(BTreeMap::new()),
             search_paths: vec![],
             maybe_sysroot: None,
@@ -606,6 +657,7 @@ impl DebuggingOptions {
             report_delayed_bugs: self.report_delayed_bugs,
             external_macro_backtrace: self.external_macro_backtrace,
             deduplicate_diagnostics: self.deduplicate_diagnostics.unwrap_or(true),
+            sort_lints: self.sort_lints,
         }
     }
 }
@@ -894,7 +946,8 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
             "Compiler information to print on stdout",
             "[crate-name|file-names|sysroot|cfg|target-list|\
              target-cpus|target-features|relocation-models|\
-             code-models|tls-models|target-spec-json|native-static-libs]",
+             code-models|tls-models|target-spec-json|native-static-libs|\
+             lints-json|lint-groups-json]",
         ),
         opt::flagmulti_s("g", "", "Equivalent to -C debuginfo=2"),
         opt::flagmulti_s("O", "", "Equivalent to -C opt-level=2"),
@@ -978,6 +1031,34 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
             "Remap source names in all output (compiler messages and output files)",
             "FROM=TO",
         ),
+        opt::opt(
+            "",
+            "deny-lints-from-env",
+            "Deny the lints and lint groups listed, separated by commas, in the environment \
+             variable VAR",
+            "VAR",
+        ),
+        opt::opt(
+            "",
+            "lint-changed",
+            "Only report lint warnings on the lines listed in PATH, a JSON object mapping file \
+             paths to arrays of line numbers and `[first, last]` line ranges",
+            "PATH",
+        ),
+        opt::opt(
+            "",
+            "lint-spec",
+            "Set lint levels from PATH, a JSON object mapping `rust` and lint tool names to \
+             objects of lint names and levels, as in a Cargo `[lints]` table",
+            "PATH",
+        ),
+        opt::multi(
+            "",
+            "extern-lint-level",
+            "Set the level of lints raised in code expanded from the macros of, or inlined \
+             from, the crate CRATE",
+            "CRATE=LEVEL:LINT[,LINT...]",
+        ),
     ]);
     opts
 }
@@ -991,7 +1072,7 @@ pub fn get_cmd_lint_options(
 
     for &level in &[lint::Allow, lint::Warn, lint::Deny, lint::Forbid] {
         for lint_name in matches.opt_strs(level.as_str()) {
-            if lint_name == "help" {
+            if lint_name == "help" || lint_name.starts_with("help=") {
                 describe_lints = true;
             } else {
                 lint_opts.push((lint_name.replace("-", "_"), level));
@@ -1006,6 +1087,251 @@ pub fn get_cmd_lint_options(
     (lint_opts, describe_lints, lint_cap)
 }
 
+/// Reads the lints to deny from the environment variable named by `--deny-lints-from-env`. An
+/// unset variable denies nothing, so the flag can be passed unconditionally.
+pub fn get_cmd_deny_lints_from_env(matches: &getopts::Matches) -> Option<(String, Vec<String>)> {
+    let var = matches.opt_str("deny-lints-from-env")?;
+    let lint_names = env::var(&var)
+        .unwrap_or_default()
+        .split(',')
+        .map(|lint_name| lint_name.trim())
+        .filter(|lint_name| !lint_name.is_empty())
+        .map(|lint_name| lint_name.replace("-", "_"))
+        .collect();
+    Some((var, lint_names))
+}
+
+/// The environment variable read by `get_lint_levels_from_env`.
+pub const LINT_LEVELS_ENV_VAR: &str = "RUSTC_LINT_LEVELS";
+
+/// Reads the `LEVEL:LINT` entries of the `RUSTC_LINT_LEVELS` environment variable, such as
+/// `warn:unused,deny:unsafe_code`, which set lint levels for a single run without touching the
+/// flags a build script passes.
+pub fn get_lint_levels_from_env(error_format: ErrorOutputType) -> Vec<(String, lint::Level)> {
+    let value = match env::var(LINT_LEVELS_ENV_VAR) {
+        Ok(value) => value,
+        Err(_) => return Vec::new(),
+    };
+    let mut levels = Vec::new();
+    for entry in value.split(',').map(|entry| entry.trim()).filter(|entry| !entry.is_empty()) {
+        let mut parts = entry.splitn(2, ':');
+        let level = parts.next().unwrap().trim();
+        let lint_name = match parts.next().map(|lint_name| lint_name.trim()) {
+            Some(lint_name) if !lint_name.is_empty() => lint_name,
+            _ => early_error(
+                error_format,
+                &format!(
+                    "`{}` must be a comma-separated list of `LEVEL:LINT` (instead had `{}`)",
+                    LINT_LEVELS_ENV_VAR, entry
+                ),
+            ),
+        };
+        let level = lint::Level::from_str(level).unwrap_or_else(|| {
+            early_error(
+                error_format,
+                &format!("unknown lint level in `{}`: `{}`", LINT_LEVELS_ENV_VAR, level),
+            )
+        });
+        levels.push((lint_name.replace("-", "_"), level));
+    }
+    levels
+}
+
+/// Parses the `--extern-lint-level CRATE=LEVEL:LINT[,LINT...]` flags into a `(crate, lint,
+/// level)` triple for every lint named.
+pub fn get_cmd_extern_lint_levels(
+    matches: &getopts::Matches,
+    error_format: ErrorOutputType,
+) -> Vec<(String, String, lint::Level)> {
+    let mut levels = Vec::new();
+    for arg in matches.opt_strs("extern-lint-level") {
+        let invalid = || -> ! {
+            early_error(
+                error_format,
+                &format!(
+                    "`--extern-lint-level` must be of the form `CRATE=LEVEL:LINT[,LINT...]` \
+                     (instead was `{}`)",
+                    arg
+                ),
+            )
+        };
+        let mut parts = arg.splitn(2, '=');
+        let krate = parts.next().unwrap();
+        let mut parts = parts.next().unwrap_or_else(|| invalid()).splitn(2, ':');
+        let level = parts.next().unwrap();
+        let lint_names = parts.next().unwrap_or_else(|| invalid());
+        if krate.is_empty() {
+            invalid();
+        }
+        let level = lint::Level::from_str(level).unwrap_or_else(|| {
+            early_error(error_format, &format!("unknown lint level: `{}`", level))
+        });
+        for lint_name in lint_names.split(',') {
+            if lint_name.is_empty() {
+                invalid();
+            }
+            levels.push((krate.replace("-", "_"), lint_name.replace("-", "_"), level));
+        }
+    }
+    levels
+}
+
+/// A lint level set in the file given to `--lint-spec`.
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub struct LintSpecEntry {
+    /// The tool owning the lint, e.g. `clippy`, or `None` for rustc's own lints.
+    pub tool: Option<String>,
+    pub name: String,
+    pub level: lint::Level,
+    pub priority: i64,
+    /// The names the naming convention lints let through, for names imposed from outside such
+    /// as those of FFI functions. A `*` in a name matches any run of characters.
+    pub allowed_names: Vec<String>,
+}
+
+/// Reads the lint levels from the file given to `--lint-spec`, which has the shape of a Cargo
+/// `[lints]` table:
+///
+/// ```json
+/// {
+///     "rust": { "unsafe_code": "forbid", "unused": { "level": "warn", "priority": -1 } },
+///     "clippy": { "enum_glob_use": "deny" }
+/// }
+/// ```
+///
+/// The table of `non_camel_case_types`, `non_snake_case`, `non_upper_case_globals` or
+/// `upper_case_acronyms` can also list `allowed-names`, e.g.
+/// `{ "level": "deny", "allowed-names": ["DllMain", "FFI_*"] }`.
+///
+/// The entries are returned in the order they apply, lowest priority first, so that a group can
+/// be given a lower priority than the lints in it that it would otherwise override. Entries with
+/// the same priority are ordered by tool and lint name.
+pub fn get_cmd_lint_spec(
+    matches: &getopts::Matches,
+    error_format: ErrorOutputType,
+) -> Vec<LintSpecEntry> {
+    let path = match matches.opt_str("lint-spec") {
+        Some(path) => PathBuf::from(path),
+        None => return Vec::new(),
+    };
+    let invalid = |msg: &str| -> ! {
+        early_error(
+            error_format,
+            &format!("invalid `--lint-spec` file `{}`: {}", path.display(), msg),
+        )
+    };
+
+    let contents = fs::read_to_string(&path).unwrap_or_else(|e| invalid(&e.to_string()));
+    let tools = match Json::from_str(&contents) {
+        Ok(Json::Object(tools)) => tools,
+        Ok(_) => invalid("expected an object mapping tool names to lint tables"),
+        Err(e) => invalid(&e.to_string()),
+    };
+
+    let mut entries = Vec::new();
+    for (tool, lints) in tools {
+        let lints = match lints {
+            Json::Object(lints) => lints,
+            _ => invalid(&format!("the `{}` lint table is not an object", tool)),
+        };
+        let tool = if tool == "rust" { None } else { Some(tool) };
+        for (name, config) in lints {
+            let full_name = match tool {
+                Some(ref tool) => format!("{}::{}", tool, name),
+                None => name.clone(),
+            };
+            let (level, priority, allowed_names) = match config {
+                Json::String(ref level) => (level.as_str(), 0, Vec::new()),
+                Json::Object(ref config) => {
+                    let level = match config.get("level") {
+                        Some(Json::String(level)) => level.as_str(),
+                        _ => invalid(&format!("`{}` has no `level`", full_name)),
+                    };
+                    let priority = match config.get("priority") {
+                        None => 0,
+                        Some(priority) => priority.as_i64().unwrap_or_else(|| {
+                            invalid(&format!("the priority of `{}` is not an integer", full_name))
+                        }),
+                    };
+                    let not_strings =
+                        format!("the allowed names of `{}` are not strings", full_name);
+                    let allowed_names = match config.get("allowed-names") {
+                        None => Vec::new(),
+                        Some(Json::Array(names)) => names
+                            .iter()
+                            .map(|name| name.as_string().map(str::to_string))
+                            .collect::<Option<_>>()
+                            .unwrap_or_else(|| invalid(&not_strings)),
+                        Some(_) => invalid(&not_strings),
+                    };
+                    (level, priority, allowed_names)
+                }
+                _ => invalid(&format!("the level of `{}` is not a string or object", full_name)),
+            };
+            let level = lint::Level::from_str(level).unwrap_or_else(|| {
+                invalid(&format!("unknown lint level `{}` for `{}`", level, full_name))
+            });
+            entries.push(LintSpecEntry {
+                tool: tool.clone(),
+                name: name.replace("-", "_"),
+                level,
+                priority,
+                allowed_names,
+            });
+        }
+    }
+
+    // The sort is stable, and `Json::Object` iterates in key order.
+    entries.sort_by_key(|entry| entry.priority);
+    entries
+}
+
+/// Which lints `-W help` lists, narrowed with `-W help=<filter>,...`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LintHelpFilter {
+    /// Only list the members of these lint groups (`-W help=unused,nonstandard-style`).
+    pub groups: Vec<String>,
+    /// Only list the lints of this tool (`-W help=tool:clippy`).
+    pub tool: Option<String>,
+    /// Whether to list lints registered by plugins (disabled with `-W help=no-plugins`).
+    pub plugins: bool,
+}
+
+impl Default for LintHelpFilter {
+    fn default() -> LintHelpFilter {
+        LintHelpFilter { groups: Vec::new(), tool: None, plugins: true }
+    }
+}
+
+pub fn get_cmd_lint_help_filter(
+    matches: &getopts::Matches,
+    error_format: ErrorOutputType,
+) -> LintHelpFilter {
+    let mut filter = LintHelpFilter::default();
+
+    for &level in &[lint::Allow, lint::Warn, lint::Deny, lint::Forbid] {
+        for lint_name in matches.opt_strs(level.as_str()) {
+            if !lint_name.starts_with("help=") {
+                continue;
+            }
+            for part in lint_name["help=".len()..].split(',') {
+                if part == "no-plugins" {
+                    filter.plugins = false;
+                } else if part.starts_with("tool:") {
+                    filter.tool = Some(part["tool:".len()..].to_string());
+                } else if !part.is_empty() {
+                    filter.groups.push(part.to_lowercase().replace("-", "_"));
+                } else {
+                    let msg = format!("empty lint help filter in `{}`", lint_name);
+                    early_error(error_format, &msg);
+                }
+            }
+        }
+    }
+
+    filter
+}
+
 /// Parses the `--color` flag.
 pub fn parse_color(matches: &getopts::Matches) -> ColorConfig {
     match matches.opt_str("color").as_ref().map(|s| &s[..]) {
@@ -1082,6 +1408,7 @@ pub fn parse_error_format(
             Some("json") => ErrorOutputType::Json { pretty: false, json_rendered },
             Some("pretty-json") => ErrorOutputType::Json { pretty: true, json_rendered },
             Some("short") => ErrorOutputType::HumanReadable(HumanReadableErrorType::Short(color)),
+            Some("github") => ErrorOutputType::Github,
 
             Some(arg) => early_error(
                 ErrorOutputType::HumanReadable(HumanReadableErrorType::Default(color)),
@@ -1164,6 +1491,12 @@ fn check_debug_option_stability(
                 "`--error-format=human-annotate-rs` is unstable",
             );
         }
+        if let ErrorOutputType::Github = error_format {
+            early_error(
+                ErrorOutputType::Json { pretty: false, json_rendered },
+                "`--error-format=github` is unstable",
+            );
+        }
     }
 }
 
@@ -1193,6 +1526,18 @@ fn parse_output_types(
             }
         }
     };
+    if !debugging_opts.unstable_options {
+        let unstable_output_types =
+            [OutputType::LintReport, OutputType::LintReportJunit, OutputType::LintMetadata];
+        for output_type in &unstable_output_types {
+            if output_types.contains_key(output_type) {
+                early_error(
+                    error_format,
+                    &format!("`--emit {}` is unstable", output_type.shorthand()),
+                );
+            }
+        }
+    }
     if output_types.is_empty() {
         output_types.insert(OutputType::Exe, None);
     }
@@ -1336,6 +1681,28 @@ fn collect_print_requests(
                 );
             }
         }
+        "lints-json" => {
+            if dopts.unstable_options {
+                PrintRequest::LintsJson
+            } else {
+                early_error(
+                    error_format,
+                    "the `-Z unstable-options` flag must also be passed to \
+                     enable the lints-json print option",
+                );
+            }
+        }
+        "lint-groups-json" => {
+            if dopts.unstable_options {
+                PrintRequest::LintGroupsJson
+            } else {
+                early_error(
+                    error_format,
+                    "the `-Z unstable-options` flag must also be passed to \
+                     enable the lint-groups-json print option",
+                );
+            }
+        }
         req => early_error(error_format, &format!("unknown print request `{}`", req)),
     }));
 
@@ -1634,6 +2001,12 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         .unwrap_or_else(|e| early_error(error_format, &e[..]));
 
     let (lint_opts, describe_lints, lint_cap) = get_cmd_lint_options(matches, error_format);
+    let lint_help_filter = get_cmd_lint_help_filter(matches, error_format);
+    let deny_lints_from_env = get_cmd_deny_lints_from_env(matches);
+    let lint_levels_from_env = get_lint_levels_from_env(error_format);
+    let lint_changed = matches.opt_str("lint-changed").map(PathBuf::from);
+    let lint_spec = get_cmd_lint_spec(matches, error_format);
+    let extern_lint_levels = get_cmd_extern_lint_levels(matches, error_format);
 
     let mut debugging_opts = build_debugging_options(matches, error_format);
     check_debug_option_stability(&debugging_opts, error_format, json_rendered);
@@ -1708,7 +2081,13 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         debuginfo,
         lint_opts,
         lint_cap,
+        deny_lints_from_env,
+        lint_levels_from_env,
+        lint_changed,
+        lint_spec,
+        extern_lint_levels,
         describe_lints,
+        lint_help_filter,
         output_types,
         search_paths,
         maybe_sysroot: sysroot_opt,
@@ -1971,8 +2350,8 @@ impl PpMode {
 /// how the hash should be calculated when adding a new command-line argument.
 crate mod dep_tracking {
     use super::{
-        CrateType, DebugInfo, ErrorOutputType, LinkerPluginLto, LtoCli, OptLevel, OutputTypes,
-        Passes, Sanitizer, SwitchWithOptPath, SymbolManglingVersion,
+        CrateType, DebugInfo, ErrorOutputType, LinkerPluginLto, LintSpecEntry, LtoCli, OptLevel,
+        OutputTypes, Passes, Sanitizer, SwitchWithOptPath, SymbolManglingVersion,
     };
     use crate::lint;
     use crate::utils::NativeLibraryKind;
@@ -2024,6 +2403,7 @@ crate mod dep_tracking {
     impl_dep_tracking_hash_via_hash!(Option<usize>);
     impl_dep_tracking_hash_via_hash!(Option<String>);
     impl_dep_tracking_hash_via_hash!(Option<(String, u64)>);
+    impl_dep_tracking_hash_via_hash!(Option<(String, Vec<String>)>);
     impl_dep_tracking_hash_via_hash!(Option<Vec<String>>);
     impl_dep_tracking_hash_via_hash!(Option<MergeFunctions>);
     impl_dep_tracking_hash_via_hash!(Option<PanicStrategy>);
@@ -2049,11 +2429,14 @@ crate mod dep_tracking {
     impl_dep_tracking_hash_via_hash!(LinkerPluginLto);
     impl_dep_tracking_hash_via_hash!(SwitchWithOptPath);
     impl_dep_tracking_hash_via_hash!(SymbolManglingVersion);
+    impl_dep_tracking_hash_via_hash!(Vec<LintSpecEntry>);
 
     impl_dep_tracking_hash_for_sortable_vec_of!(String);
+    impl_dep_tracking_hash_for_sortable_vec_of!(u64);
     impl_dep_tracking_hash_for_sortable_vec_of!(PathBuf);
     impl_dep_tracking_hash_for_sortable_vec_of!(CrateType);
     impl_dep_tracking_hash_for_sortable_vec_of!((String, lint::Level));
+    impl_dep_tracking_hash_for_sortable_vec_of!((String, String, lint::Level));
     impl_dep_tracking_hash_for_sortable_vec_of!((
         String,
         Option<String>,
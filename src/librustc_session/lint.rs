@@ -74,6 +74,10 @@ pub struct Lint {
     /// e.g., "imports that are never used"
     pub desc: &'static str,
 
+    /// The doc comment of the `declare_lint!` invocation, if any, explaining the lint at length
+    /// and usually with an example. Each line keeps the space that followed `///`.
+    pub doc: &'static str,
+
     /// Starting at the given edition, default to the given lint level. If this is `None`, then use
     /// `default_level`.
     pub edition_lint_opts: Option<(Edition, Level)>,
@@ -102,6 +106,7 @@ impl Lint {
             name: "",
             default_level: Level::Forbid,
             desc: "",
+            doc: "",
             edition_lint_opts: None,
             is_plugin: false,
             report_in_external_macro: false,
@@ -267,17 +272,19 @@ impl LintBuffer {
 /// Declares a static item of type `&'static Lint`.
 #[macro_export]
 macro_rules! declare_lint {
-    ($vis: vis $NAME: ident, $Level: ident, $desc: expr) => (
+    ($(#[doc = $doc:literal])* $vis: vis $NAME: ident, $Level: ident, $desc: expr) => (
         $crate::declare_lint!(
-            $vis $NAME, $Level, $desc,
+            $(#[doc = $doc])* $vis $NAME, $Level, $desc,
         );
     );
-    ($vis: vis $NAME: ident, $Level: ident, $desc: expr,
+    ($(#[doc = $doc:literal])* $vis: vis $NAME: ident, $Level: ident, $desc: expr,
      $(@future_incompatible = $fi:expr;)? $($v:ident),*) => (
+        $(#[doc = $doc])*
         $vis static $NAME: &$crate::lint::Lint = &$crate::lint::Lint {
             name: stringify!($NAME),
             default_level: $crate::lint::$Level,
             desc: $desc,
+            doc: concat!($($doc, "\n"),*),
             edition_lint_opts: None,
             is_plugin: false,
             $($v: true,)*
@@ -285,13 +292,15 @@ macro_rules! declare_lint {
             ..$crate::lint::Lint::default_fields_for_macro()
         };
     );
-    ($vis: vis $NAME: ident, $Level: ident, $desc: expr,
+    ($(#[doc = $doc:literal])* $vis: vis $NAME: ident, $Level: ident, $desc: expr,
      $lint_edition: expr => $edition_level: ident
     ) => (
+        $(#[doc = $doc])*
         $vis static $NAME: &$crate::lint::Lint = &$crate::lint::Lint {
             name: stringify!($NAME),
             default_level: $crate::lint::$Level,
             desc: $desc,
+            doc: concat!($($doc, "\n"),*),
             edition_lint_opts: Some(($lint_edition, $crate::lint::Level::$edition_level)),
             report_in_external_macro: false,
             is_plugin: false,
@@ -321,6 +330,7 @@ macro_rules! declare_tool_lint {
             name: &concat!(stringify!($tool), "::", stringify!($NAME)),
             default_level: $crate::lint::$Level,
             desc: $desc,
+            doc: "",
             edition_lint_opts: None,
             report_in_external_macro: $external,
             future_incompatible: None,
@@ -342,6 +352,12 @@ pub type LintArray = Vec<&'static Lint>;
 
 pub trait LintPass {
     fn name(&self) -> &'static str;
+
+    /// The lints the pass was declared with, for `-Z lint-coverage`. Passes implementing this
+    /// trait by hand, without `declare_lint_pass!` or `impl_lint_pass!`, declare none.
+    fn lints(&self) -> LintArray {
+        Vec::new()
+    }
 }
 
 /// Implements `LintPass for $name` with the given list of `Lint` statics.
@@ -350,6 +366,7 @@ macro_rules! impl_lint_pass {
     ($name:ident => [$($lint:expr),* $(,)?]) => {
         impl $crate::lint::LintPass for $name {
             fn name(&self) -> &'static str { stringify!($name) }
+            fn lints(&self) -> $crate::lint::LintArray { $name::get_lints() }
         }
         impl $name {
             pub fn get_lints() -> $crate::lint::LintArray { $crate::lint_array!($($lint),*) }
@@ -168,6 +168,13 @@ declare_lint! {
     };
 }
 
+declare_lint! {
+    pub PRIVATE_IN_PUBLIC_IMPL_TRAIT,
+    Allow,
+    "detect private types whose auto traits leak through the `impl Trait` return types of public \
+     functions"
+}
+
 declare_lint! {
     pub EXPORTED_PRIVATE_DEPENDENCIES,
     Warn,
@@ -275,6 +282,48 @@ declare_lint! {
     "functions that cannot return without calling themselves"
 }
 
+declare_lint! {
+    pub FFI_UNWIND,
+    Warn,
+    "functions with a non-Rust ABI that may unwind"
+}
+
+declare_lint! {
+    pub LARGE_FUTURES,
+    Warn,
+    "futures and generators whose state machine exceeds `-Z large-future-threshold` bytes"
+}
+
+declare_lint! {
+    pub LARGE_STACK_FRAMES,
+    Warn,
+    "functions whose stack frame is estimated to exceed `-Z stack-frame-threshold` bytes"
+}
+
+declare_lint! {
+    pub GENERIC_DIVISION_BY_ZERO,
+    Warn,
+    "divisions by a generic constant that is zero for some instantiation"
+}
+
+declare_lint! {
+    pub REACHABLE_PANICS,
+    Allow,
+    "functions from which a panic can be reached after monomorphization"
+}
+
+declare_lint! {
+    pub EXCESSIVE_MONOMORPHIZATION,
+    Allow,
+    "generic functions instantiated more than `-Z instantiation-threshold` times"
+}
+
+declare_lint! {
+    pub REACHABLE_ALLOCATIONS,
+    Allow,
+    "public functions from which the global allocator can be reached after monomorphization"
+}
+
 declare_lint! {
     pub SINGLE_USE_LIFETIMES,
     Allow,
@@ -464,6 +513,41 @@ declare_lint! {
     };
 }
 
+declare_lint! {
+    /// The `unused_allows` lint detects `allow` attributes that did not suppress any lint.
+    ///
+    /// ```rust
+    /// #![warn(unused_allows)]
+    ///
+    /// #[allow(dead_code)]
+    /// pub fn used() {}
+    /// ```
+    ///
+    /// Such attributes are usually left over from code that was since fixed, and hide the lint
+    /// if the problem comes back somewhere else in their scope.
+    pub UNUSED_ALLOWS,
+    Allow,
+    "detects `allow` attributes that did not suppress any lint"
+}
+
+declare_lint! {
+    /// The `expired_lint_attributes` lint detects lint attributes whose `expires` date or Rust
+    /// version has passed.
+    ///
+    /// ```rust
+    /// #![feature(lint_expiry)]
+    ///
+    /// #[allow(dead_code, expires = "2020-01-31")]
+    /// fn unused() {}
+    /// ```
+    ///
+    /// The attribute keeps applying after it expires, so that the build doesn't break on the
+    /// day; the lint is the reminder to fix the code it covers, or to push the date back.
+    pub EXPIRED_LINT_ATTRIBUTES,
+    Warn,
+    "detects lint attributes past their `expires` date or Rust version"
+}
+
 declare_lint_pass! {
     /// Does nothing as a lint pass, but registers some `Lint`s
     /// that are used by other parts of the compiler.
@@ -489,6 +573,7 @@ declare_lint_pass! {
         TRIVIAL_CASTS,
         TRIVIAL_NUMERIC_CASTS,
         PRIVATE_IN_PUBLIC,
+        PRIVATE_IN_PUBLIC_IMPL_TRAIT,
         EXPORTED_PRIVATE_DEPENDENCIES,
         PUB_USE_OF_PRIVATE_EXTERN_CRATE,
         INVALID_TYPE_PARAM_DEFAULT,
@@ -503,6 +588,13 @@ declare_lint_pass! {
         UNUSED_UNSAFE,
         UNUSED_MUT,
         UNCONDITIONAL_RECURSION,
+        FFI_UNWIND,
+        LARGE_FUTURES,
+        LARGE_STACK_FRAMES,
+        GENERIC_DIVISION_BY_ZERO,
+        REACHABLE_PANICS,
+        REACHABLE_ALLOCATIONS,
+        EXCESSIVE_MONOMORPHIZATION,
         SINGLE_USE_LIFETIMES,
         UNUSED_LIFETIMES,
         UNUSED_LABELS,
@@ -526,5 +618,7 @@ declare_lint_pass! {
         MUTABLE_BORROW_RESERVATION_CONFLICT,
         INDIRECT_STRUCTURAL_MATCH,
         SOFT_UNSTABLE,
+        UNUSED_ALLOWS,
+        EXPIRED_LINT_ATTRIBUTES,
     ]
 }
@@ -91,7 +91,18 @@ top_level_options!(
         debuginfo: DebugInfo [TRACKED],
         lint_opts: Vec<(String, lint::Level)> [TRACKED],
         lint_cap: Option<lint::Level> [TRACKED],
+        // The environment variable named by `--deny-lints-from-env`, and the lints it lists.
+        deny_lints_from_env: Option<(String, Vec<String>)> [TRACKED],
+        // The lint levels set by the `RUSTC_LINT_LEVELS` environment variable.
+        lint_levels_from_env: Vec<(String, lint::Level)> [TRACKED],
+        // The file given to `--lint-changed`, restricting lints to the lines it lists.
+        lint_changed: Option<PathBuf> [TRACKED],
+        // The lint levels from the file given to `--lint-spec`, in the order they apply.
+        lint_spec: Vec<LintSpecEntry> [TRACKED],
+        // The `(crate, lint, level)` triples of `--extern-lint-level`.
+        extern_lint_levels: Vec<(String, String, lint::Level)> [TRACKED],
         describe_lints: bool [UNTRACKED],
+        lint_help_filter: LintHelpFilter [UNTRACKED],
         output_types: OutputTypes [TRACKED],
         search_paths: Vec<SearchPath> [UNTRACKED],
         libs: Vec<(String, Option<String>, Option<NativeLibraryKind>)> [TRACKED],
@@ -283,12 +294,16 @@ macro_rules! options {
             Some("one of: `disabled`, `trampolines`, or `aliases`");
         pub const parse_symbol_mangling_version: Option<&str> =
             Some("either `legacy` or `v0` (RFC 2603)");
+        pub const parse_allow_suggestions: Option<&str> =
+            Some("one of: `help`, `rendered`, or `off`");
+        pub const parse_pointer_widths: Option<&str> =
+            Some("a comma-separated list of `16`, `32` and `64`");
     }
 
     #[allow(dead_code)]
     mod $mod_set {
         use super::{$struct_name, Passes, Sanitizer, LtoCli, LinkerPluginLto, SwitchWithOptPath,
-            SymbolManglingVersion};
+            SymbolManglingVersion, AllowSuggestions};
         use rustc_target::spec::{LinkerFlavor, MergeFunctions, PanicStrategy, RelroLevel};
         use std::path::PathBuf;
         use std::str::FromStr;
@@ -609,6 +624,30 @@ macro_rules! options {
             };
             true
         }
+
+        fn parse_allow_suggestions(slot: &mut AllowSuggestions, v: Option<&str>) -> bool {
+            *slot = match v {
+                Some("help") => AllowSuggestions::Help,
+                Some("rendered") => AllowSuggestions::Rendered,
+                Some("off") => AllowSuggestions::Off,
+                _ => return false,
+            };
+            true
+        }
+
+        fn parse_pointer_widths(slot: &mut Vec<u64>, v: Option<&str>) -> bool {
+            let widths = match v {
+                Some(s) => s.split(',').map(|width| width.parse().ok()).collect::<Option<Vec<_>>>(),
+                None => None,
+            };
+            match widths {
+                Some(widths) if widths.iter().all(|width| [16, 32, 64].contains(width)) => {
+                    *slot = widths;
+                    true
+                }
+                _ => false,
+            }
+        }
     }
 ) }
 
@@ -946,4 +985,57 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
          (such as entering an empty infinite loop) by inserting llvm.sideeffect"),
     deduplicate_diagnostics: Option<bool> = (None, parse_opt_bool, [UNTRACKED],
         "deduplicate identical diagnostics"),
+    non_send_across_await_all: bool = (false, parse_bool, [UNTRACKED],
+        "make the `non_send_across_await` lint check every async fn and block, \
+         not only exported async fns"),
+    large_future_threshold: usize = (16 * 1024, parse_uint, [TRACKED],
+        "the size in bytes above which the `large_futures` lint fires"),
+    stack_frame_threshold: Option<usize> = (None, parse_opt_uint, [TRACKED],
+        "the estimated stack frame size in bytes above which the `large_stack_frames` lint \
+         fires (default: the lint is off)"),
+    instantiation_threshold: usize = (32, parse_uint, [TRACKED],
+        "the number of instantiations of a generic function above which the \
+         `excessive_monomorphization` lint fires"),
+    large_copy_threshold: usize = (128, parse_uint, [TRACKED],
+        "the size in bytes above which the `large_copy_types` lint fires"),
+    large_const_data_threshold: usize = (1024 * 1024, parse_uint, [TRACKED],
+        "the size in bytes above which the `large_const_data` lint fires"),
+    portable_pointer_widths: Vec<u64> = (vec![32, 64], parse_pointer_widths, [TRACKED],
+        "the pointer widths in bits the `pointer_width_casts` lint expects the crate to be \
+         built for (default: `32,64`)"),
+    private_dependencies: Vec<String> = (Vec::new(), parse_list, [TRACKED],
+        "names of crates to treat as private dependencies, as if passed with `--extern priv:`"),
+    required_traits: Vec<String> = (Vec::new(), parse_list, [TRACKED],
+        "traits that reachable types must implement, checked by the \
+         `missing_trait_implementations` lint"),
+    missing_docs_kinds: Vec<String> = (Vec::new(), parse_list, [TRACKED],
+        "item kinds checked by the `missing_docs` lint (e.g. `fn trait field`); \
+         all kinds are checked if empty"),
+    missing_docs_coverage: bool = (false, parse_bool, [UNTRACKED],
+        "print a JSON summary of the documentation coverage checked by `missing_docs`"),
+    lint_emission_cap: Option<usize> = (None, parse_opt_uint, [UNTRACKED],
+        "emit at most this many warnings per lint, and report how many more were suppressed"),
+    suggest_allow_attributes: AllowSuggestions = (AllowSuggestions::Help,
+        parse_allow_suggestions, [UNTRACKED],
+        "how each lint warning tells where to put an `#[allow(..)]` for it: as a help naming the \
+        enclosing node, as a `rendered` suggestion for editors to offer, or `off`"),
+    lint_macros_from: Vec<String> = (Vec::new(), parse_list, [TRACKED],
+        "lint the expansions of `macro_rules` macros from these crates as if they were local"),
+    sort_lints: bool = (false, parse_bool, [UNTRACKED],
+        "hold lints back and emit them sorted by file, position and name, for diffing the output"),
+    lint_summary: bool = (false, parse_bool, [UNTRACKED],
+        "print how many times each lint was warned about, denied and allowed at the end"),
+    lint_coverage: bool = (false, parse_bool, [UNTRACKED],
+        "print whether the pass of each lint ran, and whether the lint was checked, emitted or \
+         suppressed, at the end"),
+    lint_warning_threshold: Option<usize> = (None, parse_opt_uint, [UNTRACKED],
+        "exit with an error code if more than this many lint warnings are emitted"),
+    lint_warning_threshold_lints: Vec<String> = (Vec::new(), parse_list, [UNTRACKED],
+        "only count the warnings of these lints and lint groups towards \
+         `-Z lint-warning-threshold`"),
+    verbose_lints: bool = (false, parse_bool, [UNTRACKED],
+        "print when each lint pass starts and finishes, with its time and number of lints"),
+    unsafe_report: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "write a JSON inventory of the `unsafe` blocks, functions, impls and traits of the \
+         crate to this path, whether or not `unsafe_code` warns about them"),
 }
@@ -3,7 +3,7 @@ pub use crate::code_stats::{DataTypeKind, FieldInfo, SizeKind, VariantInfo};
 
 use crate::cgu_reuse_tracker::CguReuseTracker;
 use rustc_data_structures::fingerprint::Fingerprint;
-use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_data_structures::fx::{FxHashMap, FxHashSet, FxHasher};
 
 use crate::config::{self, OutputType, PrintRequest, Sanitizer, SwitchWithOptPath};
 use crate::filesearch;
@@ -11,6 +11,7 @@ use crate::lint;
 use crate::search_paths::{PathKind, SearchPath};
 use rustc_data_structures::profiling::duration_to_secs_str;
 use rustc_errors::ErrorReported;
+use rustc_serialize::json::Json;
 
 use rustc_data_structures::base_n;
 use rustc_data_structures::impl_stable_hash_via_hash;
@@ -22,10 +23,12 @@ use crate::parse::ParseSess;
 use rustc_errors::annotate_snippet_emitter_writer::AnnotateSnippetEmitterWriter;
 use rustc_errors::emitter::HumanReadableErrorType;
 use rustc_errors::emitter::{Emitter, EmitterWriter};
+use rustc_errors::github::GithubEmitter;
 use rustc_errors::json::JsonEmitter;
-use rustc_errors::{Applicability, DiagnosticBuilder, DiagnosticId};
+use rustc_errors::{pluralize, Applicability, Diagnostic, DiagnosticBuilder, DiagnosticId};
 use rustc_span::edition::Edition;
 use rustc_span::source_map;
+use rustc_span::symbol::Symbol;
 use rustc_span::{MultiSpan, Span};
 
 use rustc_data_structures::flock;
@@ -37,11 +40,13 @@ use std;
 use std::cell::{self, RefCell};
 use std::env;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::io::Write;
 use std::num::NonZeroU32;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 pub struct OptimizationFuel {
     /// If `-zfuel=crate=n` is specified, initially set to `n`, otherwise `0`.
@@ -133,6 +138,69 @@ pub struct Session {
     /// Mapping from ident span to path span for paths that don't exist as written, but that
     /// exist under `std`. For example, wrote `str::from_utf8` instead of `std::str::from_utf8`.
     pub confused_type_with_std_module: Lock<FxHashMap<Span, Span>>,
+
+    /// Spans of the lint names in `allow` attributes that suppressed at least one lint, for the
+    /// `unused_allows` lint.
+    pub used_lint_allows: Lock<FxHashSet<Span>>,
+
+    /// The number of warnings each lint has reported, for `-Z lint-emission-cap`.
+    lint_warning_counts: Lock<FxHashMap<lint::LintId, usize>>,
+
+    /// Crate numbers of the crates named by `-Z lint-macros-from`, whose macros are linted as if
+    /// they were local. Filled in by the crate loader.
+    pub linted_macro_crates: Lock<FxHashSet<u32>>,
+
+    /// The lint, primary span and message hash of every lint the early lint runs reported
+    /// so far, so that lints buffered again for the post-expansion run aren't reported twice.
+    emitted_early_lints: Lock<FxHashSet<(lint::LintId, Option<Span>, u64)>>,
+
+    /// How often each lint was reported at each level, for `-Z lint-summary` and
+    /// `-Z lint-coverage`.
+    lint_summary_counts: Lock<FxHashMap<lint::LintId, LintSummaryCounts>>,
+
+    /// All the registered lints, set once they are for `-Z lint-coverage`.
+    pub lint_coverage_lints: Once<Vec<lint::LintId>>,
+
+    /// The lints of the lint passes that ran, for `-Z lint-coverage`.
+    lint_coverage_passes_run: Lock<FxHashSet<lint::LintId>>,
+
+    /// The lints whose level was looked up for some node, for `-Z lint-coverage`.
+    lint_coverage_checked: Lock<FxHashSet<lint::LintId>>,
+
+    /// The lints named by `-Z lint-warning-threshold-lints`, set once all lints are registered.
+    /// Every lint counts towards the threshold if this is left unset.
+    pub lint_warning_threshold_lints: Once<FxHashSet<lint::LintId>>,
+
+    /// The number of warnings counted towards `-Z lint-warning-threshold`.
+    lint_threshold_warning_count: AtomicUsize,
+
+    /// The number of lints emitted so far, for `-Z verbose-lints`.
+    lint_emission_count: AtomicUsize,
+
+    /// The changed lines of each file named in the `--lint-changed` file, as inclusive ranges.
+    lint_changed_lines: Option<FxHashMap<PathBuf, Vec<(usize, usize)>>>,
+
+    /// The lint diagnostics emitted so far, recorded for the lint reports of `--emit`.
+    recorded_lints: Option<Lrc<Lock<Vec<Diagnostic>>>>,
+
+    /// The crate name and output filenames the lint reports of `--emit` are written with, at
+    /// the end of the session. Set once the outputs are known.
+    pub lint_report_outputs: Once<(String, config::OutputFilenames)>,
+
+    /// The levels `--extern-lint-level` sets for code from each crate it names, with the lint
+    /// names resolved. Set once the lint store is complete.
+    pub extern_lint_levels: Once<FxHashMap<Symbol, FxHashMap<lint::LintId, lint::Level>>>,
+
+    /// The names of the crates named by `--extern-lint-level`, by crate number. Filled in by the
+    /// crate loader.
+    pub extern_lint_level_crates: Lock<FxHashMap<u32, Symbol>>,
+}
+
+#[derive(Clone, Copy, Default)]
+struct LintSummaryCounts {
+    warned: usize,
+    denied: usize,
+    allowed: usize,
 }
 
 pub struct PerfStats {
@@ -305,6 +373,329 @@ impl Session {
     pub fn note_without_error(&self, msg: &str) {
         self.diagnostic().note_without_error(msg)
     }
+
+    /// Records that an early lint run is reporting `lint` at `span` with `msg`, returning
+    /// `false` if an identical lint already was reported.
+    pub fn mark_early_lint_emitted(
+        &self,
+        lint: lint::LintId,
+        span: Option<Span>,
+        msg: &str,
+    ) -> bool {
+        let mut hasher = FxHasher::default();
+        msg.hash(&mut hasher);
+        self.emitted_early_lints.lock().insert((lint, span, hasher.finish()))
+    }
+
+    /// Counts a warning of `lint`, returning whether it is still within `-Z lint-emission-cap`.
+    pub fn lint_within_emission_cap(&self, lint: lint::LintId) -> bool {
+        let cap = match self.opts.debugging_opts.lint_emission_cap {
+            Some(cap) => cap,
+            None => return true,
+        };
+        let mut counts = self.lint_warning_counts.lock();
+        let count = counts.entry(lint).or_insert(0);
+        *count += 1;
+        *count <= cap
+    }
+
+    /// Reports how many warnings of each lint were left out because of `-Z lint-emission-cap`.
+    pub fn report_capped_lints(&self) {
+        let cap = match self.opts.debugging_opts.lint_emission_cap {
+            Some(cap) => cap,
+            None => return,
+        };
+        let mut capped = self
+            .lint_warning_counts
+            .lock()
+            .iter()
+            .filter(|&(_, &count)| count > cap)
+            .map(|(lint, &count)| (lint.to_string(), count - cap))
+            .collect::<Vec<_>>();
+        capped.sort();
+        for (name, suppressed) in capped {
+            // Group the digits in threes, as in "1,240".
+            let digits = suppressed.to_string();
+            let mut count = String::new();
+            for (i, digit) in digits.chars().enumerate() {
+                if i > 0 && (digits.len() - i) % 3 == 0 {
+                    count.push(',');
+                }
+                count.push(digit);
+            }
+            self.note_without_error(&format!(
+                "`{}`: {} additional warning{} suppressed",
+                name,
+                count,
+                pluralize!(suppressed)
+            ));
+        }
+    }
+
+    /// Counts a warning of `lint` towards `-Z lint-warning-threshold`.
+    pub fn count_lint_warning(&self, lint: lint::LintId) {
+        if self.opts.debugging_opts.lint_warning_threshold.is_none() {
+            return;
+        }
+        if let Some(lints) = self.lint_warning_threshold_lints.try_get() {
+            if !lints.contains(&lint) {
+                return;
+            }
+        }
+        self.lint_threshold_warning_count.fetch_add(1, SeqCst);
+    }
+
+    /// Fails the compilation if more warnings were counted than `-Z lint-warning-threshold`
+    /// allows. The warnings themselves stay warnings; only the exit code changes.
+    pub fn check_lint_warning_threshold(&self) -> Result<(), ErrorReported> {
+        let threshold = match self.opts.debugging_opts.lint_warning_threshold {
+            Some(threshold) => threshold,
+            None => return Ok(()),
+        };
+        let count = self.lint_threshold_warning_count.load(SeqCst);
+        if count <= threshold {
+            return Ok(());
+        }
+        self.note_without_error(&format!(
+            "{} lint warning{} emitted, more than the {} allowed by `-Z lint-warning-threshold`",
+            count,
+            pluralize!(count),
+            threshold
+        ));
+        Err(ErrorReported)
+    }
+
+    /// Whether `span` touches a line listed by `--lint-changed`, if that was passed. Relative
+    /// paths in that file name any source file whose path ends with them.
+    pub fn in_lint_changed_lines(&self, span: Span) -> bool {
+        let changed_lines = match self.lint_changed_lines {
+            Some(ref changed_lines) => changed_lines,
+            None => return true,
+        };
+        // Lints in macros are also reported if the line invoking the macro changed.
+        let intersects = |span: Span| {
+            if span.is_dummy() {
+                return false;
+            }
+            let lo = self.source_map().lookup_char_pos(span.lo());
+            let hi = self.source_map().lookup_char_pos(span.hi());
+            let file = match lo.file.name {
+                source_map::FileName::Real(ref file) => file,
+                _ => return false,
+            };
+            changed_lines
+                .iter()
+                .filter(|(path, _)| file == path || (path.is_relative() && file.ends_with(path)))
+                .flat_map(|(_, ranges)| ranges)
+                .any(|&(first, last)| first <= hi.line && lo.line <= last)
+        };
+        intersects(span) || intersects(span.source_callsite())
+    }
+
+    /// The lint diagnostics emitted so far, in the order they were emitted. Only recorded when a
+    /// lint report is requested with `--emit`; empty otherwise.
+    pub fn recorded_lints(&self) -> Vec<Diagnostic> {
+        match self.recorded_lints {
+            Some(ref lints) => lints.lock().clone(),
+            None => Vec::new(),
+        }
+    }
+
+    /// The level `--extern-lint-level` gives `lint` in code from another crate, along with that
+    /// crate's name. The code comes from a crate if it was inlined from it, or expanded from one
+    /// of its macros; the innermost such crate of the first primary span that has one wins.
+    pub fn extern_lint_level(
+        &self,
+        lint: lint::LintId,
+        span: &MultiSpan,
+    ) -> Option<(lint::Level, Symbol)> {
+        let levels = self.extern_lint_levels.try_get()?;
+        let crates = self.extern_lint_level_crates.borrow();
+        for &span in span.primary_spans() {
+            if span.is_dummy() {
+                continue;
+            }
+            for cnum in self.crates_of_origin(span) {
+                let name = match crates.get(&cnum) {
+                    Some(&name) => name,
+                    None => continue,
+                };
+                if let Some(&level) = levels.get(&name).and_then(|lints| lints.get(&lint)) {
+                    return Some((level, name));
+                }
+            }
+        }
+        None
+    }
+
+    /// The crate `span`'s source file comes from, followed by the crates defining the macros it
+    /// was expanded from, innermost first.
+    fn crates_of_origin(&self, mut span: Span) -> Vec<u32> {
+        let mut crates = vec![self.source_map().lookup_source_file(span.lo()).crate_of_origin];
+        while span.from_expansion() {
+            let expn_data = span.ctxt().outer_expn_data();
+            // Macros loaded from other crates are parsed again under a new span; find the one in
+            // the crate they were loaded from.
+            let def_site = match self.imported_macro_spans.borrow().get(&expn_data.def_site) {
+                Some(&(_, original_span)) => original_span,
+                None => expn_data.def_site,
+            };
+            if !def_site.is_dummy() {
+                crates.push(self.source_map().lookup_source_file(def_site.lo()).crate_of_origin);
+            }
+            span = expn_data.call_site;
+        }
+        crates
+    }
+
+    /// Counts a lint that is about to be emitted, for `-Z verbose-lints`.
+    pub fn count_lint_emission(&self) {
+        self.lint_emission_count.fetch_add(1, SeqCst);
+    }
+
+    /// Runs the lint pass `f`, saying on stderr when it starts and finishes under
+    /// `-Z verbose-lints`. Counts include the lints of any pass running in parallel.
+    pub fn verbose_lint_pass<R>(&self, name: impl FnOnce() -> String, f: impl FnOnce() -> R) -> R {
+        if !self.opts.debugging_opts.verbose_lints {
+            return f();
+        }
+        let name = name();
+        eprintln!("lint pass {}: started", name);
+        let emitted_before = self.lint_emission_count.load(SeqCst);
+        let start = Instant::now();
+        let r = f();
+        let elapsed = start.elapsed();
+        let emitted = self.lint_emission_count.load(SeqCst) - emitted_before;
+        eprintln!(
+            "lint pass {}: finished in {}s, {} lint{} emitted",
+            name,
+            duration_to_secs_str(elapsed),
+            emitted,
+            pluralize!(emitted)
+        );
+        r
+    }
+
+    /// Counts that `lint` was reported at `level`, or suppressed if that is `Allow`, for
+    /// `-Z lint-summary` and `-Z lint-coverage`.
+    pub fn record_lint_level(&self, lint: lint::LintId, level: lint::Level) {
+        if !self.opts.debugging_opts.lint_summary && !self.opts.debugging_opts.lint_coverage {
+            return;
+        }
+        let mut counts = self.lint_summary_counts.lock();
+        let counts = counts.entry(lint).or_default();
+        match level {
+            lint::Level::Allow => counts.allowed += 1,
+            lint::Level::Warn => counts.warned += 1,
+            lint::Level::Deny | lint::Level::Forbid => counts.denied += 1,
+        }
+    }
+
+    /// Records that a lint pass declaring `lints` ran, for `-Z lint-coverage`.
+    pub fn record_lint_pass_run(&self, lints: impl FnOnce() -> lint::LintArray) {
+        if !self.opts.debugging_opts.lint_coverage {
+            return;
+        }
+        let mut passes_run = self.lint_coverage_passes_run.lock();
+        passes_run.extend(lints().into_iter().map(lint::LintId::of));
+    }
+
+    /// Records that the level of `lint` was looked up to check some node, for
+    /// `-Z lint-coverage`.
+    pub fn record_lint_check(&self, lint: lint::LintId) {
+        if !self.opts.debugging_opts.lint_coverage {
+            return;
+        }
+        self.lint_coverage_checked.lock().insert(lint);
+    }
+
+    /// Prints the table of `-Z lint-coverage`: for every registered lint, whether the pass
+    /// declaring it ran, whether its level was ever looked up to check some node, and how often
+    /// it was emitted and suppressed. The lints raised by the compiler outside of lint passes
+    /// belong to the late module passes.
+    pub fn print_lint_coverage(&self) {
+        if !self.opts.debugging_opts.lint_coverage {
+            return;
+        }
+        let lints = match self.lint_coverage_lints.try_get() {
+            Some(lints) => lints,
+            None => return,
+        };
+        let passes_run = self.lint_coverage_passes_run.lock();
+        let checked = self.lint_coverage_checked.lock();
+        let counts = self.lint_summary_counts.lock();
+        let mut rows = lints
+            .iter()
+            .map(|lint| {
+                let counts = counts.get(lint).cloned().unwrap_or_default();
+                (
+                    lint.to_string(),
+                    if passes_run.contains(lint) { "ran" } else { "no" },
+                    if checked.contains(lint) { "yes" } else { "no" },
+                    counts.warned + counts.denied,
+                    counts.allowed,
+                )
+            })
+            .collect::<Vec<_>>();
+        rows.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let width = rows.iter().map(|row| row.0.len()).max().unwrap_or(0).max("lint".len());
+        let print_row = |lint: &str, pass: &str, checked: &str, emitted: &str, suppressed: &str| {
+            println!(
+                "{:<width$}  {:>4}  {:>7}  {:>7}  {:>10}",
+                lint,
+                pass,
+                checked,
+                emitted,
+                suppressed,
+                width = width
+            );
+        };
+        print_row("lint", "pass", "checked", "emitted", "suppressed");
+        for (name, pass, checked, emitted, suppressed) in rows {
+            print_row(&name, pass, checked, &emitted.to_string(), &suppressed.to_string());
+        }
+    }
+
+    /// Prints the table of `-Z lint-summary`. Lints that check their level before doing any
+    /// work aren't counted as allowed, as they never get as far as being reported.
+    pub fn print_lint_summary(&self) {
+        if !self.opts.debugging_opts.lint_summary {
+            return;
+        }
+        let mut rows = self
+            .lint_summary_counts
+            .lock()
+            .iter()
+            .map(|(lint, counts)| (lint.to_string(), *counts))
+            .collect::<Vec<_>>();
+        if rows.is_empty() {
+            return;
+        }
+        rows.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0).max("lint".len());
+        let print_row = |lint: &str, warned: &str, denied: &str, allowed: &str| {
+            println!(
+                "{:<width$}  {:>6}  {:>6}  {:>7}",
+                lint,
+                warned,
+                denied,
+                allowed,
+                width = width
+            );
+        };
+        print_row("lint", "warned", "denied", "allowed");
+        for (name, counts) in rows {
+            print_row(
+                &name,
+                &counts.warned.to_string(),
+                &counts.denied.to_string(),
+                &counts.allowed.to_string(),
+            );
+        }
+    }
+
     pub fn span_note_without_error<S: Into<MultiSpan>>(&self, sp: S, msg: &str) {
         self.diagnostic().span_note_without_error(sp, msg)
     }
@@ -911,6 +1302,12 @@ fn default_emitter(
             )
             .ui_testing(sopts.debugging_opts.ui_testing()),
         ),
+        (config::ErrorOutputType::Github, None) => {
+            Box::new(GithubEmitter::stderr(source_map.clone()))
+        }
+        (config::ErrorOutputType::Github, Some(dst)) => {
+            Box::new(GithubEmitter::new(dst, source_map.clone()))
+        }
     }
 }
 
@@ -946,12 +1343,74 @@ pub fn build_session_with_source_map(
     };
     let emitter = default_emitter(&sopts, registry, &source_map, write_dest);
 
+    let records_lints = sopts.output_types.contains_key(&OutputType::LintReport)
+        || sopts.output_types.contains_key(&OutputType::LintReportJunit)
+        || sopts.output_types.contains_key(&OutputType::LintMetadata);
+    let (emitter, recorded_lints) = if records_lints {
+        let lints = Lrc::new(Lock::new(Vec::new()));
+        let emitter: Box<dyn Emitter + sync::Send> =
+            Box::new(LintRecordingEmitter { inner: emitter, lints: lints.clone() });
+        (emitter, Some(lints))
+    } else {
+        (emitter, None)
+    };
+
     let diagnostic_handler = rustc_errors::Handler::with_emitter_and_flags(
         emitter,
         sopts.debugging_opts.diagnostic_handler_flags(can_emit_warnings),
     );
 
-    build_session_(sopts, local_crate_source_file, diagnostic_handler, source_map, lint_caps)
+    build_session_(
+        sopts,
+        local_crate_source_file,
+        diagnostic_handler,
+        source_map,
+        lint_caps,
+        recorded_lints,
+    )
+}
+
+/// Builds a session for running the early lint passes over a crate that was parsed into
+/// `source_map` outside of a compilation, handing all diagnostics to `emitter`. No sysroot is
+/// looked for, so it works in tools that don't ship with one.
+pub fn build_early_lint_session(
+    sopts: config::Options,
+    source_map: Lrc<source_map::SourceMap>,
+    emitter: Box<dyn Emitter + sync::Send>,
+) -> Session {
+    let diagnostic_handler = rustc_errors::Handler::with_emitter_and_flags(
+        emitter,
+        sopts.debugging_opts.diagnostic_handler_flags(true),
+    );
+    let sopts = config::Options { maybe_sysroot: Some(PathBuf::new()), ..sopts };
+    build_session_(sopts, None, diagnostic_handler, source_map, Default::default(), None)
+}
+
+/// Passes diagnostics on to `inner`, keeping a copy of those coming from lints.
+struct LintRecordingEmitter {
+    inner: Box<dyn Emitter + sync::Send>,
+    lints: Lrc<Lock<Vec<Diagnostic>>>,
+}
+
+impl Emitter for LintRecordingEmitter {
+    fn emit_diagnostic(&mut self, diag: &Diagnostic) {
+        if let Some(DiagnosticId::Lint { .. }) = diag.code {
+            self.lints.lock().push(diag.clone());
+        }
+        self.inner.emit_diagnostic(diag);
+    }
+
+    fn emit_artifact_notification(&mut self, path: &Path, artifact_type: &str) {
+        self.inner.emit_artifact_notification(path, artifact_type);
+    }
+
+    fn should_show_explain(&self) -> bool {
+        self.inner.should_show_explain()
+    }
+
+    fn source_map(&self) -> Option<&Lrc<source_map::SourceMap>> {
+        self.inner.source_map()
+    }
 }
 
 fn build_session_(
@@ -960,6 +1419,7 @@ fn build_session_(
     span_diagnostic: rustc_errors::Handler,
     source_map: Lrc<source_map::SourceMap>,
     driver_lint_caps: FxHashMap<lint::LintId, lint::Level>,
+    recorded_lints: Option<Lrc<Lock<Vec<Diagnostic>>>>,
 ) -> Session {
     let self_profiler = if let SwitchWithOptPath::Enabled(ref d) = sopts.debugging_opts.self_profile
     {
@@ -1021,6 +1481,11 @@ fn build_session_(
     });
     let working_dir = file_path_mapping.map_prefix(working_dir);
 
+    let lint_changed_lines = sopts
+        .lint_changed
+        .as_ref()
+        .map(|path| load_lint_changed_lines(path, &parse_sess.span_diagnostic));
+
     let cgu_reuse_tracker = if sopts.debugging_opts.query_dep_graph {
         CguReuseTracker::new()
     } else {
@@ -1069,6 +1534,22 @@ fn build_session_(
         driver_lint_caps,
         trait_methods_not_found: Lock::new(Default::default()),
         confused_type_with_std_module: Lock::new(Default::default()),
+        used_lint_allows: Lock::new(Default::default()),
+        lint_warning_counts: Lock::new(Default::default()),
+        linted_macro_crates: Lock::new(Default::default()),
+        emitted_early_lints: Lock::new(Default::default()),
+        lint_summary_counts: Lock::new(Default::default()),
+        lint_coverage_lints: Once::new(),
+        lint_coverage_passes_run: Lock::new(Default::default()),
+        lint_coverage_checked: Lock::new(Default::default()),
+        lint_warning_threshold_lints: Once::new(),
+        lint_threshold_warning_count: AtomicUsize::new(0),
+        lint_emission_count: AtomicUsize::new(0),
+        lint_changed_lines,
+        recorded_lints,
+        lint_report_outputs: Once::new(),
+        extern_lint_levels: Once::new(),
+        extern_lint_level_crates: Lock::new(Default::default()),
     };
 
     validate_commandline_args_with_session_available(&sess);
@@ -1076,6 +1557,51 @@ fn build_session_(
     sess
 }
 
+/// Reads the file given to `--lint-changed`: a JSON object mapping file paths to arrays of
+/// changed lines, each either a line number or an inclusive `[first, last]` range.
+fn load_lint_changed_lines(
+    path: &Path,
+    handler: &rustc_errors::Handler,
+) -> FxHashMap<PathBuf, Vec<(usize, usize)>> {
+    let invalid = |msg: &str| -> ! {
+        handler
+            .fatal(&format!("invalid `--lint-changed` file `{}`: {}", path.display(), msg))
+            .raise()
+    };
+    let contents = fs::read_to_string(path).unwrap_or_else(|e| invalid(&e.to_string()));
+    let json = Json::from_str(&contents).unwrap_or_else(|e| invalid(&e.to_string()));
+    let files = match json {
+        Json::Object(files) => files,
+        _ => invalid("expected an object mapping file paths to changed lines"),
+    };
+
+    files
+        .into_iter()
+        .map(|(file, lines)| {
+            let lines = match lines {
+                Json::Array(lines) => lines,
+                _ => invalid(&format!("the changed lines of `{}` are not an array", file)),
+            };
+            let ranges = lines
+                .iter()
+                .map(|range| {
+                    if let Some(line) = range.as_u64() {
+                        return (line as usize, line as usize);
+                    }
+                    match range.as_array().map(|range| &range[..]) {
+                        Some([first, last]) => match (first.as_u64(), last.as_u64()) {
+                            (Some(first), Some(last)) => (first as usize, last as usize),
+                            _ => invalid(&format!("invalid line range for `{}`", file)),
+                        },
+                        _ => invalid(&format!("invalid line range for `{}`", file)),
+                    }
+                })
+                .collect();
+            (PathBuf::from(file), ranges)
+        })
+        .collect()
+}
+
 // If it is useful to have a Session available already for validating a
 // commandline argument, you can do so here.
 fn validate_commandline_args_with_session_available(sess: &Session) {
@@ -1207,6 +1733,9 @@ pub fn early_error(output: config::ErrorOutputType, msg: &str) -> ! {
         config::ErrorOutputType::Json { pretty, json_rendered } => {
             Box::new(JsonEmitter::basic(pretty, json_rendered, false))
         }
+        config::ErrorOutputType::Github => Box::new(GithubEmitter::stderr(Lrc::new(
+            source_map::SourceMap::new(source_map::FilePathMapping::empty()),
+        ))),
     };
     let handler = rustc_errors::Handler::with_emitter(true, None, emitter);
     handler.struct_fatal(msg).emit();
@@ -1222,6 +1751,9 @@ pub fn early_warn(output: config::ErrorOutputType, msg: &str) {
         config::ErrorOutputType::Json { pretty, json_rendered } => {
             Box::new(JsonEmitter::basic(pretty, json_rendered, false))
         }
+        config::ErrorOutputType::Github => Box::new(GithubEmitter::stderr(Lrc::new(
+            source_map::SourceMap::new(source_map::FilePathMapping::empty()),
+        ))),
     };
     let handler = rustc_errors::Handler::with_emitter(true, None, emitter);
     handler.struct_warn(msg).emit();
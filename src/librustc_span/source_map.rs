@@ -583,6 +583,49 @@ impl SourceMap {
         })
     }
 
+    /// Returns the source snippet as `String` after the given `Span`.
+    pub fn span_to_next_source(&self, sp: Span) -> Result<String, SpanSnippetError> {
+        self.span_to_source(sp, |src, _, end_index| {
+            src.get(end_index..)
+                .map(|s| s.to_string())
+                .ok_or_else(|| SpanSnippetError::IllFormedSpan(sp))
+        })
+    }
+
+    /// Extends the given `Span` over the indentation before it and the rest of its last line,
+    /// newline included, if it is the only thing on the lines it spans. Returns the same span
+    /// otherwise, or if an error occurred while retrieving the code snippets.
+    ///
+    /// This is meant for suggestions removing a whole item, so that they don't leave empty
+    /// lines behind.
+    pub fn span_extend_to_whole_lines(&self, sp: Span) -> Span {
+        let is_blank = |c: char| c == ' ' || c == '\t';
+        let (prev_source, next_source) =
+            match (self.span_to_prev_source(sp), self.span_to_next_source(sp)) {
+                (Ok(prev_source), Ok(next_source)) => (prev_source, next_source),
+                _ => return sp,
+            };
+
+        let before = prev_source.trim_end_matches(is_blank);
+        if !before.is_empty() && !before.ends_with('\n') {
+            return sp;
+        }
+        let after = next_source.trim_start_matches(is_blank);
+        let newline_len = if after.starts_with("\r\n") {
+            2
+        } else if after.starts_with('\n') {
+            1
+        } else if after.is_empty() {
+            0
+        } else {
+            return sp;
+        };
+
+        let indent = (prev_source.len() - before.len()) as u32;
+        let trailing = (next_source.len() - after.len() + newline_len) as u32;
+        sp.with_lo(sp.lo() - BytePos(indent)).with_hi(sp.hi() + BytePos(trailing))
+    }
+
     /// Extends the given `Span` to just after the previous occurrence of `c`. Return the same span
     /// if no character could be found or if an error occurred while retrieving the code snippet.
     pub fn span_extend_to_prev_char(&self, sp: Span, c: char) -> Span {
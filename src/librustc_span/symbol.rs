@@ -120,6 +120,8 @@ symbols! {
         abi_vectorcall,
         abi_x86_interrupt,
         aborts,
+        AcqRel,
+        Acquire,
         add_with_overflow,
         advanced_slice_patterns,
         adx_target_feature,
@@ -153,6 +155,7 @@ symbols! {
         assume_init,
         async_await,
         async_closure,
+        atomic,
         attr,
         attributes,
         attr_literals,
@@ -161,14 +164,18 @@ symbols! {
         avx512_target_feature,
         await_macro,
         begin_panic,
+        begin_panic_fmt,
         bench,
         bin,
         bind_by_move_pattern_guards,
         bindings_after_at,
         block,
+        blocking_functions,
         bool,
+        borrow_trait,
         borrowck_graphviz_postflow,
         borrowck_graphviz_preflow,
+        box_new,
         box_patterns,
         box_syntax,
         braced_empty_structs,
@@ -197,8 +204,11 @@ symbols! {
         cmpxchg16b_target_feature,
         cold,
         column,
+        compare_exchange,
+        compare_exchange_weak,
         compile_error,
         compiler_builtins,
+        compiler_fence,
         concat,
         concat_idents,
         conservative_impl_trait,
@@ -296,10 +306,12 @@ symbols! {
         enclosing_scope,
         except,
         exclusive_range_pattern,
+        exhaustive,
         exhaustive_integer_patterns,
         exhaustive_patterns,
         existential_type,
         expected,
+        expires,
         export_name,
         expr,
         extern_absolute_paths,
@@ -313,6 +325,8 @@ symbols! {
         f32,
         f64,
         feature,
+        fence,
+        fetch_update,
         ffi_returns_twice,
         field,
         field_init_shorthand,
@@ -346,6 +360,7 @@ symbols! {
         half_open_range_patterns,
         hash,
         Hash,
+        hash_trait,
         HashSet,
         HashMap,
         hexagon_target_feature,
@@ -415,8 +430,10 @@ symbols! {
         link_ordinal,
         link_section,
         LintPass,
+        lint_expiry,
         lint_reasons,
         literal,
+        load,
         local_inner_macros,
         log_syntax,
         loop_break_value,
@@ -507,11 +524,13 @@ symbols! {
         or,
         or_patterns,
         Ord,
+        ord_trait,
         Ordering,
         Output,
         overlapping_marker_traits,
         packed,
         panic,
+        panic_fmt,
         panic_handler,
         panic_impl,
         panic_implementation,
@@ -576,7 +595,9 @@ symbols! {
         reflect,
         register_attr,
         register_tool,
+        Relaxed,
         relaxed_adts,
+        Release,
         repr,
         repr128,
         repr_align,
@@ -606,6 +627,7 @@ symbols! {
         rustc_allow_const_fn_ptr,
         rustc_args_required_const,
         rustc_attrs,
+        rustc_blocking,
         rustc_builtin_macro,
         rustc_clean,
         rustc_const_unstable,
@@ -621,6 +643,7 @@ symbols! {
         rustc_dump_program_clauses,
         rustc_dump_user_substs,
         rustc_error,
+        rustc_executor_entry_point,
         rustc_expected_cgu_reuse,
         rustc_if_this_changed,
         rustc_inherit_overflow_checks,
@@ -666,6 +689,7 @@ symbols! {
         self_in_typedefs,
         self_struct_ctor,
         send_trait,
+        SeqCst,
         should_panic,
         simd,
         simd_extract,
@@ -691,6 +715,7 @@ symbols! {
         static_recursion,
         std,
         std_inject,
+        store,
         str,
         stringify,
         stmt,
@@ -703,7 +728,9 @@ symbols! {
         sty,
         sub_with_overflow,
         suggestion,
+        sync,
         sync_trait,
+        Target,
         target_feature,
         target_has_atomic,
         target_has_atomic_load_store,
@@ -762,6 +789,7 @@ symbols! {
         unmarked_api,
         unreachable_code,
         unrestricted_attribute_tokens,
+        unsafe_code,
         unsafe_no_drop_flag,
         unsized_locals,
         unsized_tuple_coercion,
@@ -92,11 +92,13 @@ impl<'a, 'tcx> InteriorVisitor<'a, 'tcx> {
                 // Map the type to the number of types added before it
                 let entries = self.types.len();
                 let scope_span = scope.map(|s| s.span(self.fcx.tcx, self.region_scope_tree));
+                let yield_span = Some(yield_data.span).filter(|span| !span.is_dummy());
                 self.types
                     .entry(ty::GeneratorInteriorTypeCause {
                         span: source_span,
                         ty: &ty,
                         scope_span,
+                        yield_span,
                     })
                     .or_insert(entries);
             }
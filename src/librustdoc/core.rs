@@ -16,6 +16,7 @@ use rustc_lint;
 use rustc_resolve as resolve;
 
 use rustc_errors::emitter::{Emitter, EmitterWriter};
+use rustc_errors::github::GithubEmitter;
 use rustc_errors::json::JsonEmitter;
 use rustc_span::source_map;
 use rustc_span::symbol::sym;
@@ -196,6 +197,12 @@ pub fn new_handler(
                     .ui_testing(debugging_opts.ui_testing()),
             )
         }
+        ErrorOutputType::Github => {
+            let source_map = source_map.unwrap_or_else(|| {
+                Lrc::new(source_map::SourceMap::new(source_map::FilePathMapping::empty()))
+            });
+            Box::new(GithubEmitter::stderr(source_map))
+        }
     };
 
     rustc_errors::Handler::with_emitter_and_flags(
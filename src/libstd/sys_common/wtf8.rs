@@ -822,6 +822,7 @@ impl<'a> Iterator for EncodeWide<'a> {
     }
 }
 
+#[cfg_attr(not(bootstrap), allow(inconsistent_trait_impls))]
 impl Hash for CodePoint {
     #[inline]
     fn hash<H: Hasher>(&self, state: &mut H) {
@@ -829,6 +830,7 @@ impl Hash for CodePoint {
     }
 }
 
+#[cfg_attr(not(bootstrap), allow(inconsistent_trait_impls))]
 impl Hash for Wtf8Buf {
     #[inline]
     fn hash<H: Hasher>(&self, state: &mut H) {
@@ -837,6 +839,7 @@ impl Hash for Wtf8Buf {
     }
 }
 
+#[cfg_attr(not(bootstrap), allow(inconsistent_trait_impls))]
 impl Hash for Wtf8 {
     #[inline]
     fn hash<H: Hasher>(&self, state: &mut H) {
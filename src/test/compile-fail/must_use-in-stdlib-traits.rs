@@ -1,4 +1,4 @@
-#![deny(unused_must_use)]
+#![deny(unused_must_use, unused_futures)]
 #![feature(arbitrary_self_types)]
 
 use std::iter::Iterator;
@@ -40,7 +40,7 @@ fn square_fn() -> impl Fn(u32) -> u32 {
 
 fn main() {
    iterator(); //~ ERROR unused implementer of `std::iter::Iterator` that must be used
-   future(); //~ ERROR unused implementer of `std::future::Future` that must be used
+   future(); //~ ERROR future is created but never awaited
    square_fn_once(); //~ ERROR unused implementer of `std::ops::FnOnce` that must be used
    square_fn_mut(); //~ ERROR unused implementer of `std::ops::FnMut` that must be used
    square_fn(); //~ ERROR unused implementer of `std::ops::Fn` that must be used
@@ -0,0 +1,40 @@
+// The value of `x` is known to the out-of-bounds lint, but the MIR keeps reading it.
+
+fn main() {
+    let x: usize = 2;
+    let _y = [0u32, 1, 2, 3][x];
+}
+
+// END RUST SOURCE
+// START rustc.main.ConstProp.before.mir
+//  bb0: {
+//      ...
+//      _1 = const 2usize;
+//      ...
+//      _4 = _1;
+//      _5 = const 4usize;
+//      _6 = Lt(_4, _5);
+//      assert(move _6, "index out of bounds: the len is move _5 but the index is _4") -> bb1;
+//  }
+//  bb1: {
+//      _2 = _3[_4];
+//      ...
+//      return;
+//  }
+// END rustc.main.ConstProp.before.mir
+// START rustc.main.ConstProp.after.mir
+//  bb0: {
+//      ...
+//      _1 = const 2usize;
+//      ...
+//      _4 = _1;
+//      _5 = const 4usize;
+//      _6 = Lt(_4, _5);
+//      assert(move _6, "index out of bounds: the len is move _5 but the index is _4") -> bb1;
+//  }
+//  bb1: {
+//      _2 = _3[_4];
+//      ...
+//      return;
+//  }
+// END rustc.main.ConstProp.after.mir
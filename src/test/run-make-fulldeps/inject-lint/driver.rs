@@ -0,0 +1,49 @@
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_interface;
+#[macro_use]
+extern crate rustc_session;
+
+use rustc_driver::Compilation;
+use rustc_hir::ItemKind;
+use rustc_interface::{interface, Queries};
+
+declare_lint!(INJECTED, Warn, "functions found by the driver");
+
+struct InjectingCallbacks;
+
+impl rustc_driver::Callbacks for InjectingCallbacks {
+    fn config(&mut self, config: &mut interface::Config) {
+        config.register_lints = Some(Box::new(|_, lint_store| {
+            lint_store.register_lints(&[INJECTED]);
+        }));
+    }
+
+    fn after_analysis<'tcx>(
+        &mut self,
+        _compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        queries.global_ctxt().unwrap().peek_mut().enter(|tcx| {
+            for item in tcx.hir().krate().items.values() {
+                if let ItemKind::Fn(..) = item.kind {
+                    let msg = format!("function `{}` found by the driver", item.ident);
+                    tcx.inject_lint(INJECTED, item.hir_id, item.span, &msg, |err| {
+                        err.note("only called for the lints that are emitted");
+                    });
+                }
+            }
+        });
+        Compilation::Stop
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    rustc_driver::catch_fatal_errors(|| {
+        rustc_driver::run_compiler(&args, &mut InjectingCallbacks, None, None).ok();
+    })
+    .ok();
+}
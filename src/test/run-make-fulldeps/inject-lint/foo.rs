@@ -0,0 +1,6 @@
+#![crate_type = "lib"]
+
+pub fn linted() {}
+
+#[allow(injected)]
+pub fn allowed() {}
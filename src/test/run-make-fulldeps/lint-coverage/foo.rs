@@ -0,0 +1,9 @@
+#![warn(unused_variables)]
+#![allow(while_true)]
+
+fn main() {
+    let x = 1;
+    while true {
+        break;
+    }
+}
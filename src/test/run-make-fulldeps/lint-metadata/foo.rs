@@ -0,0 +1,7 @@
+#![warn(unused_variables)]
+#![allow(empty_loops)]
+
+fn main() {
+    let x = 1;
+    while true {}
+}
@@ -0,0 +1,5 @@
+#![warn(exceeding_bitshifts, const_err)]
+
+fn main() {
+    let _x = 1_u8 << 8;
+}
@@ -0,0 +1,24 @@
+#![crate_type = "dylib"]
+#![feature(plugin_registrar, rustc_private)]
+
+extern crate rustc;
+#[macro_use]
+extern crate rustc_session;
+extern crate rustc_driver;
+
+use rustc::lint::{EarlyLintPass, LintId};
+use rustc_driver::plugin::Registry;
+
+declare_lint!(TEST_LINT, Warn, "Warn about items named 'lintme'");
+declare_tool_lint!(pub clippy::TEST_TOOL_LINT, Warn, "Warn about other stuff");
+
+declare_lint_pass!(Pass => [TEST_LINT, TEST_TOOL_LINT]);
+
+impl EarlyLintPass for Pass {}
+
+#[plugin_registrar]
+pub fn plugin_registrar(reg: &mut Registry) {
+    reg.lint_store.register_lints(&[&TEST_LINT, &TEST_TOOL_LINT]);
+    reg.lint_store.register_early_pass(|| Box::new(Pass));
+    reg.lint_store.register_group(true, "test_group", None, vec![LintId::of(&TEST_LINT)]);
+}
@@ -0,0 +1,20 @@
+#!/usr/bin/env python
+
+import sys
+import json
+
+with open(sys.argv[1]) as f:
+    output = json.load(f)
+
+lints = dict((lint["name"], lint) for lint in output["lints"])
+groups = dict((group["name"], group) for group in output["groups"])
+
+# A builtin lint.
+assert lints["while_true"]["source"] == {"kind": "rustc", "name": None}
+assert groups["unused"]["source"] == {"kind": "rustc", "name": None}
+
+# The lints and the group of the plugin name the library they were loaded from, tool lints too.
+for source in [lints["test_lint"]["source"], lints["clippy::test_tool_lint"]["source"],
+               groups["test_group"]["source"]]:
+    assert source["kind"] == "plugin", source
+    assert "lint_plugin" in source["name"], source
@@ -0,0 +1,69 @@
+#![feature(rustc_private)]
+
+extern crate rustc_errors;
+extern crate rustc_lint;
+extern crate rustc_parse;
+extern crate rustc_session;
+extern crate rustc_span;
+extern crate syntax;
+
+use rustc_errors::Level;
+use rustc_lint::{check_ast_crate_standalone, StandaloneLintOptions};
+use rustc_session::config::CrateType;
+use rustc_session::lint;
+use rustc_session::parse::ParseSess;
+use rustc_span::edition::Edition;
+use rustc_span::source_map::{FileName, FilePathMapping};
+
+const SRC: &str = r#"
+struct point;
+
+fn main() {
+    let _x = (1);
+}
+"#;
+
+fn lint(lint_opts: Vec<(String, lint::Level)>) -> Vec<(Level, String)> {
+    let parse_sess = ParseSess::new(FilePathMapping::empty());
+    let krate = rustc_parse::parse_crate_from_source_str(
+        FileName::Custom("standalone".to_string()),
+        SRC.to_string(),
+        &parse_sess,
+    )
+    .unwrap_or_else(|mut err| {
+        err.emit();
+        panic!("failed to parse the test crate")
+    });
+
+    let options = StandaloneLintOptions {
+        crate_name: "standalone".to_string(),
+        crate_types: vec![CrateType::Executable],
+        edition: Edition::Edition2018,
+        lint_opts,
+        lint_cap: None,
+    };
+    check_ast_crate_standalone(&krate, parse_sess.source_map().clone(), options)
+        .iter()
+        .map(|diag| (diag.level, diag.message()))
+        .collect()
+}
+
+fn main() {
+    syntax::with_default_globals(|| {
+        let camel_case = "type `point` should have an upper camel case name".to_string();
+        let parens = "unnecessary parentheses around assigned value".to_string();
+
+        assert_eq!(
+            lint(vec![]),
+            vec![(Level::Warning, camel_case.clone()), (Level::Warning, parens.clone())]
+        );
+        assert_eq!(
+            lint(vec![("unused_parens".to_string(), lint::Deny)]),
+            vec![(Level::Warning, camel_case), (Level::Error, parens.clone())]
+        );
+        assert_eq!(
+            lint(vec![("non_camel_case_types".to_string(), lint::Allow)]),
+            vec![(Level::Warning, parens)]
+        );
+    });
+}
@@ -0,0 +1,4 @@
+#![feature(plugin)]
+#![plugin(lint_plugin)]
+
+fn main() {}
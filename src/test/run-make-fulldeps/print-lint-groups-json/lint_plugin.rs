@@ -0,0 +1,29 @@
+#![crate_type = "dylib"]
+#![feature(plugin_registrar, rustc_private)]
+
+extern crate rustc;
+#[macro_use]
+extern crate rustc_session;
+extern crate rustc_driver;
+
+use rustc::lint::{EarlyLintPass, LintId};
+use rustc_driver::plugin::Registry;
+
+declare_tool_lint!(pub clippy::FIRST_LINT, Warn, "Warn about stuff");
+declare_tool_lint!(pub clippy::SECOND_LINT, Warn, "Warn about other stuff");
+
+declare_lint_pass!(Pass => [FIRST_LINT, SECOND_LINT]);
+
+impl EarlyLintPass for Pass {}
+
+#[plugin_registrar]
+pub fn plugin_registrar(reg: &mut Registry) {
+    reg.lint_store.register_lints(&[&FIRST_LINT, &SECOND_LINT]);
+    reg.lint_store.register_early_pass(|| Box::new(Pass));
+    reg.lint_store.register_group(
+        true,
+        "clippy::group",
+        Some("clippy_group"),
+        vec![LintId::of(&SECOND_LINT), LintId::of(&FIRST_LINT)],
+    );
+}
@@ -0,0 +1,33 @@
+#!/usr/bin/env python
+
+import sys
+import json
+
+with open(sys.argv[1]) as f:
+    output = json.load(f)
+
+assert sorted(output.keys()) == ["groups", "removed", "renamed"], output.keys()
+groups = output["groups"]
+renamed = output["renamed"]
+removed = output["removed"]
+
+# Every group maps to a sorted list of lint names.
+for name, lints in groups.items():
+    assert lints == sorted(lints), "the lints of `%s` aren't sorted" % name
+
+assert "unused_variables" in groups["unused"]
+assert "non_camel_case_types" in groups["nonstandard_style"]
+
+# A renamed lint and a removed one, which the groups don't list.
+assert renamed["bare_trait_object"] == "bare_trait_objects"
+assert removed["unknown_features"] == "replaced by an error"
+for lints in groups.values():
+    assert "bare_trait_object" not in lints
+    assert "unknown_features" not in lints
+
+# The group registered by the plugin, under its tool's name, and its deprecated name.
+if "--plugin" in sys.argv:
+    assert groups["clippy::group"] == ["clippy::first_lint", "clippy::second_lint"]
+    assert renamed["clippy_group"] == "clippy::group"
+else:
+    assert "clippy::group" not in groups
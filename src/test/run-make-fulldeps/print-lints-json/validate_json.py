@@ -0,0 +1,31 @@
+#!/usr/bin/env python
+
+import sys
+import json
+
+with open(sys.argv[1]) as f:
+    output = json.load(f)
+
+lints = dict((lint["name"], lint) for lint in output["lints"])
+groups = dict((group["name"], group) for group in output["groups"])
+
+for lint in lints.values():
+    for key in ["default_level", "edition_level", "level_for_current_edition", "desc", "doc",
+                "groups", "future_incompatible", "report_in_external_macro", "is_plugin",
+                "source", "docs_url"]:
+        assert key in lint, "lint `%s` has no `%s`" % (lint["name"], key)
+
+# A builtin lint, with the doc comment of its `declare_lint!`.
+while_true = lints["while_true"]
+assert while_true["default_level"] == "warn"
+assert while_true["source"] == {"kind": "rustc", "name": None}
+assert while_true["doc"].startswith("The `while_true` lint detects `while true { }`.")
+
+# A lint in a group, and the group listing it.
+unused_variables = lints["unused_variables"]
+assert "unused" in unused_variables["groups"]
+assert "unused_variables" in groups["unused"]["lints"]
+assert groups["unused"]["is_plugin"] is False
+
+# A future incompatible lint.
+assert lints["safe_packed_borrows"]["future_incompatible"]["reference"].startswith("issue #")
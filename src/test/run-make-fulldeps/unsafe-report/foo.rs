@@ -0,0 +1,28 @@
+macro_rules! zeroed {
+    () => {
+        unsafe { std::mem::zeroed() }
+    };
+}
+
+pub unsafe trait Zeroable {}
+
+unsafe impl Zeroable for u32 {}
+
+pub mod raw {
+    pub unsafe fn read(ptr: *const u32) -> u32 {
+        *ptr
+    }
+
+    pub struct Buffer(pub *const u32);
+
+    impl Buffer {
+        pub fn first(&self) -> u32 {
+            unsafe { read(self.0) }
+        }
+    }
+}
+
+fn main() {
+    let x: u32 = zeroed!();
+    let _ = raw::Buffer(&x).first();
+}
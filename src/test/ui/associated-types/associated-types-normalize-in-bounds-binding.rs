@@ -1,5 +1,6 @@
 // run-pass
 #![allow(unused_variables)]
+#![allow(empty_loops)]
 // Test that we normalize associated types that appear in a bound that
 // contains a binding. Issue #21664.
 
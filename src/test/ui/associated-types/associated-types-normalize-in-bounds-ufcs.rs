@@ -1,5 +1,6 @@
 // run-pass
 #![allow(unused_variables)]
+#![allow(empty_loops)]
 // Test that we normalize associated types that appear in bounds; if
 // we didn't, the call to `self.split2()` fails to type check.
 
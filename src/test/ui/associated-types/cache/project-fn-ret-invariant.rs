@@ -8,7 +8,7 @@
 
 // revisions: ok oneuse transmute krisskross
 
-#![allow(dead_code, unused_variables)]
+#![allow(dead_code, unused_variables, empty_loops)]
 
 use std::marker::PhantomData;
 
@@ -5,6 +5,7 @@
 #![deny(dead_code)]
 #![allow(unused_variables)]
 #![allow(unused_must_use)]
+#![allow(unused_futures)]
 #![allow(path_statements)]
 
 // Test that the drop order for locals in a fn and async fn matches up.
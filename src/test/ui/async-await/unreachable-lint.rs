@@ -1,6 +1,7 @@
 // check-pass
 // edition:2018
 #![deny(unreachable_code)]
+#![allow(empty_loops)]
 
 async fn foo() {
     endless().await;
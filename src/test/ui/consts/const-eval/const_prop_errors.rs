@@ -1,4 +1,6 @@
 // build-pass (FIXME(62277): could be check-pass?)
+#![allow(empty_loops)]
+#![allow(shadowed_generic_params)]
 
 pub trait Foo {
     fn foo(self) -> u32;
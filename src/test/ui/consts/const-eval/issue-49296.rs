@@ -2,6 +2,7 @@
 
 #![feature(const_fn)]
 #![feature(const_fn_union)]
+#![allow(shadowed_generic_params)]
 
 const unsafe fn transmute<T: Copy, U: Copy>(t: T) -> U {
     #[repr(C)]
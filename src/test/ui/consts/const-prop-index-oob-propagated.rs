@@ -0,0 +1,39 @@
+// build-fail
+
+#![feature(slice_patterns)]
+
+const LEN: usize = 3;
+const LAST: usize = LEN - 1;
+const PAST_END: usize = LAST + 1;
+
+#[derive(Clone, Copy)]
+enum Slot {
+    First,
+    Second,
+    Third,
+    Fourth,
+}
+
+fn main() {
+    let array = [0u8; LEN];
+
+    let index = PAST_END;
+    array[index]; //~ ERROR index out of bounds: the len is 3 but the index is 3
+
+    let slot = Slot::Fourth;
+    array[slot as usize]; //~ ERROR index out of bounds: the len is 3 but the index is 3
+
+    let reference = &array;
+    reference[4]; //~ ERROR index out of bounds: the len is 3 but the index is 4
+
+    let [_, rest @ ..] = &array;
+    rest[LAST]; //~ ERROR index out of bounds: the len is 2 but the index is 2
+
+    // Not linted, the index is checked before it is used.
+    let checked = PAST_END;
+    if checked < array.len() {
+        array[checked];
+    }
+
+    let _ = (Slot::First, Slot::Second, Slot::Third);
+}
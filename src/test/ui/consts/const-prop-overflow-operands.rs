@@ -0,0 +1,18 @@
+// build-fail
+// compile-flags: -C overflow-checks=on
+
+#![allow(unused_variables)]
+
+const CONFIG_PADDING: u8 = 4;
+const HEADER_LEN: u8 = 250;
+
+fn main() {
+    let a = std::u8::MAX + CONFIG_PADDING;
+    //~^ ERROR attempt to add with overflow
+    let b = HEADER_LEN + CONFIG_PADDING * 2;
+    //~^ ERROR attempt to add with overflow
+    let c = CONFIG_PADDING - HEADER_LEN;
+    //~^ ERROR attempt to subtract with overflow
+    let d = 1i64 << 70;
+    //~^ ERROR attempt to shift left with overflow
+}
@@ -1,4 +1,5 @@
 // build-pass (FIXME(62277): could be check-pass?)
+#![allow(empty_loops)]
 
 fn main() {
 
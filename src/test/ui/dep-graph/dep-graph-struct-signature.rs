@@ -4,7 +4,7 @@
 // compile-flags: -Z query-dep-graph
 
 #![feature(rustc_attrs)]
-#![allow(dead_code)]
+#![allow(dead_code, empty_loops)]
 #![allow(unused_variables)]
 
 fn main() { }
@@ -0,0 +1,10 @@
+#![crate_type = "lib"]
+
+#[rustc_blocking] //~ ERROR the `#[rustc_blocking]` attribute is an experimental feature
+pub fn wait_for_disk() {}
+
+#[rustc_executor_entry_point]
+//~^ ERROR the `#[rustc_executor_entry_point]` attribute is an experimental feature
+pub fn poll_with<F: FnOnce()>(f: F) {
+    f()
+}
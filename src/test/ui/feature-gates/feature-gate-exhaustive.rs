@@ -0,0 +1,8 @@
+#![crate_type = "lib"]
+
+#[exhaustive] //~ ERROR the `#[exhaustive]` attribute is an experimental feature
+pub enum Ordering {
+    Less,
+    Equal,
+    Greater,
+}
@@ -0,0 +1,4 @@
+#![crate_type = "lib"]
+
+#[allow(dead_code, expires = "9999-12-31")] //~ ERROR lint attribute expiry is experimental
+fn unused() {}
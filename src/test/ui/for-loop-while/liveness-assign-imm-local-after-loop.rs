@@ -1,6 +1,7 @@
 // run-pass
 #![allow(dead_code)]
 #![allow(unused_assignments)]
+#![allow(empty_loops)]
 // pretty-expanded FIXME #23616
 
 #![allow(unreachable_code)]
@@ -1,6 +1,7 @@
 // run-pass
 
 #![allow(unreachable_code)]
+#![allow(empty_loops)]
 #![feature(never_type)]
 
 #[allow(unused)]
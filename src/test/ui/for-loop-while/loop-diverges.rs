@@ -1,5 +1,6 @@
 // run-pass
 #![allow(unused_parens)]
+#![allow(empty_loops)]
 // pretty-expanded FIXME #23616
 
 /* Make sure a loop{} can be the tailexpr in the body
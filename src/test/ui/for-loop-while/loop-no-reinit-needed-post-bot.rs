@@ -1,5 +1,6 @@
 // run-pass
 // pretty-expanded FIXME #23616
+#![allow(empty_loops)]
 
 struct S;
 // Ensure S is moved, not copied, on assignment.
@@ -3,6 +3,7 @@
 
 // build-pass (FIXME(62277): could be check-pass?)
 
+#![allow(empty_loops)]
 #![feature(rustc_attrs)]
 
 struct StLt<#[rustc_dummy] 'a>(&'a u32);
@@ -1,4 +1,5 @@
 // check-pass
+#![allow(empty_loops)]
 
 // This used to ICE because the "if" being unreachable was not handled correctly
 fn err() {
@@ -3,6 +3,8 @@
 
 // check-pass
 
+#![allow(empty_loops)]
+
 fn server() -> impl FilterBase2 {
     segment2(|| { loop { } }).map2(|| "")
 }
@@ -1,5 +1,6 @@
 // check-pass
 // Check that associated types are `Sized`
+#![allow(empty_loops)]
 
 // pretty-expanded FIXME #23616
 
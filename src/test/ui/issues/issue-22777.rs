@@ -6,6 +6,7 @@
 // pretty-expanded FIXME #23616
 
 #![allow(non_camel_case_types)]
+#![allow(empty_loops)]
 
 pub fn noop_fold_impl_item() -> SmallVector<ImplItem> {
     loop  { }
@@ -1,4 +1,6 @@
 // run-pass
+#![allow(empty_loops)]
+
 #[allow(unused)]
 fn main() {
     || {
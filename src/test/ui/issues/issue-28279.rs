@@ -1,5 +1,6 @@
 // check-pass
 #![allow(dead_code)]
+#![allow(empty_loops)]
 use std::rc::Rc;
 
 fn test1() -> Rc<dyn for<'a> Fn(&'a usize) + 'static> {
@@ -1,5 +1,6 @@
 // check-pass
 #![allow(dead_code)]
+#![allow(empty_loops)]
 trait Make {
     type Out;
 
@@ -1,3 +1,5 @@
+#![allow(shadowed_generic_params)]
+
 fn foo<T>() {
     struct Foo {
         x: T, //~ ERROR can't use generic parameters from outer function
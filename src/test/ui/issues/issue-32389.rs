@@ -1,4 +1,6 @@
 // run-pass
+#![allow(empty_loops)]
+
 fn foo<T>() -> T { loop {} }
 
 fn test() {
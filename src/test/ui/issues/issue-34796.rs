@@ -1,5 +1,6 @@
 // run-pass
 #![allow(dead_code)]
+#![allow(empty_loops)]
 // This test case exposes conditions where the encoding of a trait object type
 // with projection predicates would differ between this crate and the upstream
 // crate, because the predicates were encoded in different order within each
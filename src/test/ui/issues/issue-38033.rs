@@ -1,4 +1,6 @@
 // run-pass
+#![allow(empty_loops)]
+
 use std::marker;
 use std::mem;
 
@@ -1,5 +1,6 @@
 // run-pass
 #![allow(unused)]
+#![allow(empty_loops)]
 
 fn main() {
 }
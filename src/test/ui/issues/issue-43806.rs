@@ -1,5 +1,6 @@
 // check-pass
 
+#![allow(empty_loops)]
 #![deny(unused_results)]
 
 enum Void {}
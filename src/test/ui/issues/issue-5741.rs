@@ -2,6 +2,7 @@
 // pretty-expanded FIXME #23616
 #![allow(while_true)]
 #![allow(unreachable_code)]
+#![allow(empty_loops)]
 
 pub fn main() {
     return;
@@ -1,5 +1,6 @@
 // run-pass
 #![allow(dead_code)]
+#![allow(shadowed_generic_params)]
 
 fn f<T>() -> bool {
     enum E<T> { V(T) }
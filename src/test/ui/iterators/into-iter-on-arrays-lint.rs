@@ -1,6 +1,8 @@
 // run-pass
 // run-rustfix
 
+#![allow(unused_allocation)]
+
 fn main() {
     let small = [1, 2];
     let big = [0u8; 33];
@@ -2,6 +2,7 @@
 
 #![allow(dead_code)]
 #![allow(unused_parens)]
+#![allow(shadowed_generic_params)]
 // Issue #1818
 
 
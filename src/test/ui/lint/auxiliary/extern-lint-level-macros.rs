@@ -0,0 +1,4 @@
+#[macro_export]
+macro_rules! unsafe_block {
+    ($e:expr) => (unsafe { $e })
+}
@@ -0,0 +1,8 @@
+#[repr(C)]
+pub struct Wrapper<T> {
+    pub inner: T,
+}
+
+pub struct Opaque {
+    pub x: u32,
+}
@@ -0,0 +1,18 @@
+#[must_use]
+pub fn make() -> u32 {
+    0
+}
+
+#[macro_export]
+macro_rules! discard {
+    ($e:expr) => {
+        $e;
+    };
+}
+
+#[macro_export]
+macro_rules! internal_temporary {
+    () => {{
+        $crate::make();
+    }};
+}
@@ -0,0 +1,26 @@
+// compile-flags:-Zshare-generics=yes
+// no-prefer-dynamic
+
+#![crate_type = "rlib"]
+
+pub fn get<T: Copy>(values: &[T], i: usize) -> T {
+    values[i]
+}
+
+pub fn boxed<T>(value: T) -> &'static mut T {
+    Box::leak(Box::new(value))
+}
+
+pub fn opaque_get(values: &[u32], i: usize) -> u32 {
+    values[i]
+}
+
+pub fn opaque_boxed(value: u32) -> &'static mut u32 {
+    Box::leak(Box::new(value))
+}
+
+// The instances used here are shared with the crates linking to this one.
+pub fn instantiate() {
+    get(&[0u32], 0);
+    boxed(0u32);
+}
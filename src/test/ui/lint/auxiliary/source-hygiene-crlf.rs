@@ -0,0 +1,3 @@
+// ignore-tidy-cr
+
+pub fn f() {}
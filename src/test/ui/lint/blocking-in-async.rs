@@ -0,0 +1,44 @@
+// edition:2018
+
+#![feature(blocking_functions)]
+#![deny(blocking_in_async)]
+#![allow(dead_code)]
+
+use std::thread;
+use std::time::Duration;
+
+#[rustc_blocking]
+fn wait_for_disk() {}
+
+#[rustc_executor_entry_point]
+fn poll_with<F: FnOnce()>(f: F) {
+    f()
+}
+
+async fn work() {
+    thread::sleep(Duration::from_millis(1));
+    //~^ ERROR call to blocking function `std::thread::sleep` in async code
+    wait_for_disk(); //~ ERROR call to blocking function `wait_for_disk` in async code
+    let handle = thread::spawn(|| {});
+    handle.join().unwrap();
+    //~^ ERROR call to blocking function `std::thread::JoinHandle::<T>::join` in async code
+}
+
+async fn in_closure() {
+    // The closure may well run on a dedicated thread.
+    let _ = || wait_for_disk();
+}
+
+fn on_executor() {
+    poll_with(|| wait_for_disk()); //~ ERROR call to blocking function `wait_for_disk` in async code
+    poll_with(|| {
+        let _ = || wait_for_disk();
+    });
+}
+
+fn not_async() {
+    thread::sleep(Duration::from_millis(1));
+    wait_for_disk();
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+#![deny(broad_unsafe_code_allows)]
+#![allow(unsafe_code)] //~ ERROR `allow(unsafe_code)` applies to the whole crate
+
+#[allow(dead_code, unsafe_code)] //~ ERROR `allow(unsafe_code)` applies to the whole module
+mod raw {
+    pub fn read(ptr: *const u8) -> u8 {
+        unsafe { *ptr }
+    }
+}
+
+mod inner {
+    #![allow(unsafe_code)] //~ ERROR `allow(unsafe_code)` applies to the whole module
+}
+
+mod exempt {
+    #[allow(unsafe_code)]
+    pub fn read(ptr: *const u8) -> u8 {
+        unsafe { *ptr }
+    }
+}
+
+fn main() {
+    exempt::read(&0);
+}
@@ -0,0 +1,21 @@
+// The same lint is buffered for both copies of the function, with the same span and message.
+// Allowing it in the first copy doesn't hide it in the second.
+
+macro_rules! twice {
+    ($item:item) => {
+        #[allow(ill_formed_attribute_input)]
+        mod a { $item }
+        mod b { $item }
+    };
+}
+
+twice! {
+    #[inline = ""] //~ ERROR attribute must be of the form
+    //~^ WARN this was previously accepted
+    pub fn f() {}
+}
+
+fn main() {
+    a::f();
+    b::f();
+}
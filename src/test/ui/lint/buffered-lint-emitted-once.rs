@@ -0,0 +1,20 @@
+// The malformed attribute is validated in both copies of the function the macro expands to, so
+// the same lint is buffered twice, with the same span and message. It is only reported once.
+
+macro_rules! twice {
+    ($item:item) => {
+        mod a { $item }
+        mod b { $item }
+    };
+}
+
+twice! {
+    #[inline = ""] //~ ERROR attribute must be of the form
+    //~^ WARN this was previously accepted
+    pub fn f() {}
+}
+
+fn main() {
+    a::f();
+    b::f();
+}
@@ -0,0 +1,18 @@
+// Checks that lints buffered before the lint passes run, like `unused_imports`, point at the
+// outermost local macro invocation they come from.
+
+// check-pass
+
+#![warn(unused_imports)]
+
+macro_rules! inner {
+    () => { use std::string::ToString; } //~ WARNING unused import
+}
+
+macro_rules! outer {
+    () => { inner!(); }
+}
+
+mod a { outer!(); }
+
+fn main() {}
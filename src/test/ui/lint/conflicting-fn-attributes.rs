@@ -0,0 +1,28 @@
+#![deny(conflicting_fn_attributes)]
+#![allow(dead_code)]
+
+#[cold]
+#[inline(always)]
+fn cold_inline() {} //~^^ ERROR `#[cold]` conflicts with `#[inline(always)]`
+
+#[inline(always)]
+#[inline(never)]
+fn always_never() {} //~^^ ERROR `#[inline(always)]` conflicts with `#[inline(never)]`
+
+#[no_mangle]
+#[export_name = "exported"]
+pub fn mangled() {} //~^^ ERROR `#[no_mangle]` conflicts with `#[export_name]`
+
+struct S;
+
+impl S {
+    #[inline(always)]
+    #[cold]
+    fn method(&self) {} //~^^ ERROR `#[cold]` conflicts with `#[inline(always)]`
+}
+
+#[cold]
+#[inline(never)]
+fn cold_never() {}
+
+fn main() {}
@@ -0,0 +1,10 @@
+// compile-flags: -Z unstable-options --deny-lints-from-env=RUSTC_DENY_LINTS
+// rustc-env:RUSTC_DENY_LINTS=nonstandard-style,non_upper_case_globals
+
+fn Bar() {}
+//~^ ERROR function `Bar` should have a snake case name
+
+static bar: i32 = 0;
+//~^ ERROR static variable `bar` should have an upper case name
+
+fn main() {}
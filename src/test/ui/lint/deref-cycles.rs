@@ -0,0 +1,79 @@
+#![deny(deref_cycles)]
+
+use std::ops::Deref;
+
+pub struct Loop;
+
+impl Deref for Loop {
+    //~^ ERROR `Deref` implementation of `Loop` targets `Loop` itself
+    type Target = Self;
+
+    fn deref(&self) -> &Self {
+        self
+    }
+}
+
+pub struct Ping;
+pub struct Pong;
+
+impl Deref for Ping {
+    //~^ ERROR dereferencing `Ping` leads back to `Ping`
+    type Target = Pong;
+
+    fn deref(&self) -> &Pong {
+        &Pong
+    }
+}
+
+impl Deref for Pong {
+    type Target = Ping;
+
+    fn deref(&self) -> &Ping {
+        &Ping
+    }
+}
+
+pub struct Boxed(Box<Boxed>);
+
+impl Deref for Boxed {
+    //~^ ERROR dereferencing `Boxed` leads back to `Boxed`
+    type Target = Box<Boxed>;
+
+    fn deref(&self) -> &Box<Boxed> {
+        &self.0
+    }
+}
+
+pub struct Reference;
+
+impl Deref for Reference {
+    //~^ ERROR dereferencing `Reference` leads back to `Reference`
+    type Target = &'static Reference;
+
+    fn deref(&self) -> &&'static Reference {
+        &&Reference
+    }
+}
+
+// Not cycles: autoderef stops at `T`, or at `Vec<u8>`.
+pub struct Wrapper<T>(T);
+
+impl<T> Deref for Wrapper<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+pub struct Bytes(Wrapper<Vec<u8>>);
+
+impl Deref for Bytes {
+    type Target = Wrapper<Vec<u8>>;
+
+    fn deref(&self) -> &Wrapper<Vec<u8>> {
+        &self.0
+    }
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+// check-pass
+
+#![crate_type = "lib"]
+#![no_std]
+
+// Embedded programs halt this way, so it is only linted where the lint is enabled.
+pub fn halt() -> ! {
+    loop {}
+}
+
+#[warn(empty_loops)]
+pub fn spin() -> ! {
+    loop {}
+    //~^ WARN empty `loop {}` busy-waits forever
+}
@@ -0,0 +1,20 @@
+#![deny(empty_loops)]
+
+pub fn spin() -> ! {
+    loop {}
+    //~^ ERROR empty `loop {}` busy-waits forever
+}
+
+pub fn wait() {
+    while true {}
+    //~^ ERROR empty `while true {}` busy-waits forever
+    //~| WARN denote infinite loops with `loop { ... }`
+}
+
+pub fn hint() -> ! {
+    loop {
+        std::sync::atomic::spin_loop_hint();
+    }
+}
+
+fn main() {}
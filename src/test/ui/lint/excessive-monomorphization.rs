@@ -0,0 +1,32 @@
+// build-fail
+// compile-flags: -Z instantiation-threshold=3
+
+#![deny(excessive_monomorphization)]
+
+use std::fmt::Debug;
+
+fn pair<A: Debug, B: Debug>(a: A, b: B) -> String { //~ ERROR `pair` is instantiated 4 times
+    format!("{:?} {:?}", a, b)
+}
+
+fn few<T: Debug>(value: T) -> String {
+    format!("{:?}", value)
+}
+
+#[allow(excessive_monomorphization)]
+fn quiet<T: Debug>(value: T) -> String {
+    format!("{:?}", value)
+}
+
+fn main() {
+    pair(1u8, 2u8);
+    pair(1u8, 2u16);
+    pair(1u8, true);
+    pair(1u16, false);
+    few(1u8);
+    few(1u16);
+    quiet(1u8);
+    quiet(1u16);
+    quiet(1u32);
+    quiet(1u64);
+}
@@ -0,0 +1,35 @@
+// compile-flags: --crate-type lib
+#![feature(exhaustive)]
+#![deny(exhaustive_public_types)]
+#![allow(dead_code)]
+
+pub enum Color { //~ ERROR public enum `Color` is exhaustive
+    Red,
+    Green,
+}
+
+pub struct Unit; //~ ERROR public struct `Unit` is exhaustive
+
+pub struct Pair(pub u8, pub u8); //~ ERROR public struct `Pair` is exhaustive
+
+#[non_exhaustive]
+pub enum Extensible {
+    A,
+}
+
+#[exhaustive]
+pub enum Ordering {
+    Less,
+    Equal,
+    Greater,
+}
+
+pub struct Opaque(u8);
+
+pub struct Braced {
+    pub x: u8,
+}
+
+enum Private {
+    A,
+}
@@ -0,0 +1,29 @@
+#![feature(lint_expiry, lint_reasons)]
+#![deny(expired_lint_attributes)]
+
+#[allow(dead_code, expires = "2020-01-31")]
+//~^ ERROR this `allow` attribute expired on 2020-01-31
+fn past() {}
+
+#[allow(dead_code, expires = "9999-12-31")]
+fn future() {}
+
+#[allow(unused_variables, reason = "kept until the next release", expires = "1.0.0")]
+//~^ ERROR this `allow` attribute expired with Rust 1.0.0
+fn version() {
+    let x = 1;
+}
+
+#[allow(dead_code, expires = "tomorrow")]
+//~^ ERROR malformed lint attribute input
+//~| ERROR malformed lint attribute input
+//~| ERROR malformed lint attribute input
+fn malformed() {}
+
+#[allow(expires = "2020-01-31", dead_code)]
+//~^ ERROR malformed lint attribute input
+//~| ERROR malformed lint attribute input
+//~| ERROR malformed lint attribute input
+fn misplaced() {}
+
+fn main() {}
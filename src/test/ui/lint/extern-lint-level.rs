@@ -0,0 +1,13 @@
+// Checks that `--extern-lint-level` sets the level of lints in code expanded from the named
+// crate's macros, and only there.
+
+// aux-build:extern-lint-level-macros.rs
+// compile-flags: -Z unstable-options --extern-lint-level=extern_lint_level_macros=deny:unsafe_code
+
+#[macro_use]
+extern crate extern_lint_level_macros;
+
+fn main() {
+    let _ = unsafe_block!(0); //~ ERROR usage of an `unsafe` block
+    let _ = unsafe { 0 };
+}
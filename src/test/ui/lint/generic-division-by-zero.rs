@@ -0,0 +1,37 @@
+// build-fail
+
+#![deny(generic_division_by_zero)]
+
+trait Buckets {
+    const COUNT: usize;
+}
+
+struct Empty;
+struct Four;
+
+impl Buckets for Empty {
+    const COUNT: usize = 0;
+}
+
+impl Buckets for Four {
+    const COUNT: usize = 4;
+}
+
+fn bucket<B: Buckets>(hash: usize) -> usize {
+    hash % B::COUNT
+    //~^ ERROR attempt to calculate the remainder with a divisor of zero in `bucket::<Empty>`
+}
+
+fn average<B: Buckets>(total: usize) -> usize {
+    total / B::COUNT
+    //~^ ERROR attempt to divide by zero in `average::<Empty>`
+}
+
+fn stats<B: Buckets>(hash: usize) -> (usize, usize) {
+    (bucket::<B>(hash), average::<B>(hash))
+}
+
+fn main() {
+    stats::<Four>(7);
+    stats::<Empty>(7);
+}
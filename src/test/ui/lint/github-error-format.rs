@@ -0,0 +1,10 @@
+// compile-flags: -Z unstable-options --error-format=github
+
+#![deny(non_snake_case)]
+#![allow(empty_loops)]
+
+fn Foo() {}
+
+fn main() {
+    while true {}
+}
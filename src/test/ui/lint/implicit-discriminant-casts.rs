@@ -0,0 +1,26 @@
+#![deny(implicit_discriminant_casts)]
+
+enum Color {
+    Red,
+    Green,
+}
+
+#[repr(u8)]
+enum Level {
+    Low,
+    High,
+}
+
+enum Code {
+    Ok = 0,
+    Err = 1,
+}
+
+fn main() {
+    let _ = Color::Green as u8; //~ ERROR cast of `Color` to `u8` uses its implicit discriminants
+    let _ = Color::Red;
+    let _ = Level::High as u8;
+    let _ = Level::Low;
+    let _ = Code::Err as i32;
+    let _ = Code::Ok;
+}
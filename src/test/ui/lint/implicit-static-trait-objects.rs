@@ -0,0 +1,28 @@
+// run-rustfix
+#![deny(implicit_static_trait_objects)]
+#![allow(dead_code)]
+
+use std::fmt::Debug;
+
+pub struct Handlers<'a> {
+    pub boxed: Box<dyn Fn()>, //~ ERROR trait object in a public field has an implicit `'static`
+    pub borrowed: &'a dyn Debug,
+    pub explicit: Box<dyn Fn() + 'a>,
+    pub explicit_static: Box<dyn Fn() + 'static>,
+    pub forever: &'static dyn Debug, //~ ERROR trait object in a public field has an implicit
+    private: Box<dyn Debug>,
+}
+
+pub fn make() -> Box<dyn Debug> { //~ ERROR trait object in a public return type has an implicit
+    Box::new(())
+}
+
+pub fn borrow(x: &u8) -> &dyn Debug {
+    x
+}
+
+fn private() -> Box<dyn Debug> {
+    Box::new(())
+}
+
+fn main() {}
@@ -0,0 +1,77 @@
+#![deny(inconsistent_trait_impls)]
+#![allow(dead_code)]
+
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+#[derive(PartialEq, Eq)]
+struct Key {
+    id: u32,
+    name: String,
+}
+
+impl Hash for Key {
+    //~^ ERROR `Hash` is implemented manually for `Key`, but `PartialEq` is derived
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+#[derive(Hash)]
+struct CaseInsensitive(String);
+
+impl PartialEq for CaseInsensitive {
+    //~^ ERROR `PartialEq` is implemented manually for `CaseInsensitive`, but `Hash` is derived
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+#[derive(PartialEq, Eq, Ord)]
+struct Version(u32);
+
+impl PartialOrd for Version {
+    //~^ ERROR `PartialOrd` is implemented manually for `Version`, but `Ord` is derived
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        other.0.partial_cmp(&self.0)
+    }
+}
+
+// Both derived, or both implemented manually.
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Derived(u32);
+
+struct Manual(u32);
+
+impl PartialEq for Manual {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Hash for Manual {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+// A `partial_cmp` delegating to `Ord` agrees with it, derived or not.
+#[derive(PartialEq, Eq, Ord)]
+struct Delegating(u32);
+
+impl PartialOrd for Delegating {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(PartialEq, Eq, Ord)]
+struct DelegatingPath(u32);
+
+impl PartialOrd for DelegatingPath {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(Ord::cmp(self, other))
+    }
+}
+
+fn main() {}
@@ -0,0 +1,52 @@
+#![deny(inline_always_recursion)]
+
+#[inline(always)]
+fn direct(n: u32) -> u32 {
+    if n == 0 { 0 } else { direct(n - 1) + 1 }
+}
+
+#[inline(always)]
+fn is_even(n: u32) -> bool {
+    n == 0 || is_odd(n - 1)
+}
+
+fn is_odd(n: u32) -> bool {
+    n != 0 && is_even(n - 1)
+}
+
+struct Tree(Vec<Tree>);
+
+impl Tree {
+    // Calls made from closures are not followed.
+    #[inline(always)]
+    fn depth(&self) -> usize {
+        1 + self.0.iter().map(|t| t.depth()).max().unwrap_or(0)
+    }
+
+    #[inline(always)]
+    fn size(&self) -> usize {
+        let mut size = 1;
+        for child in &self.0 {
+            size += child.size();
+        }
+        size
+    }
+}
+
+#[inline(always)]
+fn leaf(n: u32) -> u32 {
+    n + 1
+}
+
+#[inline]
+fn hinted(n: u32) -> u32 {
+    if n == 0 { leaf(n) } else { hinted(n - 1) }
+}
+
+fn main() {
+    direct(3);
+    is_even(3);
+    Tree(vec![]).depth();
+    Tree(vec![]).size();
+    hinted(2);
+}
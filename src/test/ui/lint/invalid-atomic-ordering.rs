@@ -0,0 +1,24 @@
+#![deny(invalid_atomic_ordering)]
+
+use std::sync::atomic::{compiler_fence, fence, AtomicUsize, Ordering};
+
+fn main() {
+    let x = AtomicUsize::new(0);
+
+    let _ = x.load(Ordering::Acquire);
+    let _ = x.load(Ordering::Release); //~ ERROR atomic loads cannot have `Release` or `AcqRel`
+    let _ = x.load(Ordering::AcqRel); //~ ERROR atomic loads cannot have `Release` or `AcqRel`
+
+    x.store(1, Ordering::Release);
+    x.store(1, Ordering::Acquire); //~ ERROR atomic stores cannot have `Acquire` or `AcqRel`
+
+    let _ = x.compare_exchange(0, 1, Ordering::SeqCst, Ordering::Acquire);
+    let _ = x.compare_exchange(0, 1, Ordering::SeqCst, Ordering::Release);
+    //~^ ERROR `compare_exchange`'s failure ordering may not be `Release` or `AcqRel`
+    let _ = x.compare_exchange_weak(0, 1, Ordering::Release, Ordering::Acquire);
+    //~^ ERROR `compare_exchange_weak`'s failure ordering may not be stronger
+
+    fence(Ordering::SeqCst);
+    fence(Ordering::Relaxed); //~ ERROR memory fences cannot have `Relaxed` ordering
+    compiler_fence(Ordering::Relaxed); //~ ERROR memory fences cannot have `Relaxed` ordering
+}
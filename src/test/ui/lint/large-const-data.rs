@@ -0,0 +1,17 @@
+// compile-flags: -Z large-const-data-threshold=64
+
+#![deny(large_const_data)]
+#![allow(dead_code)]
+
+const TABLE: [u8; 100] = [0; 100]; //~ ERROR constant `TABLE` takes up 100 bytes
+
+// The pointer and the 80 bytes it points to.
+static BYTES: &[u8] = &[1; 80]; //~ ERROR static `BYTES` takes up 96 bytes
+
+const NESTED: &[&[u8]] = &[&[2; 40], &[3; 40]]; //~ ERROR constant `NESTED` takes up 128 bytes
+
+const SMALL: [u8; 64] = [0; 64];
+
+static SHORT: &str = "short";
+
+fn main() {}
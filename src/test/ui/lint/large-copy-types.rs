@@ -0,0 +1,28 @@
+// compile-flags: -Z large-copy-threshold=64
+
+#![deny(large_copy_types)]
+#![allow(dead_code)]
+
+#[derive(Clone, Copy)]
+struct Matrix { //~ ERROR `Matrix` is `Copy` and 128 bytes in size
+    data: [f64; 16],
+}
+
+struct Buffer([u8; 100]);
+
+impl Clone for Buffer {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl Copy for Buffer {} //~ ERROR `Buffer` is `Copy` and 100 bytes in size
+
+#[derive(Clone, Copy)]
+struct Small([u8; 64]);
+
+// The size depends on `T`, so this can't be checked here.
+#[derive(Clone, Copy)]
+struct Wrapper<T>([T; 100]);
+
+fn main() {}
@@ -0,0 +1,26 @@
+// build-fail
+// edition:2018
+// compile-flags: -Z large-future-threshold=1024
+// normalize-stderr-test "is \d+ bytes" -> "is N bytes"
+
+// A generic future is reported once, however many types it is instantiated with.
+
+#![deny(large_futures)]
+
+use std::future::Future;
+
+async fn yield_now() {}
+
+async fn big<T>(value: T) {
+    //~^ ERROR which is larger than the limit of 1024 bytes
+    let buf = [0u8; 4096];
+    yield_now().await;
+    drop(buf);
+    drop(value);
+}
+
+fn main() {
+    let _: Box<dyn Future<Output = ()>> = Box::new(big(0u8));
+    let _: Box<dyn Future<Output = ()>> = Box::new(big(0u64));
+    let _: Box<dyn Future<Output = ()>> = Box::new(big([0u8; 16]));
+}
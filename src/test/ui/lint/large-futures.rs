@@ -0,0 +1,28 @@
+// build-fail
+// edition:2018
+// compile-flags: -Z large-future-threshold=1024
+// normalize-stderr-test "is \d+ bytes" -> "is N bytes"
+
+#![deny(large_futures)]
+
+use std::future::Future;
+
+async fn yield_now() {}
+
+async fn big() {
+    //~^ ERROR which is larger than the limit of 1024 bytes
+    let buf = [0u8; 4096];
+    yield_now().await;
+    drop(buf);
+}
+
+async fn small() {
+    let buf = [0u8; 16];
+    yield_now().await;
+    drop(buf);
+}
+
+fn main() {
+    let _: Box<dyn Future<Output = ()>> = Box::new(big());
+    let _: Box<dyn Future<Output = ()>> = Box::new(small());
+}
@@ -0,0 +1,27 @@
+// build-fail
+// compile-flags: -Z stack-frame-threshold=1024
+// normalize-stderr-test "estimated at \d+ bytes" -> "estimated at N bytes"
+
+#![deny(large_stack_frames)]
+
+fn big() -> u8 { //~ ERROR the stack frame of `big` is estimated at
+    let buf = [0u8; 4096];
+    buf[0]
+}
+
+fn small() -> u8 {
+    let buf = [0u8; 16];
+    buf[1]
+}
+
+fn fill<T: Copy>(value: T) -> usize { //~ ERROR the stack frame of `fill::<[u8; 64]>`
+    let values = [value; 64];
+    values.len()
+}
+
+fn main() {
+    big();
+    small();
+    fill(1u8);
+    fill([0u8; 64]);
+}
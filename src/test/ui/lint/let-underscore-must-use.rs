@@ -0,0 +1,31 @@
+#![deny(let_underscore_must_use)]
+#![allow(dead_code)]
+
+#[must_use]
+struct Token;
+
+#[must_use = "the returned count should be checked"]
+fn count() -> usize {
+    3
+}
+
+fn fallible() -> Result<(), ()> {
+    Ok(())
+}
+
+fn main() {
+    let _ = fallible(); //~ ERROR unused `std::result::Result` that must be used
+    let _ = Token; //~ ERROR unused `Token` that must be used
+    let _ = count(); //~ ERROR unused return value of `count` that must be used
+
+    // Named bindings and `drop` are explicit enough.
+    let _result = fallible();
+    drop(fallible());
+    let _ = 1 + 1;
+
+    // Places aren't moved out of by `let _ =`.
+    let token = Token;
+    let _ = token;
+    let tokens = [Token];
+    let _ = tokens[0];
+}
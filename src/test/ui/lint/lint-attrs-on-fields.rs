@@ -0,0 +1,37 @@
+// Lint attributes on the fields of struct expressions and patterns apply to the field.
+
+// check-pass
+
+#![deny(unused_variables, ellipsis_inclusive_range_patterns, while_true)]
+
+struct Foo {
+    a: u8,
+    b: u8,
+}
+
+fn main() {
+    let Foo {
+        #[allow(unused_variables)]
+        a,
+        b,
+    } = Foo {
+        #[allow(while_true)]
+        a: {
+            while true {
+                break;
+            }
+            0
+        },
+        b: 1,
+    };
+    let _ = b;
+
+    match (Foo { a: 0, b: 0 }) {
+        Foo {
+            #[allow(ellipsis_inclusive_range_patterns)]
+            a: 0...9,
+            b: _,
+        } => {}
+        _ => {}
+    }
+}
@@ -0,0 +1,25 @@
+// Lint attributes on generic parameters apply to the parameter.
+
+// check-pass
+
+#![deny(single_use_lifetimes, unused_lifetimes, non_camel_case_types, non_snake_case)]
+
+fn single_use<#[allow(single_use_lifetimes)] 'a>(x: &'a u8) -> u8 {
+    *x
+}
+
+fn unused<#[allow(unused_lifetimes)] 'a>() {}
+
+#[allow(dead_code)]
+struct Lowercase<#[allow(non_camel_case_types)] t>(t);
+
+fn upper_case<#[allow(non_snake_case)] 'A, 'b>(x: &'A u8, y: &'b u8) -> (&'A u8, &'b u8) {
+    (x, y)
+}
+
+fn main() {
+    single_use(&0);
+    unused();
+    let _ = Lowercase(0);
+    upper_case(&0, &0);
+}
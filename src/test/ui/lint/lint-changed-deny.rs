@@ -0,0 +1,13 @@
+// compile-flags: -Z unstable-options --lint-changed={{src-base}}/lint/lint-changed.json
+
+// None of the lines of this file are listed, but errors are reported anyway.
+
+#![deny(unused_variables)]
+#![warn(unused_mut)]
+
+fn main() {
+    let x = 1;
+    //~^ ERROR unused variable: `x`
+    let mut y = ();
+    y
+}
@@ -0,0 +1,15 @@
+// check-pass
+// compile-flags: -Z unstable-options --lint-changed={{src-base}}/lint/lint-changed.json
+
+#![warn(unused_variables)]
+
+fn main() {
+    let changed = 1;
+    //~^ WARNING unused variable: `changed`
+    let unchanged = 2;
+}
+
+fn also_changed() {
+    let x = 1;
+    //~^ WARNING unused variable: `x`
+}
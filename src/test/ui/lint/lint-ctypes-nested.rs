@@ -0,0 +1,31 @@
+// aux-build:lint-ctypes-nested-aux.rs
+#![deny(improper_ctypes)]
+
+extern crate lint_ctypes_nested_aux;
+
+use lint_ctypes_nested_aux::{Opaque, Wrapper};
+
+#[repr(C)]
+pub struct Outer {
+    pub a: u8,
+    pub middle: Middle,
+}
+
+#[repr(C)]
+pub struct Middle {
+    pub ptr: *const Inner,
+}
+
+#[repr(C)]
+pub enum Inner {
+    A(u32),
+    B(Wrapper<char>),
+}
+
+extern "C" {
+    pub fn nested(x: Outer); //~ ERROR uses type `char`
+    pub fn foreign(x: Wrapper<Opaque>);
+    //~^ ERROR uses type `lint_ctypes_nested_aux::Opaque`
+}
+
+fn main() {}
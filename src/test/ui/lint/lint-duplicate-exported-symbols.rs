@@ -0,0 +1,24 @@
+#![deny(duplicate_exported_symbols)]
+#![allow(dead_code)]
+
+#[no_mangle]
+pub extern "C" fn foo() {}
+//~^ ERROR symbol `foo` is exported more than once
+//~| ERROR symbol `foo` is exported more than once
+
+mod inner {
+    #[no_mangle]
+    pub extern "C" fn foo() {}
+}
+
+#[export_name = "foo"]
+pub extern "C" fn bar() {}
+
+#[export_name = "unique"]
+pub extern "C" fn baz() {}
+
+#[export_name = "rust_eh_personality"]
+pub extern "C" fn personality() {}
+//~^ ERROR symbol `rust_eh_personality` is already exported by the standard library
+
+fn main() {}
@@ -0,0 +1,11 @@
+// check-pass
+// compile-flags: -Z lint-emission-cap=2
+
+#![warn(unused_variables)]
+
+fn main() {
+    let a = 1; //~ WARNING unused variable: `a`
+    let b = 2; //~ WARNING unused variable: `b`
+    let c = 3;
+    let d = 4;
+}
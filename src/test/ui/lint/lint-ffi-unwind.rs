@@ -0,0 +1,55 @@
+#![deny(ffi_unwind)]
+#![feature(unwind_attributes)]
+#![allow(dead_code, empty_loops)]
+
+fn spin() -> ! {
+    loop {}
+}
+
+pub extern "C" fn index(arr: &[u8; 4], i: usize) -> u8 {
+    //~^ ERROR function with the "C" ABI may unwind
+    arr[i]
+}
+
+pub extern "C" fn explicit(x: bool) -> u8 {
+    //~^ ERROR function with the "C" ABI may unwind
+    if x { panic!("x is set") }
+    0
+}
+
+pub extern "C" fn formatted(x: u8) -> u8 {
+    //~^ ERROR function with the "C" ABI may unwind
+    if x > 3 { panic!("{} is too large", x) }
+    x
+}
+
+// Diverging calls that don't panic.
+pub extern "C" fn spins(x: bool) -> u8 {
+    if x { spin() }
+    0
+}
+
+pub extern "C" fn exits(x: bool) -> u8 {
+    if x { std::process::exit(1) }
+    0
+}
+
+pub extern "C" fn no_panic(a: u8, b: u8) -> u8 {
+    a.wrapping_add(b)
+}
+
+#[unwind(aborts)]
+pub extern "C" fn aborts(arr: &[u8; 4], i: usize) -> u8 {
+    arr[i]
+}
+
+#[unwind(allowed)]
+pub extern "C" fn allowed(arr: &[u8; 4], i: usize) -> u8 {
+    arr[i]
+}
+
+fn rust_abi(arr: &[u8; 4], i: usize) -> u8 {
+    arr[i]
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#![deny(float_equality)]
+#![crate_type = "lib"]
+#![no_std]
+
+pub fn f(a: f32, b: f32) -> bool {
+    a == b //~ ERROR strict comparison of `f32` values with `==`
+}
+
+pub fn g(a: f64, b: f64) -> bool {
+    a != b //~ ERROR strict comparison of `f64` values with `!=`
+}
@@ -0,0 +1,21 @@
+#![deny(float_equality)]
+
+use std::f64;
+
+fn main() {
+    let a = 0.1 + 0.2;
+    let b = 0.3;
+    let c = 1.5f32;
+
+    let _ = a == b; //~ ERROR strict comparison of `f64` values with `==`
+    let _ = c != 2.0 * c; //~ ERROR strict comparison of `f32` values with `!=`
+    let _ = a + b == b - a; //~ ERROR strict comparison of `f64` values with `==`
+
+    // Exact comparisons are fine.
+    let _ = a == 0.0;
+    let _ = -0.0 != b;
+    let _ = a == f64::INFINITY;
+    let _ = f64::NEG_INFINITY != b;
+    let _ = a != a;
+    let _ = a < b;
+}
@@ -0,0 +1,39 @@
+// compile-flags: --test
+#![deny(floats)]
+#![allow(dead_code)]
+
+fn scale(x: f32) -> f32 { //~ ERROR use of the floating-point type `f32`
+    //~^ ERROR use of the floating-point type `f32`
+    x * 2.5 //~ ERROR floating-point operation
+    //~^ ERROR floating-point literal
+}
+
+fn halve(x: u32) -> u32 {
+    (x as f64 / 2.0) as u32 //~ ERROR use of the floating-point type `f64`
+    //~^ ERROR floating-point operation
+    //~| ERROR floating-point literal
+}
+
+fn negate() -> bool {
+    let y = -1.0; //~ ERROR floating-point literal
+    -y < 0.0 //~ ERROR floating-point operation
+    //~^ ERROR floating-point operation
+    //~| ERROR floating-point literal
+}
+
+#[allow(floats)]
+fn allowed(x: f64) -> f64 {
+    x + 1.0
+}
+
+fn integers(x: u32) -> u32 {
+    x * 2 + 1
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn scale() {
+        assert_eq!(super::scale(2.0), 5.0);
+    }
+}
@@ -0,0 +1,5 @@
+// ignore-test: this is an auxiliary file for lint-level-note-per-file.rs
+
+pub fn f() {
+    let c = 3;
+}
@@ -0,0 +1,12 @@
+// Denied lints point at the attribute that denied them on every error, in every file.
+
+#![deny(unused_variables)]
+
+#[path = "lint-level-note-per-file-ext.rs"]
+mod ext;
+
+fn main() {
+    let a = 1; //~ ERROR unused variable: `a`
+    let b = 2; //~ ERROR unused variable: `b`
+    ext::f();
+}
@@ -0,0 +1,4 @@
+// rustc-env:RUSTC_LINT_LEVELS=warn:unused,loud:unsafe_code
+// error-pattern: unknown lint level in `RUSTC_LINT_LEVELS`: `loud`
+
+fn main() {}
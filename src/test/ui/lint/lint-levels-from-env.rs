@@ -0,0 +1,17 @@
+// Lint levels set by `RUSTC_LINT_LEVELS` apply, below those set by flags.
+
+// compile-flags: -W non-upper-case-globals
+// rustc-env:RUSTC_LINT_LEVELS=deny:nonstandard-style, warn:while-true
+
+fn Bar() {}
+//~^ ERROR function `Bar` should have a snake case name
+
+static bar: i32 = 0;
+//~^ WARN static variable `bar` should have an upper case name
+
+fn main() {
+    while true {
+        //~^ WARN denote infinite loops with `loop { ... }`
+        break;
+    }
+}
@@ -0,0 +1,14 @@
+// Checks that `-Z lint-macros-from` lints the expansions of the named crate's macros.
+
+// aux-build:lints-in-foreign-macros.rs
+// check-pass
+// compile-flags: -Z lint-macros-from=lints_in_foreign_macros
+
+#![warn(unused_imports)]
+
+#[macro_use]
+extern crate lints_in_foreign_macros;
+
+mod a { bar!(); }
+
+fn main() {}
@@ -0,0 +1,6 @@
+// run-rustfix
+
+#![allow(dead-code, unused-imports)] //~ ERROR expected one of
+                                     //~| HELP lint names use underscores instead of dashes
+
+fn main() {}
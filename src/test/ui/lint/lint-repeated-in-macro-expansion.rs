@@ -0,0 +1,23 @@
+// check-pass
+
+#![warn(unused_variables)]
+
+macro_rules! make_vars {
+    () => {
+        let a = 1;
+        let b = 2;
+        let c = 3;
+    };
+}
+
+macro_rules! make_var {
+    () => {
+        let d = 4; //~ WARNING unused variable: `d`
+    };
+}
+
+fn main() {
+    make_vars!();
+    //~^ WARNING unused variable: `a`
+    make_var!();
+}
@@ -0,0 +1,18 @@
+// compile-flags: -Z unstable-options --lint-spec={{src-base}}/lint/lint-spec-allowed-names.json
+
+// The names listed in `allowed-names` are let through, the others are still denied.
+
+#![allow(dead_code)]
+
+fn GetProcAddress() {}
+
+fn FFI_Module_Init() {}
+
+fn FFI_Module_Exit() {}
+//~^ ERROR function `FFI_Module_Exit` should have a snake case name
+
+static g_count: i32 = 0;
+static count: i32 = 0;
+//~^ ERROR static variable `count` should have an upper case name
+
+fn main() {}
@@ -0,0 +1,17 @@
+// compile-flags: -Z unstable-options --lint-spec={{src-base}}/lint/lint-spec.json -W while-true
+
+// Both crate attributes and the lint flags override the levels in the spec.
+#![allow(non_upper_case_globals, empty_loops)]
+
+struct foo;
+//~^ WARNING type `foo` should have an upper camel case name
+
+static bar: i32 = 0;
+
+fn Bar() {}
+//~^ ERROR function `Bar` should have a snake case name
+
+fn main() {
+    while true {}
+    //~^ WARNING denote infinite loops with `loop { ... }`
+}
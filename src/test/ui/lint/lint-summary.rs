@@ -0,0 +1,13 @@
+//! Checks the table printed by `-Z lint-summary`.
+
+// check-pass
+// compile-flags: -Z lint-summary
+
+#![warn(unused_variables)]
+
+fn main() {
+    let x = 1; //~ WARNING unused variable: `x`
+    let y = 2; //~ WARNING unused variable: `y`
+    #[allow(unused_variables)]
+    let z = 3;
+}
@@ -0,0 +1,11 @@
+#![deny(unknown_lints)]
+
+#![allow(nonstandard_styl)] //~ ERROR unknown lint
+                            //~| HELP did you mean
+                            //~| SUGGESTION nonstandard_style
+
+#![warn(future_incompatibl)] //~ ERROR unknown lint
+                             //~| HELP did you mean
+                             //~| SUGGESTION future_incompatible
+
+fn main() {}
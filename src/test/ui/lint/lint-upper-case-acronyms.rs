@@ -0,0 +1,20 @@
+// run-rustfix
+
+#![deny(upper_case_acronyms)]
+#![allow(dead_code)]
+
+struct HTTPServerError;
+//~^ ERROR type `HTTPServerError` has an acronym in upper case
+
+enum Encoding {
+    UTF8String,
+    //~^ ERROR variant `UTF8String` has an acronym in upper case
+    Ascii,
+}
+
+trait IOSource {}
+//~^ ERROR trait `IOSource` has an acronym in upper case
+
+struct HttpServer;
+
+fn main() {}
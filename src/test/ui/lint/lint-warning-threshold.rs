@@ -0,0 +1,14 @@
+// compile-flags: -Z lint-warning-threshold=1 -Z lint-warning-threshold-lints=unused
+
+#![warn(unused_variables, non_snake_case)]
+
+fn main() {
+    let x = 1;
+    //~^ WARNING unused variable: `x`
+    let y = 2;
+    //~^ WARNING unused variable: `y`
+}
+
+// Not in `unused`, so it doesn't count towards the threshold.
+fn Bar() {}
+//~^ WARNING function `Bar` should have a snake case name
@@ -0,0 +1,26 @@
+// build-pass
+// compile-flags: --crate-type lib -Z missing-docs-coverage
+
+//! Crate documentation.
+
+/// Documented.
+pub fn documented() {}
+
+pub fn undocumented() {}
+
+/// Documented.
+pub struct Foo {
+    /// Documented.
+    pub a: u8,
+    pub b: u8,
+    // Private fields are not counted.
+    c: u8,
+}
+
+#[doc(hidden)]
+pub fn hidden() {}
+
+#[macro_export]
+macro_rules! undocumented {
+    () => {};
+}
@@ -0,0 +1,20 @@
+// compile-flags: --crate-type lib -Z missing-docs-kinds=fn -Z missing-docs-kinds=trait
+#![deny(missing_docs)]
+
+pub fn undocumented() {} //~ ERROR missing documentation for a function
+
+/// Documented.
+pub fn documented() {}
+
+pub trait Undocumented {} //~ ERROR missing documentation for a trait
+
+// Neither structs nor their fields are checked.
+pub struct Foo {
+    pub field: u8,
+}
+
+// Nor are exported macros.
+#[macro_export]
+macro_rules! undocumented {
+    () => {};
+}
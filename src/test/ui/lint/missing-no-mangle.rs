@@ -0,0 +1,32 @@
+// compile-flags: --crate-type staticlib
+#![deny(missing_no_mangle)]
+#![allow(dead_code)]
+
+pub extern "C" fn exported() {} //~ ERROR exported `extern` function `exported` has a mangled symbol
+
+pub mod ffi {
+    pub extern "C" fn nested(x: u32) -> u32 { //~ ERROR `nested` has a mangled symbol
+        x
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn unmangled() {}
+
+#[export_name = "renamed"]
+pub extern "C" fn renamed() {}
+
+pub extern "C" fn generic<T>(_: T) {}
+
+pub fn rust_abi() {}
+
+// Only called through the function pointer, never by its symbol.
+extern "C" fn callback() {}
+
+pub fn register() -> extern "C" fn() {
+    callback
+}
+
+mod private {
+    pub extern "C" fn unreachable() {}
+}
@@ -0,0 +1,31 @@
+// compile-flags: --crate-type lib -Z required-traits=Clone -Z required-traits=Send
+#![deny(missing_trait_implementations)]
+#![allow(unused)]
+
+use std::rc::Rc;
+
+pub struct A; //~ ERROR type does not implement `Clone`
+
+#[derive(Clone)]
+pub struct B;
+
+pub enum C {}
+
+impl Clone for C {
+    fn clone(&self) -> C {
+        match *self {}
+    }
+}
+
+#[derive(Clone)]
+pub struct NotSend(Rc<u8>); //~ ERROR type is not `Send`
+
+pub struct Neither(Rc<u8>);
+//~^ ERROR type does not implement `Clone`
+//~| ERROR type is not `Send`
+
+// Generic types are not checked for auto traits.
+#[derive(Clone)]
+pub struct Generic<T>(T);
+
+struct Private;
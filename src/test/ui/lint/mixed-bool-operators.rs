@@ -0,0 +1,13 @@
+#![deny(mixed_bool_operators)]
+
+fn main() {
+    let (a, b, c, d) = (true, false, true, false);
+    let _ = a || b && c; //~ ERROR `&&` and `||` are mixed without parentheses
+    let _ = a && b || c && d || a; //~ ERROR `&&` and `||` are mixed without parentheses
+    let _ = a || (b && c);
+    let _ = (a || b) && c;
+    let _ = a || b || c;
+    let _ = a || b && (c || d && e);
+    //~^ ERROR `&&` and `||` are mixed without parentheses
+    //~| ERROR `&&` and `||` are mixed without parentheses
+}
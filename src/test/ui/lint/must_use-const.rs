@@ -0,0 +1,38 @@
+#![deny(unused_must_use)]
+#![allow(path_statements)]
+
+struct Builder;
+
+impl Builder {
+    #[must_use = "the default builder does nothing until it is built"]
+    const DEFAULT: Builder = Builder;
+
+    const PLAIN: Builder = Builder;
+}
+
+trait Limits {
+    #[must_use]
+    const MAX: u32;
+}
+
+impl Limits for Builder {
+    const MAX: u32 = 8;
+}
+
+#[must_use]
+const ANSWER: u32 = 42;
+
+#[must_use]
+static GREETING: &str = "hello";
+
+fn main() {
+    Builder::DEFAULT; //~ ERROR unused value of `Builder::DEFAULT` that must be used
+    let _ = Builder::DEFAULT; //~ ERROR unused value of `Builder::DEFAULT` that must be used
+    <Builder as Limits>::MAX; //~ ERROR unused value of `Limits::MAX` that must be used
+    ANSWER; //~ ERROR unused value of `ANSWER` that must be used
+    let _ = GREETING; //~ ERROR unused value of `GREETING` that must be used
+
+    Builder::PLAIN;
+    let _ = Builder::PLAIN;
+    let _builder = Builder::DEFAULT;
+}
@@ -0,0 +1,29 @@
+#![deny(unused_must_use)]
+
+#[must_use]
+trait Critical {}
+
+trait Sub: Critical {}
+
+struct Anon;
+
+impl Critical for Anon {}
+impl Sub for Anon {}
+
+fn get_sub() -> impl Sub {
+    Anon
+}
+
+fn get_boxed_sub() -> Box<dyn Sub> {
+    Box::new(Anon)
+}
+
+fn get_iter() -> impl DoubleEndedIterator<Item = u8> {
+    vec![1, 2, 3].into_iter()
+}
+
+fn main() {
+    get_sub(); //~ ERROR unused implementer of `Critical` that must be used
+    get_boxed_sub(); //~ ERROR unused boxed `Critical` trait object that must be used
+    get_iter(); //~ ERROR unused implementer of `std::iter::Iterator` that must be used
+}
@@ -0,0 +1,47 @@
+#![deny(non_canonical_partial_ord, inconsistent_trait_impls)]
+#![allow(dead_code)]
+
+use std::cmp::Ordering;
+
+#[derive(PartialEq, Eq)]
+struct Reversed(u32);
+
+impl Ord for Reversed {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
+impl PartialOrd for Reversed {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        //~^ ERROR `partial_cmp` of `Reversed` does not delegate to its `Ord` implementation
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+#[derive(PartialEq, Eq)]
+struct Canonical(u32);
+
+impl Ord for Canonical {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
+impl PartialOrd for Canonical {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Delegating to a derived `Ord` agrees with it too, so `inconsistent_trait_impls` is silent.
+#[derive(PartialEq, Eq, Ord)]
+struct Derived(u32);
+
+impl PartialOrd for Derived {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(Ord::cmp(self, other))
+    }
+}
+
+fn main() {}
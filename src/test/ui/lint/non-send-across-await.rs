@@ -0,0 +1,32 @@
+// edition:2018
+
+#![deny(non_send_across_await)]
+#![allow(dead_code)]
+
+use std::rc::Rc;
+
+async fn yield_now() {}
+
+pub async fn exported() {
+    let rc = Rc::new(());
+    //~^ ERROR non-`Send` value is held across an await point
+    yield_now().await;
+    drop(rc);
+}
+
+pub async fn scoped() {
+    {
+        let rc = Rc::new(());
+        drop(rc);
+    }
+    yield_now().await;
+}
+
+// Only exported async fns are checked by default.
+async fn private() {
+    let rc = Rc::new(());
+    yield_now().await;
+    drop(rc);
+}
+
+fn main() {}
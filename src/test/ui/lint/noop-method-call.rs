@@ -0,0 +1,29 @@
+#![deny(noop_method_call)]
+#![allow(unused)]
+
+use std::borrow::Borrow;
+use std::ops::Deref;
+
+struct NotClone;
+
+#[derive(Clone)]
+struct Foo;
+
+fn main() {
+    let non_clone = &NotClone;
+    let _: &NotClone = non_clone.clone(); //~ ERROR call to `.clone()` on a reference
+    let _: &NotClone = non_clone.borrow(); //~ ERROR call to `.borrow()` on a reference
+    let _: &NotClone = non_clone.deref(); //~ ERROR call to `.deref()` on a reference
+
+    let double = &&Foo;
+    let _: &Foo = double.clone(); //~ ERROR call to `.clone()` on `&&Foo` only copies
+
+    let foo = &Foo;
+    let _: Foo = foo.clone();
+    let v = vec![1];
+    let _: &Vec<i32> = v.borrow();
+}
+
+fn generic<T>(x: &T) -> &T {
+    x.clone() //~ ERROR call to `.clone()` on a reference
+}
@@ -0,0 +1,10 @@
+// compile-flags: -Z portable-pointer-widths=16,32
+
+#![deny(pointer_width_casts)]
+
+fn main() {
+    let len: usize = 10;
+    let _ = len as u16; //~ ERROR cast from `usize` to `u16` truncates on targets with 32-bit
+    let _ = len as u32;
+    let _ = len as u64;
+}
@@ -0,0 +1,12 @@
+#![deny(pointer_width_casts)]
+
+fn main() {
+    let len: usize = 10;
+    let wide: u64 = 10;
+    let _ = len as u32; //~ ERROR cast from `usize` to `u32` truncates on targets with 64-bit
+    let _ = wide as usize; //~ ERROR cast from `u64` to `usize` truncates on targets with 32-bit
+    let _ = len as u64;
+    let _ = 10u16 as usize;
+    let _ = len as u8;
+    let _ = len as isize;
+}
@@ -0,0 +1,24 @@
+// build-fail
+// aux-build:reachable-upstream.rs
+// compile-flags:-Zshare-generics=yes
+
+// Calls into other crates are followed through the functions with MIR, like the generic
+// instances shared by the crate defining them, but not through the other functions.
+
+#![deny(reachable_allocations)]
+
+extern crate reachable_upstream;
+
+pub fn shared(x: u32) -> &'static mut u32 { //~ ERROR the global allocator is reachable
+    reachable_upstream::boxed(x)
+}
+
+// `opaque_boxed` allocates, but it has no MIR to look into.
+pub fn opaque(x: u32) -> &'static mut u32 {
+    reachable_upstream::opaque_boxed(x)
+}
+
+fn main() { //~ ERROR the global allocator is reachable from `main`
+    shared(1);
+    opaque(2);
+}
@@ -0,0 +1,37 @@
+// build-fail
+
+#![deny(reachable_allocations)]
+
+pub fn boxed(x: u32) -> &'static mut u32 { //~ ERROR the global allocator is reachable from `boxed`
+    Box::leak(Box::new(x))
+}
+
+fn private_boxed(x: u32) -> &'static mut u32 {
+    Box::leak(Box::new(x))
+}
+
+pub fn through_private(x: u32) -> &'static mut u32 { //~ ERROR the global allocator is reachable
+    private_boxed(x)
+}
+
+pub fn wrap<T>(value: T) -> &'static mut T { //~ ERROR the global allocator is reachable from `wrap`
+    Box::leak(Box::new(value))
+}
+
+pub fn add(x: u32, y: u32) -> u32 {
+    x.wrapping_add(y)
+}
+
+#[allow(reachable_allocations)]
+pub fn quiet(x: u32) -> &'static mut u32 {
+    Box::leak(Box::new(x))
+}
+
+fn main() { //~ ERROR the global allocator is reachable from `main`
+    boxed(1);
+    through_private(2);
+    wrap(3u8);
+    wrap(4u16);
+    add(5, 6);
+    quiet(7);
+}
@@ -0,0 +1,25 @@
+// build-fail
+// aux-build:reachable-upstream.rs
+// compile-flags:-Zshare-generics=yes
+
+// Calls into other crates are followed through the functions with MIR, like the generic
+// instances shared by the crate defining them, but not through the other functions.
+
+#![deny(reachable_panics)]
+
+extern crate reachable_upstream;
+
+fn shared(values: &[u32]) -> u32 { //~ ERROR a panic is reachable from `shared`
+    reachable_upstream::get(values, 0)
+}
+
+// `opaque_get` can panic, but it has no MIR to look into.
+fn opaque(values: &[u32]) -> u32 {
+    reachable_upstream::opaque_get(values, 0)
+}
+
+fn main() { //~ ERROR a panic is reachable from `main`
+    let values = [1, 2, 3];
+    shared(&values);
+    opaque(&values);
+}
@@ -0,0 +1,31 @@
+// build-fail
+
+#![deny(reachable_panics)]
+
+fn first(values: &[u32]) -> u32 { //~ ERROR a panic is reachable from `first`
+    values[0]
+}
+
+fn pick<T: Copy>(values: &[T], i: usize) -> T { //~ ERROR a panic is reachable from `pick::<u32>`
+    values[i]
+}
+
+fn total(values: &[u32]) -> u32 { //~ ERROR a panic is reachable from `total`
+    first(values).wrapping_add(pick(values, 1))
+}
+
+fn len(values: &[u32]) -> usize {
+    values.len()
+}
+
+#[allow(reachable_panics)]
+fn quiet(values: &[u32]) -> u32 {
+    first(values)
+}
+
+fn main() { //~ ERROR a panic is reachable from `main`
+    let values = [1, 2, 3];
+    total(&values);
+    len(&values);
+    quiet(&values);
+}
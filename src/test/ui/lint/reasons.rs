@@ -29,7 +29,7 @@ impl fmt::Debug for CheaterDetectionMechanism {
 fn main() {
     let Social_exchange_psychology = CheaterDetectionMechanism {};
     //~^ WARN should have a snake case name
-    //~| NOTE #[warn(non_snake_case)]` implied by `#[warn(nonstandard_style)]
+    //~| NOTE `non_snake_case` implied by `nonstandard_style`
     //~| NOTE people shouldn't have to change their usual style habits
     //~| HELP convert the identifier to snake case
 }
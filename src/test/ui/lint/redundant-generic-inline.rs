@@ -0,0 +1,46 @@
+#![deny(redundant_generic_inline)]
+#![crate_type = "lib"]
+
+#[inline] //~ ERROR `#[inline]` on generic function `first`
+pub fn first<T: Copy>(items: &[T]) -> T {
+    items[0]
+}
+
+pub struct Wrapper<T>(T);
+
+impl<T> Wrapper<T> {
+    #[inline] //~ ERROR `#[inline]` on generic function `Wrapper::<T>::into_inner`
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+pub trait Describe {
+    #[inline] //~ ERROR `#[inline]` on generic function `Describe::describe`
+    fn describe(&self) -> &'static str {
+        "something"
+    }
+}
+
+// Derives mark their methods `#[inline]`.
+#[derive(Clone)]
+pub struct Pair<T>(T, T);
+
+#[inline(always)]
+pub fn second<T: Copy>(items: &[T]) -> T {
+    items[1]
+}
+
+#[inline]
+pub fn not_generic<'a>(items: &'a [u8]) -> &'a u8 {
+    &items[0]
+}
+
+#[inline]
+fn private<T: Copy>(items: &[T]) -> T {
+    items[0]
+}
+
+pub fn use_private() -> u8 {
+    private(&[1])
+}
@@ -0,0 +1,42 @@
+#![deny(shadowed_generic_params)]
+#![allow(dead_code)]
+
+fn outer<'a, T>(value: &'a T) -> &'a T {
+    fn inner<T>(value: T) -> T {
+        //~^ ERROR generic parameter `T` shadows a parameter of an enclosing item
+        value
+    }
+
+    struct Local<'a>(&'a u8);
+    //~^ ERROR generic parameter `'a` shadows a parameter of an enclosing item
+
+    value
+}
+
+struct Wrapper<T>(T);
+
+impl<T> Wrapper<T> {
+    fn get(&self) -> &T {
+        fn helper<T>(value: T) -> T {
+            //~^ ERROR generic parameter `T` shadows a parameter of an enclosing item
+            value
+        }
+        fn other<U>(value: U) -> U {
+            value
+        }
+        &self.0
+    }
+}
+
+fn with_impl<T>(_: T) {
+    struct Local<U>(U);
+
+    impl<T> Local<T> {
+        //~^ ERROR generic parameter `T` shadows a parameter of an enclosing item
+        fn get(self) -> T {
+            self.0
+        }
+    }
+}
+
+fn main() {}
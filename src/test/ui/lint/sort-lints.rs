@@ -0,0 +1,15 @@
+// Checks that `-Z sort-lints` emits the late `unused_variables` warning before the early
+// `non_camel_case_types` one further down the file.
+
+// check-pass
+// compile-flags: -Z sort-lints
+
+#![warn(unused_variables)]
+
+fn main() {
+    let x = 1;
+    //~^ WARNING unused variable: `x`
+}
+
+struct foo;
+//~^ WARNING type `foo` should have an upper camel case name
@@ -0,0 +1,18 @@
+// Source files are checked for byte-order marks, mixed line endings and invisible characters.
+
+#![deny(byte_order_marks, mixed_line_endings, invisible_characters)]
+
+#[path = "auxiliary/source-hygiene-bom.rs"]
+mod bom;
+#[path = "auxiliary/source-hygiene-crlf.rs"]
+mod crlf;
+
+// ​A zero width space outside of a literal.
+//~^ ERROR invisible character `\u{200b}` (ZERO WIDTH SPACE)
+
+fn main() {
+    let _ = "​ in a string literal";
+    let _ = '​';
+    bom::f();
+    crlf::f();
+}
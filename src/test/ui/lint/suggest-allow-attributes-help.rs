@@ -0,0 +1,12 @@
+// check-pass
+// compile-flags: -Z suggest-allow-attributes=help
+
+#![warn(unused_variables, dead_code)]
+
+fn unused() {}
+//~^ WARNING function is never used: `unused`
+
+fn main() {
+    let x = 1;
+    //~^ WARNING unused variable: `x`
+}
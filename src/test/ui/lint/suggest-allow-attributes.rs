@@ -0,0 +1,9 @@
+// check-pass
+// compile-flags: -Z suggest-allow-attributes=rendered
+
+#![warn(unused_variables)]
+
+fn main() {
+    let x = 1;
+    //~^ WARNING unused variable: `x`
+}
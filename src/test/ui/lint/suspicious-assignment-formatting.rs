@@ -0,0 +1,16 @@
+#![deny(suspicious_assignment_formatting)]
+#![allow(unused_assignments)]
+
+fn main() {
+    let mut x = 5;
+    let flag = true;
+    let mut b = false;
+    let p = &3;
+    x =- 1; //~ ERROR `=-` looks like `-=`
+    b =! flag; //~ ERROR `=!` looks like `!=`
+    x =* p; //~ ERROR `=*` looks like `*=`
+    x = -1;
+    x=-1;
+    b = !flag;
+    let _ = (x, b);
+}
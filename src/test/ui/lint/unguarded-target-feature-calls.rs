@@ -0,0 +1,38 @@
+// only-x86_64
+#![deny(unguarded_target_feature_calls)]
+
+#[target_feature(enable = "avx2")]
+unsafe fn sum_avx2(xs: &[u32]) -> u32 {
+    xs.iter().sum()
+}
+
+pub fn unguarded(xs: &[u32]) -> u32 {
+    unsafe { sum_avx2(xs) } //~ ERROR call to `sum_avx2` requires target feature `avx2`
+}
+
+pub fn guarded(xs: &[u32]) -> u32 {
+    if is_x86_feature_detected!("avx2") {
+        unsafe { sum_avx2(xs) }
+    } else {
+        xs.iter().sum()
+    }
+}
+
+pub fn wrong_branch(xs: &[u32]) -> u32 {
+    if is_x86_feature_detected!("avx2") {
+        xs.iter().sum()
+    } else {
+        unsafe { sum_avx2(xs) } //~ ERROR call to `sum_avx2` requires target feature `avx2`
+    }
+}
+
+pub unsafe fn caller_checks(xs: &[u32]) -> u32 {
+    sum_avx2(xs)
+}
+
+#[target_feature(enable = "avx2")]
+pub unsafe fn same_features(xs: &[u32]) -> u32 {
+    sum_avx2(xs)
+}
+
+fn main() {}
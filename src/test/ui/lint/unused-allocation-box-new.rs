@@ -0,0 +1,27 @@
+// run-rustfix
+#![deny(unused_allocation)]
+#![allow(dead_code)]
+
+struct Counter(u32);
+
+impl Counter {
+    fn get(&self) -> u32 {
+        self.0
+    }
+
+    fn bump(&mut self) {
+        self.0 += 1;
+    }
+}
+
+fn main() {
+    let _ = Box::new(Counter(1)).get(); //~ ERROR unnecessary allocation, use `&` instead
+    Box::new(Counter(1)).bump(); //~ ERROR unnecessary allocation, use `&mut` instead
+    let _ = &*Box::new(5); //~ ERROR unnecessary allocation, use `&` instead
+    let _ = &mut *Box::new(1 + 2); //~ ERROR unnecessary allocation, use `&mut` instead
+    let _ = Box::new(String::new() + "a").len(); //~ ERROR unnecessary allocation, use `&` instead
+
+    // The box itself is used.
+    let _ = Box::new(Counter(1)).as_ref().get();
+    let _: Box<Counter> = Box::new(Counter(1));
+}
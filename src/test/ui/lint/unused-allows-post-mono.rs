@@ -0,0 +1,38 @@
+// build-pass
+// edition:2018
+// compile-flags: -Z stack-frame-threshold=1024 -Z large-future-threshold=1024
+
+// Lints that skip their check up front when they are allowed, or only run once the mono items
+// are collected, still count the `allow` attributes they looked at as used.
+
+#![deny(unused_allows)]
+
+use std::future::Future;
+
+#[allow(large_stack_frames)]
+fn big() -> u8 {
+    let buf = [0u8; 4096];
+    buf[0]
+}
+
+async fn yield_now() {}
+
+#[allow(large_futures)]
+async fn big_future() {
+    let buf = [0u8; 4096];
+    yield_now().await;
+    drop(buf);
+}
+
+struct Private;
+
+#[allow(private_in_public_impl_trait)]
+pub fn leaky() -> impl Sized {
+    Private
+}
+
+fn main() {
+    big();
+    let _: Box<dyn Future<Output = ()>> = Box::new(big_future());
+    leaky();
+}
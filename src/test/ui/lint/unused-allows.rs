@@ -0,0 +1,35 @@
+#![deny(unused_allows)]
+
+#[allow(unused_variables)]
+fn used() {
+    let x = 1;
+}
+
+#[allow(unused_variables)] //~ ERROR `allow(unused_variables)` did not suppress any lint
+fn unused() {
+    let _x = 1;
+}
+
+#[allow(unused_mut, unused_variables)] //~ ERROR `allow(unused_mut)` did not suppress any lint
+fn partly_used() {
+    let x = 1;
+}
+
+#[allow(unused)]
+fn group() {
+    let x = 1;
+}
+
+#[allow(dead_code)]
+fn never_called() {}
+
+#[allow(unused_allows, unused_mut)]
+fn opted_out() {}
+
+fn main() {
+    used();
+    unused();
+    partly_used();
+    group();
+    opted_out();
+}
@@ -0,0 +1,18 @@
+// edition:2018
+
+#![deny(unused_futures)]
+
+async fn work() -> u32 {
+    1
+}
+
+fn main() {
+    work(); //~ ERROR future is created but never awaited
+
+    let _ = async {
+        // Closures inside an async block do not get the `.await` suggestion.
+        let _ = || {
+            work(); //~ ERROR future is created but never awaited
+        };
+    };
+}
@@ -0,0 +1,15 @@
+// edition:2018
+// run-rustfix
+
+#![deny(unused_futures)]
+#![allow(dead_code)]
+
+async fn work() -> u32 {
+    1
+}
+
+async fn caller() {
+    work(); //~ ERROR future is created but never awaited
+}
+
+fn main() {}
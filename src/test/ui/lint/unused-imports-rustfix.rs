@@ -0,0 +1,42 @@
+// Checks that the `unused_imports` fixes don't leave odd remnants behind.
+
+// run-rustfix
+// check-pass
+
+#![warn(unused_imports)]
+
+mod foo {
+    pub struct A;
+    pub struct B;
+    pub struct C;
+    pub struct D;
+    pub mod bar {
+        pub struct E;
+        pub struct F;
+    }
+}
+
+#[allow(deprecated)]
+use foo::A;
+//~^ WARNING unused import: `foo::A`
+
+use foo::{B, C};
+//~^ WARNING unused import: `C`
+
+use foo::{bar::{E, F}, D,};
+//~^ WARNING unused imports: `E`, `F`
+
+mod m {
+    use super::foo::{self, C};
+    //~^ WARNING unused import: `C`
+
+    pub fn f() {
+        let _ = foo::A;
+    }
+}
+
+fn main() {
+    let _ = B;
+    let _ = D;
+    m::f();
+}
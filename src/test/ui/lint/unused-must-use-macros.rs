@@ -0,0 +1,16 @@
+// aux-build:must-use-macros.rs
+
+#![deny(unused_must_use)]
+
+#[macro_use]
+extern crate must_use_macros;
+
+#[must_use]
+fn compute() -> u32 {
+    1
+}
+
+fn main() {
+    discard!(compute()); //~ ERROR unused return value of `compute` that must be used
+    internal_temporary!();
+}
@@ -0,0 +1,10 @@
+// check-pass
+// compile-flags: -Z verbose-lints
+// normalize-stderr-test "finished in [0-9.]+s" -> "finished in $$TIME"
+
+#![allow(empty_loops)]
+
+fn main() {
+    while true {}
+    //~^ WARNING denote infinite loops with `loop { ... }`
+}
@@ -1,5 +1,6 @@
 // build-pass (FIXME(62277): could be check-pass?)
 #![allow(unused)]
+#![allow(empty_loops)]
 
 struct S;
 
@@ -1,7 +1,7 @@
 // ignore-tidy-linelength
 
 #![feature(decl_macro, associated_type_defaults)]
-#![allow(unused, private_in_public)]
+#![allow(unused, private_in_public, empty_loops)]
 
 mod priv_trait {
     trait PrivTr {
@@ -0,0 +1,50 @@
+#![deny(private_in_public_impl_trait)]
+
+use std::rc::Rc;
+
+struct Counter {
+    count: u32,
+}
+
+impl Iterator for Counter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        self.count += 1;
+        Some(self.count)
+    }
+}
+
+#[derive(Clone)]
+struct Shared(Rc<u32>);
+
+#[derive(Clone)]
+pub struct Public(pub u32);
+
+pub fn counter() -> impl Iterator<Item = u32> { //~ ERROR leaks the auto traits
+    Counter { count: 0 }
+}
+
+pub fn sendable_counter() -> impl Iterator<Item = u32> + Send + Sync {
+    Counter { count: 0 }
+}
+
+pub fn shared() -> impl Clone {
+    Shared(Rc::new(0))
+}
+
+pub fn public() -> impl Clone {
+    Public(0)
+}
+
+pub fn empty() -> impl Iterator<Item = u32> {
+    std::iter::empty()
+}
+
+fn private_counter() -> impl Iterator<Item = u32> {
+    Counter { count: 0 }
+}
+
+fn main() {
+    private_counter();
+}
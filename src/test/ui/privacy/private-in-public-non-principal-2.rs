@@ -1,5 +1,5 @@
 #![feature(optin_builtin_traits)]
-
+#![allow(empty_loops)]
 #[allow(private_in_public)]
 mod m {
     pub trait PubPrincipal {}
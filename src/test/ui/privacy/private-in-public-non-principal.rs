@@ -1,5 +1,5 @@
 #![feature(optin_builtin_traits)]
-
+#![allow(empty_loops)]
 pub trait PubPrincipal {}
 auto trait PrivNonPrincipal {}
 
@@ -1,5 +1,5 @@
 #![feature(decl_macro)]
-#![allow(private_in_public)]
+#![allow(private_in_public, empty_loops)]
 
 mod m {
     fn priv_fn() {}
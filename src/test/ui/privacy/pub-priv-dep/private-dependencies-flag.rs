@@ -0,0 +1,23 @@
+// aux-build:priv_dep.rs
+// aux-build:pub_dep.rs
+// compile-flags: -Z private-dependencies=priv_dep
+
+#![deny(exported_private_dependencies)]
+
+// Marked as private by `-Z private-dependencies` rather than `--extern priv:`.
+extern crate priv_dep;
+extern crate pub_dep;
+
+use priv_dep::{OtherTrait, OtherType};
+use pub_dep::PubType;
+
+pub struct PublicType {
+    pub field: OtherType,
+    //~^ ERROR type `priv_dep::OtherType` from private dependency 'priv_dep' in public interface
+    pub other_field: PubType,
+}
+
+pub trait MyPubTrait: OtherTrait {}
+//~^ ERROR trait `priv_dep::OtherTrait` from private dependency 'priv_dep' in public interface
+
+fn main() {}
@@ -3,6 +3,7 @@
 #![allow(dead_code)]
 #![allow(unused_variables)]
 #![allow(unreachable_code)]
+#![allow(empty_loops)]
 // A regression test extracted from image-0.3.11. The point of
 // failure was in `index_colors` below.
 
@@ -33,6 +33,7 @@ fn bar<'a>(x: &'a isize) {
 
     // &'a CAN be declared on functions and used then:
     fn g<'a>(a: &'a isize) { } // OK
+    //~^ WARN generic parameter `'a` shadows a parameter of an enclosing item
     fn h(a: Box<dyn for<'a> FnOnce(&'a isize)>) { } // OK
 }
 
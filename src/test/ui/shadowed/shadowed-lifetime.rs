@@ -16,7 +16,11 @@ impl<'a> Foo<'a> {
 
     fn not_shadow_in_item<'b>(&'b self) {
         struct Bar<'a, 'b>(&'a isize, &'b isize); // not a shadow, separate item
+        //~^ WARN generic parameter `'a` shadows a parameter of an enclosing item
+        //~| WARN generic parameter `'b` shadows a parameter of an enclosing item
         fn foo<'a, 'b>(x: &'a isize, y: &'b isize) { } // same
+        //~^ WARN generic parameter `'a` shadows a parameter of an enclosing item
+        //~| WARN generic parameter `'b` shadows a parameter of an enclosing item
     }
 }
 
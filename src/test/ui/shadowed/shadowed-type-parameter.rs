@@ -10,7 +10,11 @@ impl<T> Foo<T> {
 
     fn not_shadow_in_item<U>(&self) {
         struct Bar<T, U>(T,U); // not a shadow, separate item
+        //~^ WARN generic parameter `T` shadows a parameter of an enclosing item
+        //~| WARN generic parameter `U` shadows a parameter of an enclosing item
         fn foo<T, U>() {} // same
+        //~^ WARN generic parameter `T` shadows a parameter of an enclosing item
+        //~| WARN generic parameter `U` shadows a parameter of an enclosing item
     }
 }
 
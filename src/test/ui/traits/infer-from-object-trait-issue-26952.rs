@@ -1,6 +1,7 @@
 // run-pass
 #![allow(dead_code)]
 #![allow(unused_variables)]
+#![allow(empty_loops)]
 // Test that when we match a trait reference like `Foo<A>: Foo<_#0t>`,
 // we unify with `_#0t` with `A`. In this code, if we failed to do
 // that, then you get an unconstrained type-variable in `call`.
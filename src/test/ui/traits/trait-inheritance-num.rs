@@ -1,6 +1,7 @@
 // run-pass
 #![allow(dead_code)]
 #![allow(unused_variables)]
+#![allow(empty_loops)]
 // pretty-expanded FIXME #23616
 
 pub trait NumExt: PartialEq + PartialOrd {}
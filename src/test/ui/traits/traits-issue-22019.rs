@@ -7,6 +7,7 @@
 
 #![allow(missing_copy_implementations)]
 #![allow(unused_variables)]
+#![allow(empty_loops)]
 
 use std::borrow::ToOwned;
 
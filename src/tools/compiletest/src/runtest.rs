@@ -1684,6 +1684,8 @@ impl<'test> TestCx<'test> {
             rustc.arg("--extern").arg(format!("{}={}/{}", aux_name, aux_dir.display(), lib_name));
         }
 
+        // Lint levels set in the environment of whoever runs the tests would change their output.
+        rustc.env_remove("RUSTC_LINT_LEVELS");
         self.props.unset_rustc_env.clone().iter().fold(&mut rustc, |rustc, v| rustc.env_remove(v));
         rustc.envs(self.props.rustc_env.clone());
         self.compose_and_run(
@@ -1865,6 +1867,7 @@ impl<'test> TestCx<'test> {
                 }
                 rustc.arg("-Zui-testing");
                 rustc.arg("-Zdeduplicate-diagnostics=no");
+                rustc.arg("-Zsuggest-allow-attributes=off");
             }
             Ui => {
                 if !self.props.compile_flags.iter().any(|s| s.starts_with("--error-format")) {
@@ -1872,6 +1875,9 @@ impl<'test> TestCx<'test> {
                 }
                 rustc.arg("-Zui-testing");
                 rustc.arg("-Zdeduplicate-diagnostics=no");
+                // Keep the help telling where to allow each warning out of the expected output
+                // of the tests that aren't about it.
+                rustc.arg("-Zsuggest-allow-attributes=off");
             }
             MirOpt => {
                 rustc.args(&[